@@ -0,0 +1,41 @@
+//! Read-only helpers for rebuilding tapes off-chain.
+//!
+//! These wrap the `pda` and account layouts so clients don't have to
+//! re-derive addresses or cast raw account bytes themselves.
+
+use crate::error::TapeError;
+use crate::pda::{tape_pda, writer_pda};
+use crate::state::{Tape, TapeState, Writer};
+use crate::utils::to_name;
+use pinocchio::pubkey::Pubkey;
+
+/// Address and bump of the tape `authority` created under `name`.
+///
+/// Panics if `name` is longer than `NAME_LEN`, like `to_name`.
+pub fn tape_address(authority: Pubkey, name: &str) -> (Pubkey, u8) {
+    tape_pda(authority, &to_name(name))
+}
+
+/// Address and bump of the writer for `tape`.
+pub fn writer_address(tape: Pubkey) -> (Pubkey, u8) {
+    writer_pda(tape)
+}
+
+/// Reads a tape from raw account data.
+///
+/// Fails with `UnexpectedState` if the data isn't a tape, including a closed
+/// account or one holding a state the program never writes.
+pub fn parse_tape(data: &[u8]) -> Result<&Tape, TapeError> {
+    let tape = Tape::unpack(data).map_err(|_| TapeError::UnexpectedState)?;
+
+    if tape.state > TapeState::Finalized as u64 {
+        return Err(TapeError::UnexpectedState);
+    }
+
+    Ok(tape)
+}
+
+/// Reads a writer from raw account data.
+pub fn parse_writer(data: &[u8]) -> Result<&Writer, TapeError> {
+    Writer::unpack(data).map_err(|_| TapeError::UnexpectedState)
+}
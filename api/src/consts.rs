@@ -28,6 +28,26 @@ pub const MINT_SEED: &[u8] = &[152, 68, 212, 200, 25, 113, 221, 71];
 pub const METADATA_NAME:   &str = "TAPE";
 pub const METADATA_SYMBOL: &str = "TAPE";
 pub const METADATA_URI:    &str = "https://tapedrive.io/metadata.json";
+/// Maximum metadata field lengths, as enforced by Metaplex
+pub const METADATA_NAME_LEN:   usize = 32;
+pub const METADATA_SYMBOL_LEN: usize = 10;
+pub const METADATA_URI_LEN:    usize = 200;
+/// Capacity in bytes of a URI packed into `[u64; 32]`
+pub const PACKED_URI_LEN:      usize = 256;
+
+/// Name of the genesis tape
+pub const GENESIS_NAME:    &str = "genesis";
+/// The genesis tape name zero-padded to NAME_LEN, as used in its PDA seeds
+pub const GENESIS_NAME_BYTES: [u8; NAME_LEN] = {
+    let name = GENESIS_NAME.as_bytes();
+    let mut out = [0u8; NAME_LEN];
+    let mut i = 0;
+    while i < name.len() {
+        out[i] = name[i];
+        i += 1;
+    }
+    out
+};
 
 // ====================================================================
 // Merkle Tree Configuration
@@ -42,6 +62,10 @@ pub const TAPE_TREE_HEIGHT: usize = 10;
 /// Number of hashes in a Merkle proof for the tape tree
 pub const TAPE_PROOF_LEN: usize = TAPE_TREE_HEIGHT;
 
+// A proof holds one sibling per tree level; any other length fails to verify.
+const _: () = assert!(SEGMENT_PROOF_LEN == SEGMENT_TREE_HEIGHT);
+const _: () = assert!(TAPE_PROOF_LEN == TAPE_TREE_HEIGHT);
+
 // ====================================================================
 // Sizing
 // ====================================================================
@@ -54,6 +78,16 @@ pub const PACKED_SEGMENT_SIZE: usize = 152; // packx::SOLUTION_SIZE
 pub const MAX_SEGMENTS_PER_TAPE: usize = 1 << SEGMENT_TREE_HEIGHT - 1;
 /// Maximum number of tapes in a spool
 pub const MAX_TAPES_PER_SPOOL: usize = 1 << TAPE_TREE_HEIGHT - 1;
+/// Maximum number of segments appended by one write, about what fits in a transaction
+pub const MAX_SEGMENTS_PER_WRITE: usize = 8;
+/// Height of the subtree each writer shard accumulates
+pub const WRITER_SHARD_HEIGHT: usize = 15;
+/// Number of segments a writer shard holds
+pub const WRITER_SHARD_SEGMENTS: usize = 1 << WRITER_SHARD_HEIGHT;
+/// Maximum number of writer shards per tape, the first being the tape's own writer
+pub const MAX_WRITER_SHARDS: usize = MAX_SEGMENTS_PER_TAPE / WRITER_SHARD_SEGMENTS;
+/// Maximum number of tapes packed by one batched pack, keeping it inside the compute budget
+pub const MAX_TAPES_PER_PACK: usize = 8;
 
 // ====================================================================
 // Token Economics
@@ -67,8 +101,14 @@ pub const MAX_SUPPLY: u64 = 7_000_000 * ONE_TAPE;
 
 /// Minimum mining difficulty
 pub const MIN_MINING_DIFFICULTY: u64       = 1;
+/// Maximum mining difficulty, keeps the chain solvable under sustained fast blocks
+pub const MAX_MINING_DIFFICULTY: u64       = 32;
+/// Most the mining difficulty can move in a single epoch retarget
+pub const MAX_DIFFICULTY_STEP: u64         = 4;
 /// Minimum packing difficulty
 pub const MIN_PACKING_DIFFICULTY: u64      = 0;
+/// Maximum packing difficulty
+pub const MAX_PACKING_DIFFICULTY: u64      = 32;
 /// Minimum block participation required to solve a block
 pub const MIN_PARTICIPATION_TARGET: u64    = 1;
 /// Maximum block participation required to solve a block
@@ -77,14 +117,29 @@ pub const MAX_PARTICIPATION_TARGET: u64    = 100;
 pub const MIN_CONSISTENCY_MULTIPLIER: u64  = 1;
 /// Maximum reward scaling factor for miners
 pub const MAX_CONSISTENCY_MULTIPLIER: u64  = 32;
+/// Refundable lamports a miner locks on registration, on top of rent
+pub const MINER_DEPOSIT: u64               = 10_000_000;
+/// Lamports of the deposit forfeited for each invalid proof
+pub const MINER_SLASH_AMOUNT: u64          = MINER_DEPOSIT / 4;
+/// Maximum reward bonus, in basis points, for mining a just-written tape (0 disables it).
+/// Reserved out of the per-proof share of the reward rate, so it never adds to emissions
+pub const MAX_RECENCY_BONUS_BPS: u64       = 1_000;
+/// Slots after a tape's last write over which its recency bonus decays to zero
+pub const RECENCY_WINDOW_SLOTS: u64        = SLOTS_PER_BLOCK * EPOCH_BLOCKS;
 
 // ====================================================================
 // Time & Epoch Constants
 // ====================================================================
 /// Duration of one block in seconds (~1 minute)
 pub const BLOCK_DURATION_SECONDS: u64 = 60;
+/// Target duration of one Solana slot in milliseconds
+pub const SLOT_DURATION_MS: u64 = 400;
+/// Number of slots per block (~150)
+pub const SLOTS_PER_BLOCK: u64 = BLOCK_DURATION_SECONDS * 1000 / SLOT_DURATION_MS;
 /// Number of blocks per epoch (~10 minutes)
 pub const EPOCH_BLOCKS: u64 = 10;
+/// Number of epochs per year
+pub const EPOCHS_PER_YEAR: u64 = 365 * 24 * 60 / EPOCH_BLOCKS;
 /// Adjustment interval (in epochs)
 pub const ADJUSTMENT_INTERVAL: u64 = 50;
 
@@ -166,3 +221,46 @@ pub const TREASURY_ATA_BUMP: u8 =
         unsafe { &*(&pinocchio_associated_token_account::id() as *const Pubkey as *const [u8; 32]) },
     )
     .1;
+
+/// Metaplex token metadata program (metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s)
+pub const MPL_TOKEN_METADATA_ID: Pubkey = [
+    11, 112, 101, 177, 227, 209, 124, 69, 56, 157, 82, 127, 107, 4, 195, 205,
+    88, 184, 108, 115, 26, 160, 253, 181, 73, 182, 209, 188, 3, 248, 41, 70,
+];
+
+/// Metaplex metadata PDA for the mint (owned by the metadata program)
+pub const METADATA_ADDRESS: Pubkey =
+    ed25519::derive_program_address(
+        &[METADATA, &MPL_TOKEN_METADATA_ID, &MINT_ADDRESS],
+        &MPL_TOKEN_METADATA_ID,
+    )
+    .0;
+
+/// The genesis tape belongs to the protocol rather than whoever runs
+/// initialize, so it is seeded by the treasury and its address is fixed
+pub const GENESIS_TAPE_ADDRESS: Pubkey =
+    ed25519::derive_program_address(
+        &[TAPE, &TREASURY_ADDRESS, &GENESIS_NAME_BYTES],
+        &PROGRAM_ID,
+    )
+    .0;
+
+pub const GENESIS_WRITER_ADDRESS: Pubkey =
+    ed25519::derive_program_address(&[WRITER, &GENESIS_TAPE_ADDRESS], &PROGRAM_ID).0;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ProofPath, SegmentTree, TapeTree};
+
+    #[test]
+    fn proof_lengths_match_tree_heights() {
+        // One sibling hash per level of the tree
+        let segments = SegmentTree::new(&[b"segments"]);
+        assert_eq!(segments.get_depth() as usize, SEGMENT_PROOF_LEN);
+        assert_eq!(core::mem::size_of::<ProofPath>(), 32 * SEGMENT_PROOF_LEN);
+
+        let tapes = TapeTree::new(&[b"tapes"]);
+        assert_eq!(tapes.get_depth() as usize, TAPE_PROOF_LEN);
+    }
+}
@@ -0,0 +1,72 @@
+use crate::consts::*;
+use crate::state::Epoch;
+
+/// Estimated TAPE emitted over a year at the epoch's current reward rate.
+///
+/// The reward rate is paid per block, so this assumes every block of the
+/// year is solved at that rate. Saturates rather than overflowing.
+#[inline]
+pub const fn annualized(epoch: &Epoch) -> u64 {
+    epoch
+        .reward_rate
+        .saturating_mul(EPOCHS_PER_YEAR)
+        .saturating_mul(EPOCH_BLOCKS)
+}
+
+/// Share of `reward` paid at a miner's consistency multiplier, out of
+/// `MAX_CONSISTENCY_MULTIPLIER`.
+///
+/// The multiplier is clamped to its range rather than trusted, so a stored
+/// value that drifted out of it can't inflate or zero the reward.
+#[inline]
+pub fn scaled_reward(reward: u64, multiplier: u64) -> u64 {
+    let multiplier = multiplier.clamp(MIN_CONSISTENCY_MULTIPLIER, MAX_CONSISTENCY_MULTIPLIER);
+
+    reward
+        .saturating_mul(multiplier)
+        .saturating_div(MAX_CONSISTENCY_MULTIPLIER)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytemuck::Zeroable;
+
+    #[test]
+    fn annualized_at_genesis_rate() {
+        // Year one pays about one TAPE per block (one per minute)
+        let mut epoch = Epoch::zeroed();
+        epoch.reward_rate = ONE_TAPE;
+
+        assert_eq!(annualized(&epoch), 525_600 * ONE_TAPE);
+        assert!(annualized(&epoch) < MAX_SUPPLY);
+    }
+
+    #[test]
+    fn annualized_after_terminal_emission_is_zero() {
+        let epoch = Epoch::zeroed();
+        assert_eq!(annualized(&epoch), 0);
+    }
+
+    #[test]
+    fn annualized_saturates() {
+        let mut epoch = Epoch::zeroed();
+        epoch.reward_rate = u64::MAX;
+        assert_eq!(annualized(&epoch), u64::MAX);
+    }
+
+    #[test]
+    fn scaled_reward_clamps_out_of_range_multipliers() {
+        let reward = 32_000;
+
+        assert_eq!(
+            scaled_reward(reward, 0),
+            scaled_reward(reward, MIN_CONSISTENCY_MULTIPLIER)
+        );
+        assert_eq!(
+            scaled_reward(reward, MAX_CONSISTENCY_MULTIPLIER + 1),
+            reward
+        );
+        assert_eq!(scaled_reward(reward, u64::MAX), reward);
+    }
+}
@@ -1,7 +1,7 @@
 use pinocchio::program_error::ProgramError;
 
 #[repr(u32)]
-#[derive(Clone, PartialEq, shank::ShankType)]
+#[derive(Clone, Debug, PartialEq, shank::ShankType)]
 pub enum TapeError {
     // Unknown error
     UnknownError = 0,
@@ -14,6 +14,28 @@ pub enum TapeError {
     TapeTooLong             = 0x12,
     // The tape does not have enough rent
     InsufficientRent        = 0x13,
+    // The tape balance exceeds the lamports held by the tape account
+    BalanceMismatch         = 0x14,
+    // The tape name is empty
+    InvalidName             = 0x15,
+    // The signer is not the tape authority
+    Unauthorized            = 0x16,
+    // The tape has no segments to recall
+    EmptyTape               = 0x17,
+    // The signer cannot cover the rent for the new accounts
+    InsufficientFunds       = 0x18,
+    // The write carries no segment data
+    EmptyWrite              = 0x19,
+    // The write carries more than MAX_SEGMENTS_PER_WRITE segments
+    WriteTooLarge           = 0x1A,
+    // The tape root does not match the writer's segment tree
+    RootMismatch            = 0x1B,
+    // A segment holds more than SEGMENT_SIZE bytes
+    SegmentTooLarge         = 0x1C,
+    // The writer shard index is out of range or out of order
+    InvalidShard            = 0x1D,
+    // A writer shard before the last one is not full
+    ShardNotFull            = 0x1E,
 
     // The provided hash is invalid
     SolutionInvalid         = 0x20,
@@ -27,6 +49,12 @@ pub enum TapeError {
     ClaimTooLarge           = 0x24,
     // Computed commitment does not match the miner commitment
     CommitmentMismatch      = 0x25,
+    // The epoch holds a value the mining rules can never produce
+    InvalidEpochState       = 0x26,
+    // The recall proof depth does not match the segment tree
+    ProofLength             = 0x27,
+    // An invalid proof slashed the deposit, reported in `SlashEvent`
+    SolutionSlashed         = 0x28,
 
     // Faild to pack the tape into the spool
     SpoolPackFailed         = 0x30,
@@ -36,6 +64,8 @@ pub enum TapeError {
     SpoolTooManyTapes       = 0x32,
     // Spool commit failed
     SpoolCommitFailed       = 0x33,
+    // The recalled tape is not in the miner's spool
+    SpoolMissingTape        = 0x34,
 }
 
 impl From<TapeError> for ProgramError {
@@ -43,3 +73,49 @@ impl From<TapeError> for ProgramError {
         Self::Custom(e as u32)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_codes_are_stable() {
+        // Clients match on these codes; never renumber an existing variant
+        let codes = [
+            (TapeError::UnknownError, 0x00),
+            (TapeError::UnexpectedState, 0x10),
+            (TapeError::WriteFailed, 0x11),
+            (TapeError::TapeTooLong, 0x12),
+            (TapeError::InsufficientRent, 0x13),
+            (TapeError::BalanceMismatch, 0x14),
+            (TapeError::InvalidName, 0x15),
+            (TapeError::Unauthorized, 0x16),
+            (TapeError::EmptyTape, 0x17),
+            (TapeError::InsufficientFunds, 0x18),
+            (TapeError::EmptyWrite, 0x19),
+            (TapeError::WriteTooLarge, 0x1A),
+            (TapeError::RootMismatch, 0x1B),
+            (TapeError::SegmentTooLarge, 0x1C),
+            (TapeError::InvalidShard, 0x1D),
+            (TapeError::ShardNotFull, 0x1E),
+            (TapeError::SolutionInvalid, 0x20),
+            (TapeError::UnexpectedTape, 0x21),
+            (TapeError::SolutionTooEasy, 0x22),
+            (TapeError::SolutionTooEarly, 0x23),
+            (TapeError::ClaimTooLarge, 0x24),
+            (TapeError::CommitmentMismatch, 0x25),
+            (TapeError::InvalidEpochState, 0x26),
+            (TapeError::ProofLength, 0x27),
+            (TapeError::SolutionSlashed, 0x28),
+            (TapeError::SpoolPackFailed, 0x30),
+            (TapeError::SpoolUnpackFailed, 0x31),
+            (TapeError::SpoolTooManyTapes, 0x32),
+            (TapeError::SpoolCommitFailed, 0x33),
+            (TapeError::SpoolMissingTape, 0x34),
+        ];
+
+        for (error, code) in codes {
+            assert_eq!(ProgramError::from(error), ProgramError::Custom(code));
+        }
+    }
+}
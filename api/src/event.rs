@@ -9,6 +9,21 @@ pub enum EventType {
     WriteEvent,
     UpdateEvent,
     FinalizeEvent,
+
+    SpoolCreateEvent,
+    SpoolPackEvent,
+    SpoolCommitEvent,
+
+    SegmentUpdateEvent,
+    HeaderUpdateEvent,
+
+    ChainAdvancedEvent,
+
+    CreateEvent,
+    MineEvent,
+
+    EpochReportEvent,
+    SlashEvent,
 }
 
 #[repr(C)]
@@ -61,8 +76,7 @@ impl WriteEvent {
     }
 
     pub fn log(&self) {
-        let bytes = self.to_bytes();
-        // pinocchio::msg!(bytes.to_string());
+        pinocchio::log::sol_log_data(&[&self.to_bytes()]);
     }
 }
 
@@ -115,8 +129,7 @@ impl UpdateEvent {
     }
 
     pub fn log(&self) {
-        let bytes = self.to_bytes();
-        //TODO: add logging here
+        pinocchio::log::sol_log_data(&[&self.to_bytes()]);
     }
 }
 
@@ -124,6 +137,7 @@ impl UpdateEvent {
 #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
 pub struct FinalizeEvent {
     pub tape: u64,
+    pub merkle_root: [u8; 32],
     pub address: [u8; 32],
 }
 
@@ -134,8 +148,8 @@ impl FinalizeEvent {
         core::mem::size_of::<Self>() + Self::DISCRIMINATOR_SIZE
     }
 
-    pub fn to_bytes(&self) -> [u8; 48] {
-        let mut result = [0u8; 48]; // 8 bytes discriminator + 40 bytes struct
+    pub fn to_bytes(&self) -> [u8; 80] {
+        let mut result = [0u8; 80]; // 8 bytes discriminator + 72 bytes struct
 
         // Add 8-byte discriminator (first byte is the enum variant, rest are zeros)
         result[0] = EventType::FinalizeEvent as u8;
@@ -168,7 +182,637 @@ impl FinalizeEvent {
     }
 
     pub fn log(&self) {
-        let bytes = self.to_bytes();
-        //TODO: add logging here
+        pinocchio::log::sol_log_data(&[&self.to_bytes()]);
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct SpoolCreateEvent {
+    pub number: u64,
+    pub authority: [u8; 32],
+    pub address: [u8; 32],
+}
+
+impl SpoolCreateEvent {
+    const DISCRIMINATOR_SIZE: usize = 8;
+
+    pub fn size_of() -> usize {
+        core::mem::size_of::<Self>() + Self::DISCRIMINATOR_SIZE
+    }
+
+    pub fn to_bytes(&self) -> [u8; 80] {
+        let mut result = [0u8; 80]; // 8 bytes discriminator + 72 bytes struct
+
+        // Add 8-byte discriminator (first byte is the enum variant, rest are zeros)
+        result[0] = EventType::SpoolCreateEvent as u8;
+        // bytes 1-7 remain as zeros
+
+        // Add struct bytes starting at index 8
+        let struct_bytes = bytemuck::bytes_of(self);
+        result[8..8 + struct_bytes.len()].copy_from_slice(struct_bytes);
+
+        result
+    }
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<&Self, &'static str> {
+        if data.len() < 8 {
+            return Err("Data too short for discriminator");
+        }
+
+        let discriminator = data[0];
+        if discriminator != EventType::SpoolCreateEvent as u8 {
+            return Err("Invalid discriminator");
+        }
+
+        let struct_size = core::mem::size_of::<Self>();
+        if data.len() < 8 + struct_size {
+            return Err("Data too short for struct");
+        }
+
+        bytemuck::try_from_bytes::<Self>(&data[8..8 + struct_size])
+            .map_err(|_| "Invalid struct data")
+    }
+
+    pub fn log(&self) {
+        pinocchio::log::sol_log_data(&[&self.to_bytes()]);
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct SpoolPackEvent {
+    pub tape: u64,
+    pub total_tapes: u64,
+    pub root: [u8; 32],
+    pub address: [u8; 32],
+}
+
+impl SpoolPackEvent {
+    const DISCRIMINATOR_SIZE: usize = 8;
+
+    pub fn size_of() -> usize {
+        core::mem::size_of::<Self>() + Self::DISCRIMINATOR_SIZE
+    }
+
+    pub fn to_bytes(&self) -> [u8; 88] {
+        let mut result = [0u8; 88]; // 8 bytes discriminator + 80 bytes struct
+
+        // Add 8-byte discriminator (first byte is the enum variant, rest are zeros)
+        result[0] = EventType::SpoolPackEvent as u8;
+        // bytes 1-7 remain as zeros
+
+        // Add struct bytes starting at index 8
+        let struct_bytes = bytemuck::bytes_of(self);
+        result[8..8 + struct_bytes.len()].copy_from_slice(struct_bytes);
+
+        result
+    }
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<&Self, &'static str> {
+        if data.len() < 8 {
+            return Err("Data too short for discriminator");
+        }
+
+        let discriminator = data[0];
+        if discriminator != EventType::SpoolPackEvent as u8 {
+            return Err("Invalid discriminator");
+        }
+
+        let struct_size = core::mem::size_of::<Self>();
+        if data.len() < 8 + struct_size {
+            return Err("Data too short for struct");
+        }
+
+        bytemuck::try_from_bytes::<Self>(&data[8..8 + struct_size])
+            .map_err(|_| "Invalid struct data")
+    }
+
+    pub fn log(&self) {
+        pinocchio::log::sol_log_data(&[&self.to_bytes()]);
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct SpoolCommitEvent {
+    pub value: [u8; 32],
+    pub address: [u8; 32],
+}
+
+impl SpoolCommitEvent {
+    const DISCRIMINATOR_SIZE: usize = 8;
+
+    pub fn size_of() -> usize {
+        core::mem::size_of::<Self>() + Self::DISCRIMINATOR_SIZE
+    }
+
+    pub fn to_bytes(&self) -> [u8; 72] {
+        let mut result = [0u8; 72]; // 8 bytes discriminator + 64 bytes struct
+
+        // Add 8-byte discriminator (first byte is the enum variant, rest are zeros)
+        result[0] = EventType::SpoolCommitEvent as u8;
+        // bytes 1-7 remain as zeros
+
+        // Add struct bytes starting at index 8
+        let struct_bytes = bytemuck::bytes_of(self);
+        result[8..8 + struct_bytes.len()].copy_from_slice(struct_bytes);
+
+        result
+    }
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<&Self, &'static str> {
+        if data.len() < 8 {
+            return Err("Data too short for discriminator");
+        }
+
+        let discriminator = data[0];
+        if discriminator != EventType::SpoolCommitEvent as u8 {
+            return Err("Invalid discriminator");
+        }
+
+        let struct_size = core::mem::size_of::<Self>();
+        if data.len() < 8 + struct_size {
+            return Err("Data too short for struct");
+        }
+
+        bytemuck::try_from_bytes::<Self>(&data[8..8 + struct_size])
+            .map_err(|_| "Invalid struct data")
+    }
+
+    pub fn log(&self) {
+        pinocchio::log::sol_log_data(&[&self.to_bytes()]);
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct SegmentUpdateEvent {
+    pub segment_number: u64,
+    pub old_root: [u8; 32],
+    pub new_root: [u8; 32],
+    pub address: [u8; 32],
+}
+
+impl SegmentUpdateEvent {
+    const DISCRIMINATOR_SIZE: usize = 8;
+
+    pub fn size_of() -> usize {
+        core::mem::size_of::<Self>() + Self::DISCRIMINATOR_SIZE
+    }
+
+    pub fn to_bytes(&self) -> [u8; 112] {
+        let mut result = [0u8; 112]; // 8 bytes discriminator + 104 bytes struct
+
+        // Add 8-byte discriminator (first byte is the enum variant, rest are zeros)
+        result[0] = EventType::SegmentUpdateEvent as u8;
+        // bytes 1-7 remain as zeros
+
+        // Add struct bytes starting at index 8
+        let struct_bytes = bytemuck::bytes_of(self);
+        result[8..8 + struct_bytes.len()].copy_from_slice(struct_bytes);
+
+        result
+    }
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<&Self, &'static str> {
+        if data.len() < 8 {
+            return Err("Data too short for discriminator");
+        }
+
+        let discriminator = data[0];
+        if discriminator != EventType::SegmentUpdateEvent as u8 {
+            return Err("Invalid discriminator");
+        }
+
+        let struct_size = core::mem::size_of::<Self>();
+        if data.len() < 8 + struct_size {
+            return Err("Data too short for struct");
+        }
+
+        bytemuck::try_from_bytes::<Self>(&data[8..8 + struct_size])
+            .map_err(|_| "Invalid struct data")
+    }
+
+    pub fn log(&self) {
+        pinocchio::log::sol_log_data(&[&self.to_bytes()]);
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct HeaderUpdateEvent {
+    pub address: [u8; 32],
+}
+
+impl HeaderUpdateEvent {
+    const DISCRIMINATOR_SIZE: usize = 8;
+
+    pub fn size_of() -> usize {
+        core::mem::size_of::<Self>() + Self::DISCRIMINATOR_SIZE
+    }
+
+    pub fn to_bytes(&self) -> [u8; 40] {
+        let mut result = [0u8; 40]; // 8 bytes discriminator + 32 bytes struct
+
+        // Add 8-byte discriminator (first byte is the enum variant, rest are zeros)
+        result[0] = EventType::HeaderUpdateEvent as u8;
+        // bytes 1-7 remain as zeros
+
+        // Add struct bytes starting at index 8
+        let struct_bytes = bytemuck::bytes_of(self);
+        result[8..8 + struct_bytes.len()].copy_from_slice(struct_bytes);
+
+        result
+    }
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<&Self, &'static str> {
+        if data.len() < 8 {
+            return Err("Data too short for discriminator");
+        }
+
+        let discriminator = data[0];
+        if discriminator != EventType::HeaderUpdateEvent as u8 {
+            return Err("Invalid discriminator");
+        }
+
+        let struct_size = core::mem::size_of::<Self>();
+        if data.len() < 8 + struct_size {
+            return Err("Data too short for struct");
+        }
+
+        bytemuck::try_from_bytes::<Self>(&data[8..8 + struct_size])
+            .map_err(|_| "Invalid struct data")
+    }
+
+    pub fn log(&self) {
+        pinocchio::log::sol_log_data(&[&self.to_bytes()]);
+    }
+}
+
+/// Block and epoch state after a proof, so indexers don't have to diff accounts
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct ChainAdvancedEvent {
+    pub new_block: u64,
+    pub new_epoch: u64,
+    // 1 if the proof closed the block, 0 otherwise
+    pub block_advanced: u64,
+    // 1 if the proof closed the epoch, 0 otherwise
+    pub epoch_advanced: u64,
+}
+
+impl ChainAdvancedEvent {
+    const DISCRIMINATOR_SIZE: usize = 8;
+
+    pub fn size_of() -> usize {
+        core::mem::size_of::<Self>() + Self::DISCRIMINATOR_SIZE
+    }
+
+    pub fn to_bytes(&self) -> [u8; 40] {
+        let mut result = [0u8; 40]; // 8 bytes discriminator + 32 bytes struct
+
+        // Add 8-byte discriminator (first byte is the enum variant, rest are zeros)
+        result[0] = EventType::ChainAdvancedEvent as u8;
+        // bytes 1-7 remain as zeros
+
+        // Add struct bytes starting at index 8
+        let struct_bytes = bytemuck::bytes_of(self);
+        result[8..8 + struct_bytes.len()].copy_from_slice(struct_bytes);
+
+        result
+    }
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<&Self, &'static str> {
+        if data.len() < 8 {
+            return Err("Data too short for discriminator");
+        }
+
+        let discriminator = data[0];
+        if discriminator != EventType::ChainAdvancedEvent as u8 {
+            return Err("Invalid discriminator");
+        }
+
+        let struct_size = core::mem::size_of::<Self>();
+        if data.len() < 8 + struct_size {
+            return Err("Data too short for struct");
+        }
+
+        bytemuck::try_from_bytes::<Self>(&data[8..8 + struct_size])
+            .map_err(|_| "Invalid struct data")
+    }
+
+    pub fn log(&self) {
+        pinocchio::log::sol_log_data(&[&self.to_bytes()]);
+    }
+}
+
+/// A new tape and its writer, as created by its authority
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct CreateEvent {
+    pub first_slot: u64,
+    pub authority: [u8; 32],
+    pub address: [u8; 32],
+}
+
+impl CreateEvent {
+    const DISCRIMINATOR_SIZE: usize = 8;
+
+    pub fn size_of() -> usize {
+        core::mem::size_of::<Self>() + Self::DISCRIMINATOR_SIZE
+    }
+
+    pub fn to_bytes(&self) -> [u8; 80] {
+        let mut result = [0u8; 80]; // 8 bytes discriminator + 72 bytes struct
+
+        // Add 8-byte discriminator (first byte is the enum variant, rest are zeros)
+        result[0] = EventType::CreateEvent as u8;
+        // bytes 1-7 remain as zeros
+
+        // Add struct bytes starting at index 8
+        let struct_bytes = bytemuck::bytes_of(self);
+        result[8..8 + struct_bytes.len()].copy_from_slice(struct_bytes);
+
+        result
+    }
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<&Self, &'static str> {
+        if data.len() < 8 {
+            return Err("Data too short for discriminator");
+        }
+
+        let discriminator = data[0];
+        if discriminator != EventType::CreateEvent as u8 {
+            return Err("Invalid discriminator");
+        }
+
+        let struct_size = core::mem::size_of::<Self>();
+        if data.len() < 8 + struct_size {
+            return Err("Data too short for struct");
+        }
+
+        bytemuck::try_from_bytes::<Self>(&data[8..8 + struct_size])
+            .map_err(|_| "Invalid struct data")
+    }
+
+    pub fn log(&self) {
+        pinocchio::log::sol_log_data(&[&self.to_bytes()]);
+    }
+}
+
+/// A successful proof and the reward it earned
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct MineEvent {
+    pub tape: u64,
+    pub reward: u64,
+    // Block the proof was counted towards, before any advance
+    pub block: u64,
+    pub miner: [u8; 32],
+}
+
+impl MineEvent {
+    const DISCRIMINATOR_SIZE: usize = 8;
+
+    pub fn size_of() -> usize {
+        core::mem::size_of::<Self>() + Self::DISCRIMINATOR_SIZE
+    }
+
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut result = [0u8; 64]; // 8 bytes discriminator + 56 bytes struct
+
+        // Add 8-byte discriminator (first byte is the enum variant, rest are zeros)
+        result[0] = EventType::MineEvent as u8;
+        // bytes 1-7 remain as zeros
+
+        // Add struct bytes starting at index 8
+        let struct_bytes = bytemuck::bytes_of(self);
+        result[8..8 + struct_bytes.len()].copy_from_slice(struct_bytes);
+
+        result
+    }
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<&Self, &'static str> {
+        if data.len() < 8 {
+            return Err("Data too short for discriminator");
+        }
+
+        let discriminator = data[0];
+        if discriminator != EventType::MineEvent as u8 {
+            return Err("Invalid discriminator");
+        }
+
+        let struct_size = core::mem::size_of::<Self>();
+        if data.len() < 8 + struct_size {
+            return Err("Data too short for struct");
+        }
+
+        bytemuck::try_from_bytes::<Self>(&data[8..8 + struct_size])
+            .map_err(|_| "Invalid struct data")
+    }
+
+    pub fn log(&self) {
+        pinocchio::log::sol_log_data(&[&self.to_bytes()]);
+    }
+}
+
+/// Snapshot of the current epoch, logged on request for light clients
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct EpochReportEvent {
+    pub number: u64,
+    pub progress: u64,
+    pub mining_difficulty: u64,
+    pub packing_difficulty: u64,
+    pub target_participation: u64,
+    pub reward_rate: u64,
+    pub epoch_rewards_distributed: u64,
+    pub last_epoch_at: i64,
+}
+
+impl EpochReportEvent {
+    const DISCRIMINATOR_SIZE: usize = 8;
+
+    pub fn size_of() -> usize {
+        core::mem::size_of::<Self>() + Self::DISCRIMINATOR_SIZE
+    }
+
+    pub fn to_bytes(&self) -> [u8; 72] {
+        let mut result = [0u8; 72]; // 8 bytes discriminator + 64 bytes struct
+
+        // Add 8-byte discriminator (first byte is the enum variant, rest are zeros)
+        result[0] = EventType::EpochReportEvent as u8;
+        // bytes 1-7 remain as zeros
+
+        // Add struct bytes starting at index 8
+        let struct_bytes = bytemuck::bytes_of(self);
+        result[8..8 + struct_bytes.len()].copy_from_slice(struct_bytes);
+
+        result
+    }
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<&Self, &'static str> {
+        if data.len() < 8 {
+            return Err("Data too short for discriminator");
+        }
+
+        let discriminator = data[0];
+        if discriminator != EventType::EpochReportEvent as u8 {
+            return Err("Invalid discriminator");
+        }
+
+        let struct_size = core::mem::size_of::<Self>();
+        if data.len() < 8 + struct_size {
+            return Err("Data too short for struct");
+        }
+
+        bytemuck::try_from_bytes::<Self>(&data[8..8 + struct_size])
+            .map_err(|_| "Invalid struct data")
+    }
+
+    pub fn log(&self) {
+        pinocchio::log::sol_log_data(&[&self.to_bytes()]);
+    }
+}
+
+/// An invalid proof and the part of the deposit it cost, already paid to
+/// the treasury
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct SlashEvent {
+    // Always `TapeError::SolutionSlashed`, so clients can match it like an error
+    pub error: u64,
+    pub amount: u64,
+    // Deposit left after the slash
+    pub deposit: u64,
+    pub miner: [u8; 32],
+}
+
+impl SlashEvent {
+    const DISCRIMINATOR_SIZE: usize = 8;
+
+    pub fn size_of() -> usize {
+        core::mem::size_of::<Self>() + Self::DISCRIMINATOR_SIZE
+    }
+
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut result = [0u8; 64]; // 8 bytes discriminator + 56 bytes struct
+
+        // Add 8-byte discriminator (first byte is the enum variant, rest are zeros)
+        result[0] = EventType::SlashEvent as u8;
+        // bytes 1-7 remain as zeros
+
+        // Add struct bytes starting at index 8
+        let struct_bytes = bytemuck::bytes_of(self);
+        result[8..8 + struct_bytes.len()].copy_from_slice(struct_bytes);
+
+        result
+    }
+
+    pub fn try_from_bytes(data: &[u8]) -> Result<&Self, &'static str> {
+        if data.len() < 8 {
+            return Err("Data too short for discriminator");
+        }
+
+        let discriminator = data[0];
+        if discriminator != EventType::SlashEvent as u8 {
+            return Err("Invalid discriminator");
+        }
+
+        let struct_size = core::mem::size_of::<Self>();
+        if data.len() < 8 + struct_size {
+            return Err("Data too short for struct");
+        }
+
+        bytemuck::try_from_bytes::<Self>(&data[8..8 + struct_size])
+            .map_err(|_| "Invalid struct data")
+    }
+
+    pub fn log(&self) {
+        pinocchio::log::sol_log_data(&[&self.to_bytes()]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn event_discriminators_are_stable() {
+        // Indexers match on the first byte; never renumber an existing variant
+        let codes = [
+            (EventType::WriteEvent, 1),
+            (EventType::UpdateEvent, 2),
+            (EventType::FinalizeEvent, 3),
+            (EventType::SpoolCreateEvent, 4),
+            (EventType::SpoolPackEvent, 5),
+            (EventType::SpoolCommitEvent, 6),
+            (EventType::SegmentUpdateEvent, 7),
+            (EventType::HeaderUpdateEvent, 8),
+            (EventType::ChainAdvancedEvent, 9),
+            (EventType::CreateEvent, 10),
+            (EventType::MineEvent, 11),
+            (EventType::EpochReportEvent, 12),
+            (EventType::SlashEvent, 13),
+        ];
+
+        for (event, code) in codes {
+            assert_eq!(event as u8, code);
+        }
+    }
+
+    #[test]
+    fn mine_event_round_trip() {
+        let event = MineEvent {
+            tape: 7,
+            reward: 1_000,
+            block: 42,
+            miner: [3; 32],
+        };
+
+        let bytes = event.to_bytes();
+        assert_eq!(bytes.len(), MineEvent::size_of());
+        assert_eq!(MineEvent::try_from_bytes(&bytes), Ok(&event));
+
+        // Another event's payload is rejected
+        assert!(CreateEvent::try_from_bytes(&bytes).is_err());
+        assert!(MineEvent::try_from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn epoch_report_event_round_trip() {
+        let event = EpochReportEvent {
+            number: 3,
+            progress: 4,
+            mining_difficulty: 5,
+            packing_difficulty: 6,
+            target_participation: 7,
+            reward_rate: 8,
+            epoch_rewards_distributed: 9,
+            last_epoch_at: -10,
+        };
+
+        let bytes = event.to_bytes();
+        assert_eq!(bytes.len(), EpochReportEvent::size_of());
+        assert_eq!(EpochReportEvent::try_from_bytes(&bytes), Ok(&event));
+        assert!(MineEvent::try_from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn slash_event_round_trip() {
+        let event = SlashEvent {
+            error: crate::error::TapeError::SolutionSlashed as u64,
+            amount: 1_000,
+            deposit: 2_000,
+            miner: [3; 32],
+        };
+
+        let bytes = event.to_bytes();
+        assert_eq!(bytes.len(), SlashEvent::size_of());
+        assert_eq!(SlashEvent::try_from_bytes(&bytes), Ok(&event));
+
+        // Same size as a mine event, but never mistaken for one
+        assert!(MineEvent::try_from_bytes(&bytes).is_err());
     }
 }
@@ -40,17 +40,42 @@ pub struct Write {
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct Finalize {}
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct FinalizeWithHeader {
+    pub header: [u8; HEADER_SIZE],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct Close {}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct Subsidize {
     pub amount: [u8; 8],
 }
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct TransferAuthority {
+    pub new_authority: Pubkey,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct CreateShard {
+    pub shard: [u8; 8],
+}
+
 /// Instruction discriminators (must match TapeInstruction enum in program)
 pub const DISCRIMINATOR_CREATE: u8 = 0x10;
 pub const DISCRIMINATOR_WRITE: u8 = 0x11;
 pub const DISCRIMINATOR_FINALIZE: u8 = 0x13;
 pub const DISCRIMINATOR_SUBSIDIZE: u8 = 0x15;
+pub const DISCRIMINATOR_CLOSE: u8 = 0x17;
+pub const DISCRIMINATOR_TRANSFER_AUTHORITY: u8 = 0x18;
+pub const DISCRIMINATOR_CREATE_SHARD: u8 = 0x19;
 
 /// Build instruction data for "create tape"
 ///
@@ -106,6 +131,37 @@ pub fn build_finalize_ix_data(data_buffer: &mut [u8]) -> usize {
     data_len
 }
 
+/// Build instruction data for "finalize tape", also setting its header
+///
+/// Returns: instruction_data_length
+#[inline(always)]
+pub fn build_finalize_with_header_ix_data(
+    header: &[u8; HEADER_SIZE],
+    data_buffer: &mut [u8],
+) -> usize {
+    let data_len = 1 + core::mem::size_of::<FinalizeWithHeader>();
+    assert!(data_buffer.len() >= data_len, "Data buffer too small");
+
+    data_buffer[0] = DISCRIMINATOR_FINALIZE;
+    data_buffer[1..data_len].copy_from_slice(bytes_of(&FinalizeWithHeader { header: *header }));
+
+    data_len
+}
+
+/// Build instruction data for "close tape"
+///
+/// Returns: instruction_data_length
+#[inline(always)]
+pub fn build_close_ix_data(data_buffer: &mut [u8]) -> usize {
+    let data_len = 1 + core::mem::size_of::<Close>();
+    assert!(data_buffer.len() >= data_len, "Data buffer too small");
+
+    data_buffer[0] = DISCRIMINATOR_CLOSE;
+    data_buffer[1..data_len].copy_from_slice(bytes_of(&Close {}));
+
+    data_len
+}
+
 /// Build instruction data for "subsidize tape"
 ///
 /// Returns: instruction_data_length
@@ -122,11 +178,52 @@ pub fn build_subsidize_ix_data(amount: u64, data_buffer: &mut [u8]) -> usize {
     data_len
 }
 
+/// Build instruction data for "transfer tape authority"
+///
+/// Returns: instruction_data_length
+#[inline(always)]
+pub fn build_transfer_authority_ix_data(new_authority: &Pubkey, data_buffer: &mut [u8]) -> usize {
+    let data_len = 1 + core::mem::size_of::<TransferAuthority>();
+    assert!(data_buffer.len() >= data_len, "Data buffer too small");
+
+    data_buffer[0] = DISCRIMINATOR_TRANSFER_AUTHORITY;
+    data_buffer[1..data_len].copy_from_slice(bytes_of(&TransferAuthority {
+        new_authority: *new_authority,
+    }));
+
+    data_len
+}
+
+/// Build instruction data for "create writer shard"
+///
+/// Returns: (instruction_data_length, shard_writer_pda)
+#[inline(always)]
+pub fn build_create_shard_ix_data(
+    tape: &Pubkey,
+    shard: u64,
+    data_buffer: &mut [u8],
+) -> (usize, Pubkey) {
+    let (writer_address, _writer_bump) = writer_shard_pda(*tape, shard);
+
+    let data_len = 1 + core::mem::size_of::<CreateShard>();
+    assert!(data_buffer.len() >= data_len, "Data buffer too small");
+
+    data_buffer[0] = DISCRIMINATOR_CREATE_SHARD;
+    data_buffer[1..data_len].copy_from_slice(bytes_of(&CreateShard {
+        shard: shard.to_le_bytes(),
+    }));
+
+    (data_len, writer_address)
+}
+
 // Helper constants for account counts
 pub const CREATE_ACCOUNTS_COUNT: usize = 6;
 pub const WRITE_ACCOUNTS_COUNT: usize = 3;
 pub const FINALIZE_ACCOUNTS_COUNT: usize = 6;
-pub const SUBSIDIZE_ACCOUNTS_COUNT: usize = 5;
+pub const SUBSIDIZE_ACCOUNTS_COUNT: usize = 3;
+pub const CLOSE_ACCOUNTS_COUNT: usize = 3;
+pub const TRANSFER_AUTHORITY_ACCOUNTS_COUNT: usize = 2;
+pub const CREATE_SHARD_ACCOUNTS_COUNT: usize = 5;
 
 // Re-export commonly used constants
 pub use crate::consts::{ARCHIVE_ADDRESS, TREASURY_ATA};
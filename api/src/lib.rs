@@ -1,7 +1,13 @@
 #![no_std]
 
+#[cfg(feature = "std")]
+extern crate std;
+
 pub mod account;
+#[cfg(feature = "std")]
+pub mod client;
 pub mod consts;
+pub mod emission;
 pub mod error;
 pub mod event;
 pub mod instruction;
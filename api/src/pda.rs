@@ -83,6 +83,35 @@ pub fn mint_pda() -> (Pubkey, u8) {
 //     )
 // }
 
+/// Addresses of the protocol's singleton accounts, as passed to initialize.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ProtocolPdas {
+    pub archive: Pubkey,
+    pub epoch: Pubkey,
+    pub block: Pubkey,
+    pub mint: Pubkey,
+    pub treasury: Pubkey,
+    pub treasury_ata: Pubkey,
+    pub metadata: Pubkey,
+    pub genesis_tape: Pubkey,
+    pub genesis_writer: Pubkey,
+}
+
+/// All singleton PDAs, read from the compile-time address constants.
+pub const fn protocol_pdas() -> ProtocolPdas {
+    ProtocolPdas {
+        archive: ARCHIVE_ADDRESS,
+        epoch: EPOCH_ADDRESS,
+        block: BLOCK_ADDRESS,
+        mint: MINT_ADDRESS,
+        treasury: TREASURY_ADDRESS,
+        treasury_ata: TREASURY_ATA,
+        metadata: METADATA_ADDRESS,
+        genesis_tape: GENESIS_TAPE_ADDRESS,
+        genesis_writer: GENESIS_WRITER_ADDRESS,
+    }
+}
+
 pub fn tape_pda(authority: Pubkey, name: &[u8; NAME_LEN]) -> (Pubkey, u8) {
     find_program_address(&[TAPE, authority.as_ref(), name.as_ref()], &crate::id())
 }
@@ -91,6 +120,25 @@ pub fn writer_pda(tape: Pubkey) -> (Pubkey, u8) {
     find_program_address(&[WRITER, tape.as_ref()], &crate::id())
 }
 
+/// Writer for shard `shard` of `tape`. Shard 0 is the tape's own writer.
+pub fn writer_shard_pda(tape: Pubkey, shard: u64) -> (Pubkey, u8) {
+    if shard == 0 {
+        return writer_pda(tape);
+    }
+
+    find_program_address(
+        &[WRITER, tape.as_ref(), shard.to_le_bytes().as_ref()],
+        &crate::id(),
+    )
+}
+
+/// Tape and writer PDAs with their bumps, as (tape, tape_bump, writer, writer_bump).
+pub fn tape_and_writer(authority: Pubkey, name: &[u8; NAME_LEN]) -> (Pubkey, u8, Pubkey, u8) {
+    let (tape, tape_bump) = tape_pda(authority, name);
+    let (writer, writer_bump) = writer_pda(tape);
+    (tape, tape_bump, writer, writer_bump)
+}
+
 pub fn miner_pda(authority: Pubkey, name: [u8; NAME_LEN]) -> (Pubkey, u8) {
     find_program_address(&[MINER, authority.as_ref(), name.as_ref()], &crate::id())
 }
@@ -136,4 +184,52 @@ mod tests {
         // let (pda, _bump) = treasury_ata();
         // assert_eq!(pda, TREASURY_ATA);
     }
+
+    #[test]
+    fn test_protocol_pdas_match_consts() {
+        let pdas = protocol_pdas();
+        assert_eq!(pdas.archive, ARCHIVE_ADDRESS);
+        assert_eq!(pdas.epoch, EPOCH_ADDRESS);
+        assert_eq!(pdas.block, BLOCK_ADDRESS);
+        assert_eq!(pdas.mint, MINT_ADDRESS);
+        assert_eq!(pdas.treasury, TREASURY_ADDRESS);
+        assert_eq!(pdas.treasury_ata, TREASURY_ATA);
+        assert_eq!(pdas.metadata, METADATA_ADDRESS);
+
+        // As above, check the metadata const against a runtime derivation
+        let (pda, _bump) = find_program_address(
+            &[
+                METADATA,
+                MPL_TOKEN_METADATA_ID.as_ref(),
+                MINT_ADDRESS.as_ref(),
+            ],
+            &MPL_TOKEN_METADATA_ID,
+        );
+        assert_eq!(pda, METADATA_ADDRESS);
+
+        // The genesis tape is the treasury's, like any tape it created
+        let (tape, _, writer, _) = tape_and_writer(TREASURY_ADDRESS, &GENESIS_NAME_BYTES);
+        assert_eq!(pdas.genesis_tape, tape);
+        assert_eq!(pdas.genesis_writer, writer);
+        assert_eq!(&GENESIS_NAME_BYTES, &crate::utils::to_name(GENESIS_NAME));
+    }
+
+    #[test]
+    fn test_tape_and_writer() {
+        let authority = [7; 32];
+        let name = [1; NAME_LEN];
+
+        let (tape, tape_bump, writer, writer_bump) = tape_and_writer(authority, &name);
+        assert_eq!((tape, tape_bump), tape_pda(authority, &name));
+        assert_eq!((writer, writer_bump), writer_pda(tape));
+    }
+
+    #[test]
+    fn test_writer_shard_pda() {
+        let tape = [7; 32];
+
+        assert_eq!(writer_shard_pda(tape, 0), writer_pda(tape));
+        assert_ne!(writer_shard_pda(tape, 1).0, writer_pda(tape).0);
+        assert_ne!(writer_shard_pda(tape, 1).0, writer_shard_pda(tape, 2).0);
+    }
 }
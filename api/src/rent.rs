@@ -3,6 +3,21 @@ use crate::state::{Archive, Tape};
 
 const BLOCKS_PER_YEAR: u64 = 60 * 60 * 24 * 365 / BLOCK_DURATION_SECONDS;
 
+/// Block containing `slot`, assuming slots of `SLOT_DURATION_MS`.
+#[inline]
+pub const fn block_from_slot(slot: u64) -> u64 {
+    slot / SLOTS_PER_BLOCK
+}
+
+/// Number of whole blocks between `from_slot` and `to_slot`.
+///
+/// Rent accrues per block, so this is the conversion to use when only slots
+/// are known (e.g. `Tape::tail_slot` and `Clock::slot`).
+#[inline]
+pub const fn blocks_elapsed(from_slot: u64, to_slot: u64) -> u64 {
+    block_from_slot(to_slot).saturating_sub(block_from_slot(from_slot))
+}
+
 /// Rent this tape pays each block.
 #[inline]
 pub const fn rent_per_block(total_segments: u64) -> u64 {
@@ -26,13 +41,19 @@ impl Tape {
     /// Check if this tape is subsidized.
     #[inline]
     pub fn has_minimum_rent(&self) -> bool {
-        self.balance >= self.rent_per_block()
+        !self.is_expired(self.last_rent_block)
+    }
+
+    /// Balance this tape needs to finalize, a year of rent at its size.
+    #[inline]
+    pub const fn required_finalize_balance(&self) -> u64 {
+        min_finalization_rent(self.total_segments)
     }
 
     /// Check if this tape has enough balance to cover finalization.
     #[inline]
     pub fn can_finalize(&self) -> bool {
-        self.balance >= min_finalization_rent(self.total_segments)
+        self.balance >= self.required_finalize_balance()
     }
 
     /// Rent this tape owes per block.
@@ -46,19 +67,59 @@ impl Tape {
     pub fn rent_owed(&self, current_block: u64) -> u64 {
         rent_owed(self.total_segments, self.last_rent_block, current_block)
     }
+
+    /// Whole blocks of rent the balance still covers at `current_block`,
+    /// after paying what is owed since last_rent_block. A tape with no
+    /// segments pays no rent and never runs out.
+    #[inline]
+    pub fn blocks_remaining(&self, current_block: u64) -> u64 {
+        let rent = self.rent_per_block();
+        if rent == 0 {
+            return u64::MAX;
+        }
+
+        let balance = self.balance.saturating_sub(self.rent_owed(current_block));
+        balance / rent
+    }
+
+    /// Check if the balance can no longer cover a block of rent at
+    /// `current_block`.
+    #[inline]
+    pub fn is_expired(&self, current_block: u64) -> bool {
+        self.blocks_remaining(current_block) == 0
+    }
 }
 
 impl Archive {
-    /// Global reward to miners for the current block.
+    /// Rent every stored tape pays together each block, `RENT_PER_SEGMENT`
+    /// per segment. The number of tapes doesn't matter, only their size.
     #[inline]
-    pub fn block_reward(&self) -> u64 {
+    pub const fn total_storage_fees(&self) -> u64 {
         rent_per_block(self.segments_stored)
     }
+
+    /// Global reward to miners for the current block.
+    ///
+    /// Storage fees are passed through to miners one for one: the epoch's
+    /// reward rate is this plus the emission schedule's base rate, so each
+    /// stored segment adds `RENT_PER_SEGMENT` to what a block pays out.
+    #[inline]
+    pub const fn block_reward(&self) -> u64 {
+        self.total_storage_fees()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use bytemuck::Zeroable;
+
+    fn tape_with(total_segments: u64, balance: u64) -> Tape {
+        let mut tape = Tape::zeroed();
+        tape.total_segments = total_segments;
+        tape.balance = balance;
+        tape
+    }
 
     #[test]
     fn rent_per_block_zero_segments() {
@@ -75,6 +136,18 @@ mod tests {
         assert_eq!(rent_per_block(u64::MAX), u64::MAX);
     }
 
+    #[test]
+    fn blocks_elapsed_over_a_year() {
+        let slots_per_year = 60 * 60 * 24 * 365 * 1000 / SLOT_DURATION_MS;
+        assert_eq!(blocks_elapsed(0, slots_per_year), BLOCKS_PER_YEAR);
+        assert_eq!(BLOCKS_PER_YEAR, 525_600);
+    }
+
+    #[test]
+    fn blocks_elapsed_reversed_is_zero() {
+        assert_eq!(blocks_elapsed(SLOTS_PER_BLOCK * 2, 0), 0);
+    }
+
     #[test]
     fn rent_owed_zero_blocks() {
         assert_eq!(rent_owed(10, 5, 5), 0);
@@ -90,4 +163,73 @@ mod tests {
             segments * RENT_PER_SEGMENT * (current - last)
         );
     }
+
+    #[test]
+    fn finalize_balance_threshold() {
+        let required = rent_per_block(10) * BLOCKS_PER_YEAR;
+        assert_eq!(tape_with(10, 0).required_finalize_balance(), required);
+
+        assert!(tape_with(10, required).can_finalize());
+        assert!(!tape_with(10, required - 1).can_finalize());
+        assert!(tape_with(10, required * 10).can_finalize());
+    }
+
+    #[test]
+    fn block_reward_pays_out_storage_fees() {
+        // (tapes_stored, segments_stored, block_reward)
+        let vectors = [
+            (0, 0, 0),
+            (1, 1, RENT_PER_SEGMENT),
+            // Many small tapes pay the same as one large tape
+            (1, 1_000, 1_000 * RENT_PER_SEGMENT),
+            (1_000, 1_000, 1_000 * RENT_PER_SEGMENT),
+            (10, 1 << 20, (1 << 20) * RENT_PER_SEGMENT),
+            (1, u64::MAX, u64::MAX),
+        ];
+
+        for (tapes_stored, segments_stored, reward) in vectors {
+            let archive = Archive {
+                tapes_stored,
+                segments_stored,
+            };
+            assert_eq!(archive.total_storage_fees(), reward);
+            assert_eq!(archive.block_reward(), reward);
+        }
+    }
+
+    #[test]
+    fn blocks_remaining_zero_balance() {
+        let tape = tape_with(10, 0);
+        assert_eq!(tape.blocks_remaining(0), 0);
+        assert!(tape.is_expired(0));
+        assert!(!tape.has_minimum_rent());
+    }
+
+    #[test]
+    fn blocks_remaining_exactly_one_block() {
+        let tape = tape_with(10, rent_per_block(10));
+        assert_eq!(tape.blocks_remaining(0), 1);
+        assert!(!tape.is_expired(0));
+        assert!(tape.has_minimum_rent());
+
+        // Once that block is owed the runway is gone
+        assert_eq!(tape.blocks_remaining(1), 0);
+        assert!(tape.is_expired(1));
+    }
+
+    #[test]
+    fn blocks_remaining_large_balance() {
+        let mut tape = tape_with(10, rent_per_block(10) * 1_000 + 1);
+        tape.last_rent_block = 100;
+        assert_eq!(tape.blocks_remaining(100), 1_000);
+        assert_eq!(tape.blocks_remaining(400), 700);
+        assert!(!tape.is_expired(400));
+    }
+
+    #[test]
+    fn blocks_remaining_without_segments_never_expires() {
+        let tape = tape_with(0, 0);
+        assert_eq!(tape.blocks_remaining(u64::MAX), u64::MAX);
+        assert!(!tape.is_expired(u64::MAX));
+    }
 }
@@ -14,8 +14,14 @@ pub struct Epoch {
     pub target_participation: u64,
     pub reward_rate: u64,
     pub duplicates: u64,
+    /// Rewards paid out so far this epoch, reset when the epoch advances
+    pub epoch_rewards_distributed: u64,
 
     pub last_epoch_at: i64,
+
+    /// Rate set by the treasury authority through SetRewardRate, kept in place
+    /// of the schedule when the epoch advances. Zero follows the schedule.
+    pub reward_rate_override: u64,
 }
 
 impl DataLen for Epoch {
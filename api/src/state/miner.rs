@@ -3,6 +3,8 @@ use crate::consts::*;
 use crate::state::utils::{
     load_acc, load_acc_mut, try_from_account_info_mut, DataLen, Initialized,
 };
+#[cfg(feature = "std")]
+use crate::{emission::scaled_reward, state::Epoch};
 use bytemuck::{Pod, Zeroable};
 use pinocchio::{
     account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult,
@@ -26,6 +28,17 @@ pub struct Miner {
 
     pub total_proofs: u64,
     pub total_rewards: u64,
+
+    /// Bump of the miner PDA, cached so handlers don't search for it again
+    pub bump: u64,
+
+    /// Refundable lamports held on top of rent, see `MINER_DEPOSIT`
+    pub deposit: u64,
+    /// Lamports slashed from the deposit, paid to the treasury by mine
+    pub forfeited: u64,
+
+    /// Mutable label for display, `name` stays the PDA seed
+    pub display_name: [u8; NAME_LEN],
 }
 
 impl DataLen for Miner {
@@ -51,6 +64,7 @@ impl Miner {
         name: [u8; NAME_LEN],
         authority: Pubkey,
         challenge: [u8; 32],
+        bump: u8,
     ) -> ProgramResult {
         let miner_state = unsafe { try_from_account_info_mut::<Miner>(miner_info) }?;
 
@@ -64,9 +78,144 @@ impl Miner {
         miner_state.last_proof_at = 0;
         miner_state.total_proofs = 0;
         miner_state.total_rewards = 0;
+        miner_state.bump = bump as u64;
+        miner_state.deposit = 0;
+        miner_state.forfeited = 0;
+        miner_state.display_name = name;
 
         Ok(())
     }
+
+    /// Whether the miner has gone more than `threshold` seconds without a proof.
+    #[inline]
+    pub fn is_stale(&self, current_time: i64, threshold: i64) -> bool {
+        current_time.saturating_sub(self.last_proof_at) > threshold
+    }
+}
+
+/// Snapshot of a miner's counters, for dashboards and other off-chain views
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MinerStats {
+    pub total_proofs: u64,
+    pub total_rewards: u64,
+    pub unclaimed_rewards: u64,
+    pub multiplier: u64,
+    pub last_proof_block: u64,
+}
+
+#[cfg(feature = "std")]
+impl Miner {
+    pub fn stats(&self) -> MinerStats {
+        MinerStats {
+            total_proofs: self.total_proofs,
+            total_rewards: self.total_rewards,
+            unclaimed_rewards: self.unclaimed_rewards,
+            multiplier: self.multiplier,
+            last_proof_block: self.last_proof_block,
+        }
+    }
+
+    /// Estimated TAPE earned over a year at the epoch's current rate and this
+    /// miner's multiplier.
+    ///
+    /// Uses the mine reward math for a subsidized tape, without the recency
+    /// bonus, and assumes the miner solves every block like `annualized`.
+    pub fn estimated_apr(&self, epoch: &Epoch) -> u64 {
+        if epoch.target_participation == 0 {
+            return 0;
+        }
+
+        let available_reward = epoch.reward_rate / epoch.target_participation;
+
+        scaled_reward(available_reward, self.multiplier)
+            .saturating_mul(EPOCHS_PER_YEAR)
+            .saturating_mul(EPOCH_BLOCKS)
+    }
 }
 
 // account!(AccountType, Miner);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THRESHOLD: i64 = 60 * 60;
+
+    fn miner_proven_at(last_proof_at: i64) -> Miner {
+        Miner {
+            last_proof_at,
+            ..Miner::zeroed()
+        }
+    }
+
+    #[test]
+    fn fresh_miner_is_not_stale() {
+        let miner = miner_proven_at(1_000);
+        assert!(!miner.is_stale(1_000, THRESHOLD));
+        assert!(!miner.is_stale(1_000 + THRESHOLD / 2, THRESHOLD));
+    }
+
+    #[test]
+    fn miner_past_threshold_is_stale() {
+        let miner = miner_proven_at(1_000);
+        assert!(miner.is_stale(1_000 + THRESHOLD * 2, THRESHOLD));
+    }
+
+    #[test]
+    fn stale_boundary() {
+        let miner = miner_proven_at(1_000);
+        assert!(!miner.is_stale(1_000 + THRESHOLD, THRESHOLD));
+        assert!(miner.is_stale(1_000 + THRESHOLD + 1, THRESHOLD));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn stats_reflect_miner_fields() {
+        let mut miner = Miner::zeroed();
+        assert_eq!(miner.stats(), MinerStats::default());
+
+        // What three proofs in a row leave behind
+        miner.total_proofs = 3;
+        miner.total_rewards = 3 * ONE_TAPE;
+        miner.unclaimed_rewards = 2 * ONE_TAPE;
+        miner.multiplier = 4;
+        miner.last_proof_block = 42;
+
+        assert_eq!(
+            miner.stats(),
+            MinerStats {
+                total_proofs: 3,
+                total_rewards: 3 * ONE_TAPE,
+                unclaimed_rewards: 2 * ONE_TAPE,
+                multiplier: 4,
+                last_proof_block: 42,
+            }
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn estimated_apr_scales_with_multiplier() {
+        let mut epoch = Epoch::zeroed();
+        epoch.reward_rate = ONE_TAPE;
+        epoch.target_participation = 1;
+
+        // At the top multiplier a lone miner earns the whole emission
+        let mut miner = Miner::zeroed();
+        miner.multiplier = MAX_CONSISTENCY_MULTIPLIER;
+        assert_eq!(
+            miner.estimated_apr(&epoch),
+            crate::emission::annualized(&epoch)
+        );
+
+        miner.multiplier = MAX_CONSISTENCY_MULTIPLIER / 2;
+        assert_eq!(
+            miner.estimated_apr(&epoch),
+            crate::emission::annualized(&epoch) / 2
+        );
+
+        epoch.target_participation = 0;
+        assert_eq!(miner.estimated_apr(&epoch), 0);
+    }
+}
@@ -14,10 +14,18 @@ pub struct Spool {
     pub seed: [u8; 32],
     pub contains: [u8; 32],
 
+    /// Tapes ever packed, carried over rollovers and never decremented
     pub total_tapes: u64,
+    /// Tapes currently in the tree, decremented by remove and reset by rollover
+    pub live_tapes: u64,
 
+    /// Block of the last proof, 0 until the spool has been proven
     pub last_proof_block: u64,
+    /// Time of the last proof, or the creation time (never 0) before that
     pub last_proof_at: i64,
+
+    /// Hash chain of the roots retired by rollover, zero until the first one
+    pub history: [u8; 32],
 }
 
 impl DataLen for Spool {
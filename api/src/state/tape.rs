@@ -1,5 +1,6 @@
 use super::AccountType;
 use crate::consts::*;
+use crate::error::TapeError;
 use crate::state::utils::{load_acc, load_acc_mut, DataLen, Initialized};
 use crate::types::*;
 use bytemuck::{Pod, Zeroable};
@@ -12,6 +13,8 @@ pub struct Tape {
     pub state: u64,
 
     pub authority: Pubkey,
+    /// Original authority, kept as the PDA seed when authority is transferred
+    pub creator: Pubkey,
 
     pub name: [u8; NAME_LEN],
     pub merkle_root: [u8; 32],
@@ -22,6 +25,8 @@ pub struct Tape {
     pub balance: u64,
     pub last_rent_block: u64,
     pub total_segments: u64,
+    /// Bump of the tape PDA, cached so handlers don't search for it again
+    pub bump: u64,
     // +Phantom Vec<Hash> for merkle subtree nodes (up to 4096).
 }
 
@@ -51,6 +56,36 @@ impl Tape {
     pub fn unpack_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
         unsafe { load_acc_mut::<Tape>(data) }
     }
+
+    /// The stored state, with any value outside the enum read as `Unknown`.
+    pub fn current_state(&self) -> TapeState {
+        match self.state {
+            s if s == TapeState::Created as u64 => TapeState::Created,
+            s if s == TapeState::Writing as u64 => TapeState::Writing,
+            s if s == TapeState::Finalized as u64 => TapeState::Finalized,
+            _ => TapeState::Unknown,
+        }
+    }
+
+    /// Fails with `UnexpectedState` unless the tape is in `expected`.
+    pub fn require_state(&self, expected: TapeState) -> Result<(), TapeError> {
+        if self.current_state() != expected {
+            return Err(TapeError::UnexpectedState);
+        }
+        Ok(())
+    }
+
+    /// Whether the tape may move to `next`. The only legal edges are
+    /// `Created -> Writing`, `Writing -> Writing` for further writes, and
+    /// `Writing -> Finalized`; a finalized tape never changes state again.
+    pub fn can_transition_to(&self, next: TapeState) -> bool {
+        matches!(
+            (self.current_state(), next),
+            (TapeState::Created, TapeState::Writing)
+                | (TapeState::Writing, TapeState::Writing)
+                | (TapeState::Writing, TapeState::Finalized)
+        )
+    }
 }
 
 // account!(AccountType, Tape);
@@ -75,3 +110,65 @@ impl Tape {
 //    let nodes: &[[u8; 32]] = bytemuck::try_cast_slice(tail).expect("len checked");
 //    Ok((header, nodes))
 //}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STATES: [TapeState; 4] = [
+        TapeState::Unknown,
+        TapeState::Created,
+        TapeState::Writing,
+        TapeState::Finalized,
+    ];
+
+    fn tape_in(state: TapeState) -> Tape {
+        Tape {
+            state: state as u64,
+            ..Tape::zeroed()
+        }
+    }
+
+    #[test]
+    fn only_legal_transitions_pass() {
+        let legal = [
+            (TapeState::Created, TapeState::Writing),
+            (TapeState::Writing, TapeState::Writing),
+            (TapeState::Writing, TapeState::Finalized),
+        ];
+
+        for from in STATES {
+            for to in STATES {
+                assert_eq!(
+                    tape_in(from).can_transition_to(to),
+                    legal.contains(&(from, to)),
+                    "{from:?} -> {to:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn require_state_matches_only_the_current_state() {
+        for current in STATES {
+            for expected in STATES {
+                let result = tape_in(current).require_state(expected);
+                if current == expected {
+                    assert_eq!(result, Ok(()));
+                } else {
+                    assert_eq!(result, Err(TapeError::UnexpectedState));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn out_of_range_state_reads_as_unknown() {
+        let tape = Tape {
+            state: 42,
+            ..Tape::zeroed()
+        };
+        assert_eq!(tape.current_state(), TapeState::Unknown);
+        assert!(STATES.iter().all(|&next| !tape.can_transition_to(next)));
+    }
+}
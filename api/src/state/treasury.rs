@@ -5,7 +5,10 @@ use pinocchio::program_error::ProgramError;
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
-pub struct Treasury {}
+pub struct Treasury {
+    /// Signer of initialize, the only key allowed to override the reward rate
+    pub authority: [u8; 32],
+}
 
 impl DataLen for Treasury {
     const LEN: usize = core::mem::size_of::<Treasury>();
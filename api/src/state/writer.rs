@@ -9,6 +9,10 @@ use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
 pub struct Writer {
     pub tape: Pubkey,
     pub state: SegmentTree,
+    /// Bump of the writer PDA, cached so handlers don't search for it again
+    pub bump: u64,
+    /// Which slice of the tape this writer appends to, 0 for the tape's own writer
+    pub shard: u64,
 }
 
 impl DataLen for Writer {
@@ -2,19 +2,24 @@ use crate::consts::*;
 use crate::error::*;
 use crate::types::*;
 use blake3::Hasher;
-// use brine_tree::Leaf;
 use bytemuck::{Pod, Zeroable};
 use core::cmp::min;
 use pinocchio::{
     account_info::AccountInfo,
     program_error::ProgramError,
-    sysvars::{clock::Clock, Sysvar},
+    sysvars::{clock::Clock, rent::Rent, Sysvar},
     ProgramResult,
 };
 use pinocchio_system::instructions::CreateAccount;
+use utils::{leaf::Leaf, tree::SEGMENT_TREE_ZEROS_18};
+
+extern crate alloc;
+use alloc::string::String;
 
 /// SlotHash from Solana's slot_hashes sysvar (Slot + Hash = 8 + 32 = 40 bytes)
 const SLOTHASH_SIZE: usize = 40;
+/// The sysvar holds a u64 entry count, then the entries, newest first
+const SLOTHASHES_PREFIX: usize = 8;
 
 #[inline(always)]
 pub fn check_condition<E>(condition: bool, err: E) -> ProgramResult
@@ -35,6 +40,26 @@ pub fn padded_array<const N: usize>(input: &[u8]) -> [u8; N] {
     out
 }
 
+/// Checks `segment` holds `data` followed only by zero padding, i.e. it is
+/// what [`padded_array`] would produce for `data`.
+#[inline(always)]
+pub fn eq_unpadded(segment: &[u8; SEGMENT_SIZE], data: &[u8]) -> bool {
+    if data.len() > SEGMENT_SIZE {
+        return false;
+    }
+
+    let (head, padding) = segment.split_at(data.len());
+    head == data && padding.iter().all(|&b| b == 0)
+}
+
+/// Zero-pads `data` into a segment, the form segments are stored and hashed
+/// in. Fails with `SegmentTooLarge` if it doesn't fit in one segment.
+#[inline(always)]
+pub fn to_segment(data: &[u8]) -> Result<[u8; SEGMENT_SIZE], ProgramError> {
+    check_condition(data.len() <= SEGMENT_SIZE, TapeError::SegmentTooLarge)?;
+    Ok(padded_array::<SEGMENT_SIZE>(data))
+}
+
 #[inline(always)]
 pub fn to_name<T>(val: T) -> [u8; NAME_LEN]
 where
@@ -56,11 +81,51 @@ pub fn from_name(val: &[u8; NAME_LEN]) -> &str {
     core::str::from_utf8(&val[..end]).unwrap()
 }
 
-// #[inline(always)]
-// pub fn compute_leaf(segment_id: u64, segment: &[u8; SEGMENT_SIZE]) -> Leaf {
-//     let segment_id = segment_id.to_le_bytes();
-//     Leaf::new(&[segment_id.as_ref(), segment])
-// }
+/// Packs a URI into the `[u64; 32]` layout of the metadata `DataV2`,
+/// little-endian, truncated to [`PACKED_URI_LEN`] bytes.
+pub fn pack_uri(s: &str) -> [u64; 32] {
+    let mut uri = [0u64; 32];
+
+    for (i, &byte) in s.as_bytes().iter().take(PACKED_URI_LEN).enumerate() {
+        uri[i / 8] |= (byte as u64) << ((i % 8) * 8);
+    }
+
+    uri
+}
+
+/// Inverse of [`pack_uri`]. Stops at the first zero byte; a multi-byte
+/// character cut by truncation is replaced rather than rejected.
+pub fn unpack_uri(uri: &[u64; 32]) -> String {
+    let mut bytes = [0u8; PACKED_URI_LEN];
+    for (chunk, word) in bytes.chunks_exact_mut(8).zip(uri) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(PACKED_URI_LEN);
+    String::from_utf8_lossy(&bytes[..end]).into_owned()
+}
+
+#[inline(always)]
+pub fn compute_leaf(segment_id: u64, segment: &[u8; SEGMENT_SIZE]) -> Leaf {
+    Leaf::from_segment(segment_id, segment)
+}
+
+/// Rebuilds the segment tree of a downloaded tape and checks it reproduces
+/// the tape's merkle root.
+pub fn verify_tape(merkle_root: &[u8; 32], segments: &[[u8; SEGMENT_SIZE]]) -> bool {
+    let mut tree = SegmentTree::from_zeros(SEGMENT_TREE_ZEROS_18);
+
+    for (segment_id, segment) in segments.iter().enumerate() {
+        if tree
+            .try_add_leaf(compute_leaf(segment_id as u64, segment))
+            .is_err()
+        {
+            return false;
+        }
+    }
+
+    tree.get_root().to_bytes() == *merkle_root
+}
 
 // #[inline(always)]
 // pub fn write_segment(
@@ -90,28 +155,34 @@ pub fn from_name(val: &[u8; NAME_LEN]) -> &str {
 //     Ok(())
 // }
 
+/// Next challenge after `current_challenge`, mixed with the hash of the most
+/// recent slot from the SlotHashes sysvar.
 #[inline(always)]
 pub fn compute_next_challenge(
     current_challenge: &[u8; 32],
     slot_hashes_info: &AccountInfo,
 ) -> Result<[u8; 32], ProgramError> {
-    // Read the first SlotHash from the SlotHashes sysvar
-    // SlotHash is 40 bytes: 8 bytes (slot) + 32 bytes (hash)
     let slothash_data = slot_hashes_info.try_borrow_data()?;
 
-    if slothash_data.len() < SLOTHASH_SIZE {
+    if slothash_data.len() < SLOTHASHES_PREFIX + SLOTHASH_SIZE {
         return Err(ProgramError::InvalidAccountData);
     }
 
-    let slothash = &slothash_data[0..SLOTHASH_SIZE];
+    // Skip the entry count and the newest entry's slot to reach its hash
+    let start = SLOTHASHES_PREFIX + 8;
+    let mut slot_hash = [0u8; 32];
+    slot_hash.copy_from_slice(&slothash_data[start..start + 32]);
 
-    // Hash current_challenge + slothash using blake3
-    let mut hasher = Hasher::new();
-    hasher.update(current_challenge);
-    hasher.update(slothash);
-    let challenge = hasher.finalize();
+    Ok(compute_next_challenge_from(current_challenge, &slot_hash))
+}
 
-    Ok(challenge.into())
+/// Pure part of [`compute_next_challenge`], so tests can supply the slot hash.
+#[inline(always)]
+pub fn compute_next_challenge_from(seed: &[u8; 32], slot_hash: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Hasher::new();
+    hasher.update(seed);
+    hasher.update(slot_hash);
+    hasher.finalize().into()
 }
 
 #[inline(always)]
@@ -125,6 +196,34 @@ pub fn compute_challenge(block_challenge: &[u8; 32], miner_challenge: &[u8; 32])
     challenge.into()
 }
 
+/// Checks the recorded tape balance is backed by the account's lamports
+/// above its rent-exempt minimum.
+#[inline(always)]
+pub fn check_tape_balance(balance: u64, tape_info: &AccountInfo) -> ProgramResult {
+    let rent_exempt = Rent::get()?.minimum_balance(tape_info.data_len());
+    let available = tape_info.lamports().saturating_sub(rent_exempt);
+
+    check_condition(balance <= available, TapeError::BalanceMismatch)
+}
+
+/// Binds a committed value to the spool it was proven against. Packing or
+/// unpacking the spool changes one of the roots, invalidating the commitment.
+#[inline(always)]
+pub fn compute_commitment(
+    value: &[u8; 32],
+    spool_contains: &[u8; 32],
+    spool_root: &[u8; 32],
+) -> [u8; 32] {
+    let mut hasher = Hasher::new();
+
+    hasher.update(value);
+    hasher.update(spool_contains);
+    hasher.update(spool_root);
+    let commitment = hasher.finalize();
+
+    commitment.into()
+}
+
 #[inline(always)]
 pub fn compute_recall_tape(challenge: &[u8; 32], total_tapes: u64) -> u64 {
     if total_tapes == 0 {
@@ -140,3 +239,196 @@ pub fn compute_recall_segment(challenge: &[u8; 32], total_segments: u64) -> u64
     }
     u64::from_le_bytes(challenge[8..16].try_into().unwrap()) % total_segments
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::tree::verify_no_std;
+
+    #[test]
+    fn eq_unpadded_exact_match() {
+        let data = [7u8; SEGMENT_SIZE];
+        assert!(eq_unpadded(&data, &data));
+    }
+
+    #[test]
+    fn eq_unpadded_match_with_padding() {
+        let segment = padded_array::<SEGMENT_SIZE>(b"hello");
+        assert!(eq_unpadded(&segment, b"hello"));
+        assert!(eq_unpadded(&[0; SEGMENT_SIZE], &[]));
+    }
+
+    #[test]
+    fn to_segment_exact_size() {
+        let data = [7u8; SEGMENT_SIZE];
+        assert_eq!(to_segment(&data), Ok(data));
+    }
+
+    #[test]
+    fn to_segment_pads_short_input() {
+        let segment = to_segment(b"hello").unwrap();
+        assert_eq!(&segment[..5], b"hello");
+        assert!(segment[5..].iter().all(|&b| b == 0));
+        assert!(eq_unpadded(&segment, b"hello"));
+
+        assert_eq!(to_segment(&[]), Ok([0; SEGMENT_SIZE]));
+    }
+
+    #[test]
+    fn to_segment_rejects_oversized_input() {
+        assert_eq!(
+            to_segment(&[1; SEGMENT_SIZE + 1]),
+            Err(TapeError::SegmentTooLarge.into())
+        );
+    }
+
+    #[test]
+    fn eq_unpadded_mismatch() {
+        let segment = padded_array::<SEGMENT_SIZE>(b"hello");
+        assert!(!eq_unpadded(&segment, b"help!"));
+        // A prefix leaves non-zero bytes in the padding
+        assert!(!eq_unpadded(&segment, b"hell"));
+        assert!(!eq_unpadded(&segment, &[0; SEGMENT_SIZE + 1]));
+    }
+
+    #[test]
+    fn genesis_single_segment_proof() {
+        // The genesis tape holds one segment, so mining it means proving leaf 0
+        // of a tree with nothing else in it
+        let (genesis_tape, _) = crate::pda::tape_pda([1; 32], &to_name(GENESIS_NAME));
+        let segment = to_segment(GENESIS_NAME.as_bytes()).unwrap();
+        let leaf = compute_leaf(0, &segment);
+
+        // A tree seeded from the genesis tape key, and the precomputed-zeros
+        // tree the writer actually starts from
+        let trees = [
+            SegmentTree::new(&[genesis_tape.as_ref()]),
+            SegmentTree::from_zeros(SEGMENT_TREE_ZEROS_18),
+        ];
+
+        let roots = trees.map(|mut tree| {
+            tree.try_add_leaf(leaf).unwrap();
+            let root = tree.get_root();
+
+            let proof = tree.get_proof_no_std::<1>(&[leaf], 0).unwrap();
+            assert!(verify_no_std(root, &proof, leaf));
+            assert!(tree.contains_no_std(&proof, &[&0u64.to_le_bytes(), &segment]));
+
+            // The segment id is part of the leaf
+            assert!(!verify_no_std(root, &proof, compute_leaf(1, &segment)));
+            root.to_bytes()
+        });
+
+        // Downloaded tapes are rebuilt from the precomputed zeros
+        assert!(!verify_tape(&roots[0], &[segment]));
+        assert!(verify_tape(&roots[1], &[segment]));
+    }
+
+    #[test]
+    fn uri_round_trip_ascii() {
+        let packed = pack_uri(METADATA_URI);
+        assert_eq!(unpack_uri(&packed), METADATA_URI);
+
+        // Bytes are packed little-endian into each word
+        assert_eq!(packed[0], u64::from_le_bytes(*b"https://"));
+
+        assert_eq!(pack_uri(""), [0; 32]);
+        assert_eq!(unpack_uri(&[0; 32]), "");
+    }
+
+    #[test]
+    fn uri_round_trip_at_capacity() {
+        let uri: String = (0..PACKED_URI_LEN)
+            .map(|i| (b'a' + (i % 26) as u8) as char)
+            .collect();
+        let packed = pack_uri(&uri);
+        assert!(packed.iter().all(|&word| word != 0));
+        assert_eq!(unpack_uri(&packed), uri);
+
+        // Anything past capacity is dropped
+        let longer = uri.clone() + "overflow";
+        assert_eq!(pack_uri(&longer), packed);
+        assert_eq!(unpack_uri(&pack_uri(&longer)), uri);
+    }
+
+    /// A challenge with `tape_word` in bytes 0..8 and `segment_word` in 8..16
+    fn challenge(tape_word: u64, segment_word: u64, rest: u8) -> [u8; 32] {
+        let mut challenge = [rest; 32];
+        challenge[0..8].copy_from_slice(&tape_word.to_le_bytes());
+        challenge[8..16].copy_from_slice(&segment_word.to_le_bytes());
+        challenge
+    }
+
+    /// Recall test vectors. Changing any of these changes which tape and
+    /// segment miners prove, so treat them as a protocol invariant.
+    #[test]
+    fn next_challenge_is_pinned() {
+        assert_eq!(
+            compute_next_challenge_from(&[1; 32], &[2; 32]),
+            [
+                141, 103, 188, 120, 54, 209, 40, 177, 8, 190, 44, 150, 85, 56, 243, 123, 188, 238,
+                62, 117, 3, 227, 94, 88, 251, 176, 68, 100, 50, 224, 82, 6,
+            ]
+        );
+    }
+
+    #[test]
+    fn next_challenge_depends_on_both_inputs() {
+        let base = compute_next_challenge_from(&[1; 32], &[2; 32]);
+
+        for i in 0..32 {
+            let mut seed = [1; 32];
+            seed[i] ^= 1;
+            assert_ne!(compute_next_challenge_from(&seed, &[2; 32]), base);
+
+            let mut slot_hash = [2; 32];
+            slot_hash[i] ^= 1;
+            assert_ne!(compute_next_challenge_from(&[1; 32], &slot_hash), base);
+        }
+
+        // The inputs aren't interchangeable
+        assert_ne!(compute_next_challenge_from(&[2; 32], &[1; 32]), base);
+    }
+
+    #[test]
+    fn recall_mapping_is_stable() {
+        let sequential: [u8; 32] = core::array::from_fn(|i| i as u8);
+
+        // (challenge, total_tapes, total_segments, tape_number, segment_number)
+        let vectors = [
+            // Empty archive and tape fall back to tape 1, segment 0
+            ([0; 32], 0, 0, 1, 0),
+            ([0xff; 32], 0, 0, 1, 0),
+            (sequential, 0, 0, 1, 0),
+            // A single tape or segment is always the one recalled
+            ([0xff; 32], 1, 1, 1, 0),
+            (sequential, 1, 1, 1, 0),
+            // Tape numbers start at 1, segment numbers at 0
+            ([0; 32], 7, 1024, 1, 0),
+            ([0xff; 32], 7, 1024, 2, 1023),
+            ([0xff; 32], 1000, 65536, 616, 65535),
+            ([0xff; 32], 1 << 20, 3, 1 << 20, 0),
+            (sequential, 7, 1024, 2, 264),
+            (sequential, 1000, 65536, 529, 2312),
+            (sequential, 1 << 20, 3, 131_329, 2),
+            // Only bytes 0..8 pick the tape and 8..16 the segment
+            (challenge(1_000_003, 42, 0), 7, 1024, 5, 42),
+            (challenge(1_000_003, 42, 0xff), 7, 1024, 5, 42),
+            (challenge(1_000_003, 42, 0), 1000, 65536, 4, 42),
+            (challenge(1_000_003, 42, 0), 1 << 20, 3, 1_000_004, 0),
+        ];
+
+        for (challenge, total_tapes, total_segments, tape_number, segment_number) in vectors {
+            assert_eq!(
+                compute_recall_tape(&challenge, total_tapes),
+                tape_number,
+                "tape for {challenge:?} of {total_tapes}"
+            );
+            assert_eq!(
+                compute_recall_segment(&challenge, total_segments),
+                segment_number,
+                "segment for {challenge:?} of {total_segments}"
+            );
+        }
+    }
+}
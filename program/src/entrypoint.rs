@@ -33,6 +33,9 @@ fn process_instruction(
         TapeInstruction::Unknown => return Err(ProgramError::InvalidInstructionData),
         TapeInstruction::Initialize => process_initialize(accounts, data),
         TapeInstruction::Airdrop => process_airdrop(accounts, data),
+        TapeInstruction::EpochReport => process_epoch_report(accounts, data),
+        TapeInstruction::InitializeWithConfig => process_initialize_with_config(accounts, data),
+        TapeInstruction::SetRewardRate => process_set_reward_rate(accounts, data),
 
         // TapeInstruction variants
         TapeInstruction::TapeCreate => process_tape_create(accounts, data),
@@ -41,12 +44,16 @@ fn process_instruction(
         TapeInstruction::TapeFinalize => process_tape_finalize(accounts, data),
         TapeInstruction::TapeSetHeader => process_tape_set_header(accounts, data),
         TapeInstruction::TapeSubsidize => process_tape_subsidize_rent(accounts, data),
+        TapeInstruction::TapeClose => process_tape_close(accounts, data),
+        TapeInstruction::TapeTransferAuthority => process_tape_transfer_authority(accounts, data),
+        TapeInstruction::TapeCreateShard => process_tape_create_shard(accounts, data),
 
         // MinerInstruction variants
         TapeInstruction::MinerRegister => process_register(accounts, data),
         TapeInstruction::MinerUnregister => process_unregister(accounts, data),
         TapeInstruction::MinerMine => process_mine(accounts, data),
         TapeInstruction::MinerClaim => process_claim(accounts, data),
+        TapeInstruction::MinerSetLabel => process_miner_set_label(accounts, data),
 
         // SpoolInstruction variants
         TapeInstruction::SpoolCreate => process_spool_create(accounts, data),
@@ -54,5 +61,8 @@ fn process_instruction(
         TapeInstruction::SpoolPack => process_spool_pack(accounts, data),
         TapeInstruction::SpoolUnpack => process_spool_unpack(accounts, data),
         TapeInstruction::SpoolCommit => process_spool_commit(accounts, data),
+        TapeInstruction::SpoolRemove => process_spool_remove(accounts, data),
+        TapeInstruction::SpoolRollover => process_spool_rollover(accounts, data),
+        TapeInstruction::SpoolPackBatch => process_spool_pack_batch(accounts, data),
     }
 }
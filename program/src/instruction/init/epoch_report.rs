@@ -0,0 +1,43 @@
+use crate::state::{Epoch, EPOCH_ADDRESS, TAPE_ID};
+use crate::utils::helpers::cast_account_data;
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+use tape_api::event::EpochReportEvent;
+
+/// Logs the current epoch's reward rate, difficulty and participation target
+/// as an `EpochReportEvent`, so light clients can follow them from logs alone.
+///
+/// The epoch is only ever read; passing it as writable is rejected.
+pub fn process_epoch_report(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let [epoch_info, _remaining @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if epoch_info.is_writable() {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if epoch_info.key().ne(&EPOCH_ADDRESS) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if !epoch_info.is_owned_by(&TAPE_ID) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let epoch_data = epoch_info.try_borrow_data()?;
+    let epoch = cast_account_data::<Epoch>(&epoch_data)?;
+
+    EpochReportEvent {
+        number: epoch.number,
+        progress: epoch.progress,
+        mining_difficulty: epoch.mining_difficulty,
+        packing_difficulty: epoch.packing_difficulty,
+        target_participation: epoch.target_participation,
+        reward_rate: epoch.reward_rate,
+        epoch_rewards_distributed: epoch.epoch_rewards_distributed,
+        last_epoch_at: epoch.last_epoch_at,
+    }
+    .log();
+
+    Ok(())
+}
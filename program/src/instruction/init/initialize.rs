@@ -1,12 +1,14 @@
-use crate::instruction::mine::miner_mine::get_base_rate;
+use crate::instruction::mine::miner_mine::{get_base_rate, get_reward_rate};
+use crate::instruction::{InitializeConfig, InitializeMetadata};
 use crate::state::*;
 use crate::utils::account_traits::AccountInfoExt;
-use crate::utils::get_pda::GetPda;
-use crate::utils::helpers::{cast_account_data_mut, create_program_account};
+use crate::utils::helpers::{cast_account_data_mut, create_program_account_with_bump};
+use crate::utils::ByteConversion;
+use bytemuck::Zeroable;
 use core::cmp::min;
 use pinocchio::{
     account_info::AccountInfo,
-    cpi::{slice_invoke, slice_invoke_signed},
+    cpi::{set_return_data, slice_invoke, slice_invoke_signed},
     instruction::{AccountMeta, Instruction, Seed, Signer},
     msg,
     program_error::ProgramError,
@@ -17,9 +19,10 @@ use pinocchio_associated_token_account::instructions::Create as CreateATA;
 use pinocchio_system::instructions::CreateAccount;
 use pinocchio_token::instructions::{InitializeMint2, MintTo};
 use tape_api::consts::{
-    BLOCK_ADDRESS, MAX_SUPPLY, METADATA_NAME, METADATA_SYMBOL, METADATA_URI, MINT_BUMP, MINT_SEED,
+    GENESIS_TAPE_ADDRESS, GENESIS_WRITER_ADDRESS, MAX_MINING_DIFFICULTY, MAX_PACKING_DIFFICULTY,
+    MAX_PARTICIPATION_TARGET, MAX_SUPPLY, METADATA_NAME, METADATA_SYMBOL, METADATA_URI,
     MIN_MINING_DIFFICULTY, MIN_PACKING_DIFFICULTY, MIN_PARTICIPATION_TARGET, TOKEN_DECIMALS,
-    TREASURY_BUMP,
+    TREASURY_ATA,
 };
 use tape_api::utils::compute_next_challenge;
 
@@ -41,23 +44,6 @@ fn string_to_bytes<const N: usize>(s: &str) -> [u8; N] {
     out
 }
 
-/// Helper to convert URI string to [u64; 32] array (unused)
-#[inline(always)]
-#[allow(dead_code)]
-fn string_to_uri(s: &str) -> [u64; 32] {
-    let bytes = s.as_bytes();
-    let mut uri = [0u64; 32];
-
-    // Pack bytes into u64s
-    for i in 0..min(bytes.len(), 256) {
-        let byte_pos = i / 8;
-        let shift = (i % 8) * 8;
-        uri[byte_pos] |= (bytes[i] as u64) << shift;
-    }
-
-    uri
-}
-
 /// Metaplex Token Metadata DataV2 struct (Borsh-serializable)
 #[derive(BorshSerialize)]
 struct MetadataDataV2 {
@@ -131,10 +117,96 @@ fn build_metadata_instruction_data_borsh(
     Ok(data)
 }
 
-pub fn process_initialize(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
-    // if !data.is_empty() {
-    //     return Err(ProgramError::InvalidInstructionData);
-    // }
+/// Reads a zero-padded metadata field, falling back to `default` when it is
+/// absent or empty
+fn metadata_field<'a>(field: Option<&'a [u8]>, default: &'a str) -> Result<&'a str, ProgramError> {
+    let Some(field) = field else {
+        return Ok(default);
+    };
+
+    let len = field.iter().position(|&b| b == 0).unwrap_or(field.len());
+    if len == 0 {
+        return Ok(default);
+    }
+
+    core::str::from_utf8(&field[..len]).map_err(|_| ProgramError::InvalidInstructionData)
+}
+
+/// Starting values for the first epoch
+#[derive(Clone, Copy)]
+struct GenesisParams {
+    target_participation: u64,
+    mining_difficulty: u64,
+    packing_difficulty: u64,
+    reward_rate: u64,
+}
+
+impl GenesisParams {
+    /// The mainnet genesis: minimum difficulty and the first-year base rate
+    fn default_params() -> Self {
+        Self {
+            target_participation: MIN_PARTICIPATION_TARGET,
+            mining_difficulty: MIN_MINING_DIFFICULTY,
+            packing_difficulty: MIN_PACKING_DIFFICULTY,
+            // Same formula as each epoch advance, with nothing stored yet
+            reward_rate: get_reward_rate(1, &Archive::zeroed()),
+        }
+    }
+
+    /// Reads the genesis values from instruction data, rejecting any outside
+    /// the bounds the epoch retarget itself keeps to
+    fn from_config(config: &InitializeConfig) -> Result<Self, ProgramError> {
+        let params = Self {
+            target_participation: u64::from_le_bytes(config.target_participation),
+            mining_difficulty: u64::from_le_bytes(config.mining_difficulty),
+            packing_difficulty: u64::from_le_bytes(config.packing_difficulty),
+            reward_rate: u64::from_le_bytes(config.reward_rate),
+        };
+
+        let valid = (MIN_PARTICIPATION_TARGET..=MAX_PARTICIPATION_TARGET)
+            .contains(&params.target_participation)
+            && (MIN_MINING_DIFFICULTY..=MAX_MINING_DIFFICULTY).contains(&params.mining_difficulty)
+            && (MIN_PACKING_DIFFICULTY..=MAX_PACKING_DIFFICULTY)
+                .contains(&params.packing_difficulty)
+            && params.reward_rate <= get_base_rate(1);
+
+        if !valid {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(params)
+    }
+}
+
+pub fn process_initialize(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    initialize(accounts, data, GenesisParams::default_params())
+}
+
+/// Initialize with the first epoch's participation target, difficulties and
+/// reward rate taken from instruction data, for devnet and test deployments.
+/// Optional token metadata may follow the config, as for Initialize.
+pub fn process_initialize_with_config(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let config_len = core::mem::size_of::<InitializeConfig>();
+    if data.len() < config_len {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let (config, metadata) = data.split_at(config_len);
+    let genesis = GenesisParams::from_config(InitializeConfig::try_from_bytes(config)?)?;
+
+    initialize(accounts, metadata, genesis)
+}
+
+fn initialize(accounts: &[AccountInfo], data: &[u8], genesis: GenesisParams) -> ProgramResult {
+    // Token metadata may be overridden by forks; without data the consts apply
+    let metadata_args = if data.is_empty() {
+        None
+    } else {
+        Some(InitializeMetadata::try_from_bytes(data)?)
+    };
+    let metadata_name = metadata_field(metadata_args.map(|m| &m.name[..]), METADATA_NAME)?;
+    let metadata_symbol = metadata_field(metadata_args.map(|m| &m.symbol[..]), METADATA_SYMBOL)?;
+    let metadata_uri = metadata_field(metadata_args.map(|m| &m.uri[..]), METADATA_URI)?;
 
     let [signer_info, archive_info, epoch_info, block_info, metadata_info, mint_info, treasury_info, treasury_ata_info, tape_info, writer_info, tape_program_info, system_program_info, token_program_info, associated_token_program_info, metadata_program_info, rent_sysvar_info, slot_hashes_info] =
         accounts
@@ -142,66 +214,92 @@ pub fn process_initialize(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResu
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
-    archive_info.check_account(ARCHIVE)?;
-    epoch_info.check_account(EPOCH)?;
-    block_info.check_account(BLOCK)?;
-
-    let (mint_address, mint_bump) = GetPda::Mint.address();
-    let (treasury_address, treasury_bump) = GetPda::Treasury.address();
-    let (metadata_address, _metadata_bump) = GetPda::Metadata(mint_address).address();
+    // All singleton addresses and bumps are compile-time constants, so no
+    // find_program_address bump search is needed here.
+    //
+    // A failed initialize rolls back every account it created, so an account
+    // can only exist here if it was made outside this instruction, e.g. by a
+    // local validator cloning accounts from another cluster, or left from an
+    // earlier deployment. Once the mint exists, anyone can also create the
+    // treasury ATA. Existing accounts are checked and left untouched, and
+    // only the remaining steps are run.
+    let archive_exists = archive_info.check_account_or_existing(&ARCHIVE_ADDRESS, &TAPE_ID)?;
+    let epoch_exists = epoch_info.check_account_or_existing(&EPOCH_ADDRESS, &TAPE_ID)?;
+    let block_exists = block_info.check_account_or_existing(&BLOCK_ADDRESS, &TAPE_ID)?;
+    let mint_exists = mint_info.check_account_or_existing(&MINT_ADDRESS, &pinocchio_token::ID)?;
+    let metadata_exists =
+        metadata_info.check_account_or_existing(&METADATA_ADDRESS, &MPL_TOKEN_METADATA_ID)?;
+    let treasury_exists = treasury_info.check_account_or_existing(&TREASURY_ADDRESS, &TAPE_ID)?;
+
+    // The genesis tape and writer have fixed addresses too
+    if tape_info.key().ne(&GENESIS_TAPE_ADDRESS) || writer_info.key().ne(&GENESIS_WRITER_ADDRESS) {
+        return Err(ProgramError::InvalidAccountData);
+    }
 
-    assert_eq!(mint_bump, MINT_BUMP);
-    assert_eq!(treasury_bump, TREASURY_BUMP);
+    // MintTo pays whatever account is passed here, so it must be the
+    // treasury's own ATA even when it already exists
+    let treasury_ata_exists =
+        treasury_ata_info.check_account_or_existing(&TREASURY_ATA, &pinocchio_token::ID)?;
 
-    mint_info.check_account_with_address(&mint_address)?;
-    metadata_info.check_account_with_address(&metadata_address)?;
-    treasury_info.check_account_with_address(&treasury_address)?;
+    let mint_supply = if mint_exists {
+        pinocchio_token::state::Mint::from_account_info(mint_info)?.supply()
+    } else {
+        0
+    };
 
-    if !treasury_ata_info.data_is_empty() {
+    // Nothing left to do, the program is already initialized
+    if archive_exists
+        && epoch_exists
+        && block_exists
+        && mint_exists
+        && metadata_exists
+        && treasury_exists
+        && treasury_ata_exists
+        && mint_supply != 0
+    {
         return Err(ProgramError::AccountAlreadyInitialized);
     }
-    if !treasury_ata_info.is_writable() {
-        return Err(ProgramError::Immutable);
-    }
 
     // Only check that tape_program_info matches TAPE_ID
     // Verify program ownership
     tape_program_info.is_program_check()?;
 
     // Initialize epoch
-    create_program_account::<Epoch>(
-        epoch_info,
-        system_program_info,
-        signer_info,
-        &TAPE_ID,
-        &[EPOCH],
-    )?;
-
-    // Set epoch fields
-    {
+    if !epoch_exists {
+        create_program_account_with_bump::<Epoch>(
+            epoch_info,
+            system_program_info,
+            signer_info,
+            &TAPE_ID,
+            &[EPOCH],
+            EPOCH_BUMP,
+        )?;
+
         let mut epoch_data = epoch_info.try_borrow_mut_data()?;
         let epoch = cast_account_data_mut::<Epoch>(&mut epoch_data)?;
         epoch.number = 1;
         epoch.progress = 0;
-        epoch.target_participation = MIN_PARTICIPATION_TARGET;
-        epoch.mining_difficulty = MIN_MINING_DIFFICULTY;
-        epoch.packing_difficulty = MIN_PACKING_DIFFICULTY;
-        epoch.reward_rate = get_base_rate(1);
+        epoch.target_participation = genesis.target_participation;
+        epoch.mining_difficulty = genesis.mining_difficulty;
+        epoch.packing_difficulty = genesis.packing_difficulty;
+        epoch.reward_rate = genesis.reward_rate;
         epoch.duplicates = 0;
+        epoch.epoch_rewards_distributed = 0;
         epoch.last_epoch_at = 0;
+        epoch.reward_rate_override = 0;
     }
 
     // Initialize block
-    create_program_account::<Block>(
-        block_info,
-        system_program_info,
-        signer_info,
-        &TAPE_ID,
-        &[BLOCK],
-    )?;
-
-    // Set block fields
-    {
+    if !block_exists {
+        create_program_account_with_bump::<Block>(
+            block_info,
+            system_program_info,
+            signer_info,
+            &TAPE_ID,
+            &[BLOCK],
+            BLOCK_BUMP,
+        )?;
+
         let mut block_data = block_info.try_borrow_mut_data()?;
         let block = cast_account_data_mut::<Block>(&mut block_data)?;
         block.number = 1;
@@ -210,22 +308,22 @@ pub fn process_initialize(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResu
         block.last_block_at = 0;
 
         // Compute next challenge using slot hashes
-        let next_challenge = compute_next_challenge(&BLOCK_ADDRESS.into(), slot_hashes_info)?;
+        let next_challenge = compute_next_challenge(&BLOCK_ADDRESS, slot_hashes_info)?;
         block.challenge = next_challenge;
         block.challenge_set = 1;
     }
 
     // Initialize archive
-    create_program_account::<Archive>(
-        archive_info,
-        system_program_info,
-        signer_info,
-        &TAPE_ID,
-        &[ARCHIVE],
-    )?;
-
-    // Set archive fields
-    {
+    if !archive_exists {
+        create_program_account_with_bump::<Archive>(
+            archive_info,
+            system_program_info,
+            signer_info,
+            &TAPE_ID,
+            &[ARCHIVE],
+            ARCHIVE_BUMP,
+        )?;
+
         let mut archive_data = archive_info.try_borrow_mut_data()?;
         let archive = cast_account_data_mut::<Archive>(&mut archive_data)?;
         archive.tapes_stored = 0;
@@ -233,16 +331,24 @@ pub fn process_initialize(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResu
     }
 
     // Initialize treasury
-    create_program_account::<Treasury>(
-        treasury_info,
-        system_program_info,
-        signer_info,
-        &TAPE_ID,
-        &[TREASURY],
-    )?;
+    if !treasury_exists {
+        create_program_account_with_bump::<Treasury>(
+            treasury_info,
+            system_program_info,
+            signer_info,
+            &TAPE_ID,
+            &[TREASURY],
+            TREASURY_BUMP,
+        )?;
+
+        // The deployer becomes the treasury authority
+        let mut treasury_data = treasury_info.try_borrow_mut_data()?;
+        let treasury = cast_account_data_mut::<Treasury>(&mut treasury_data)?;
+        treasury.authority = *signer_info.key();
+    }
 
     // Initialize mint
-    {
+    if !mint_exists {
         let rent = Rent::get()?;
         let mint_space = pinocchio_token::state::Mint::LEN;
         let lamports = rent.minimum_balance(mint_space);
@@ -277,11 +383,11 @@ pub fn process_initialize(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResu
     }
 
     // Initialize mint metadata using Pinocchio CPI with Borsh serialization
-    {
+    if !metadata_exists {
         let instruction_data = build_metadata_instruction_data_borsh(
-            METADATA_NAME,
-            METADATA_SYMBOL,
-            METADATA_URI,
+            metadata_name,
+            metadata_symbol,
+            metadata_uri,
             0,    // seller_fee_basis_points
             true, // is_mutable
         )?;
@@ -332,18 +438,20 @@ pub fn process_initialize(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResu
     }
 
     // Initialize treasury ATA
-    CreateATA {
-        funding_account: signer_info,
-        account: treasury_ata_info,
-        wallet: treasury_info,
-        mint: mint_info,
-        system_program: system_program_info,
-        token_program: token_program_info,
+    if !treasury_ata_exists {
+        CreateATA {
+            funding_account: signer_info,
+            account: treasury_ata_info,
+            wallet: treasury_info,
+            mint: mint_info,
+            system_program: system_program_info,
+            token_program: token_program_info,
+        }
+        .invoke()?;
     }
-    .invoke()?;
 
-    // Fund the treasury token account with MAX_SUPPLY
-    {
+    // Fund the treasury token account with MAX_SUPPLY, unless already minted
+    if mint_supply == 0 {
         let treasury_bump_binding = [TREASURY_BUMP];
         let treasury_seeds = [
             Seed::from(TREASURY),
@@ -360,5 +468,11 @@ pub fn process_initialize(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResu
         .invoke_signed(&treasury_signer)?;
     }
 
+    // Report the genesis tape and writer so deploy tooling can capture them
+    let mut return_data = [0u8; 64];
+    return_data[..32].copy_from_slice(&GENESIS_TAPE_ADDRESS);
+    return_data[32..].copy_from_slice(&GENESIS_WRITER_ADDRESS);
+    set_return_data(&return_data);
+
     Ok(())
 }
@@ -1,5 +1,9 @@
 pub mod airdrop;
+pub mod epoch_report;
 pub mod initialize;
+pub mod set_reward_rate;
 
 pub use airdrop::*;
+pub use epoch_report::*;
 pub use initialize::*;
+pub use set_reward_rate::*;
@@ -0,0 +1,54 @@
+use crate::instruction::mine::miner_mine::get_base_rate;
+use crate::instruction::SetRewardRate;
+use crate::state::{Epoch, Treasury, EPOCH_ADDRESS, TAPE_ID, TREASURY_ADDRESS};
+use crate::utils::helpers::{cast_account_data, cast_account_data_mut};
+use crate::utils::ByteConversion;
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+
+/// Emergency override of the epoch reward rate, signed by the treasury
+/// authority (the signer of initialize). The rate is clamped to the first
+/// year's base rate, the same ceiling InitializeWithConfig enforces, and
+/// replaces the current rate as well as the schedule at every later epoch.
+/// A rate of zero clears the override, so the next epoch follows the
+/// schedule again.
+pub fn process_set_reward_rate(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let args = SetRewardRate::try_from_bytes(data)?;
+
+    let [signer_info, epoch_info, treasury_info, _remaining @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if treasury_info.key().ne(&TREASURY_ADDRESS) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if !treasury_info.is_owned_by(&TAPE_ID) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let treasury_data = treasury_info.try_borrow_data()?;
+    let treasury = cast_account_data::<Treasury>(&treasury_data)?;
+
+    if !signer_info.is_signer() || signer_info.key().ne(&treasury.authority) {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if epoch_info.key().ne(&EPOCH_ADDRESS) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if !epoch_info.is_owned_by(&TAPE_ID) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let mut epoch_data = epoch_info.try_borrow_mut_data()?;
+    let epoch = cast_account_data_mut::<Epoch>(&mut epoch_data)?;
+
+    let reward_rate = u64::from_le_bytes(args.reward_rate).min(get_base_rate(1));
+    if reward_rate != 0 {
+        epoch.reward_rate = reward_rate;
+    }
+    epoch.reward_rate_override = reward_rate;
+
+    Ok(())
+}
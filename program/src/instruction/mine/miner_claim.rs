@@ -53,6 +53,11 @@ pub fn process_claim(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
     }
     drop(beneficiary_data);
 
+    // Only a program-owned miner can have earned rewards
+    if !miner_info.is_owned_by(&tape_api::ID) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
     // Load and validate miner account
     let mut miner_data = miner_info.try_borrow_mut_data()?;
     let miner = Miner::unpack_mut(&mut miner_data)?;
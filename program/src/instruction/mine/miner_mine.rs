@@ -1,8 +1,12 @@
 use crate::{
-    api::utils::{compute_challenge, compute_next_challenge},
+    api::utils::{
+        check_tape_balance, compute_challenge, compute_commitment, compute_next_challenge,
+    },
     state::{
-        try_from_account_info_mut, Archive, Block, Epoch, Mine, Miner, PoA, PoW, Tape,
-        ADJUSTMENT_INTERVAL, BLOCK_DURATION_SECONDS, EPOCH_BLOCKS,
+        pda::{miner_derive_pda, tape_derive_pda},
+        try_from_account_info_mut, try_from_account_info_unchecked,
+        try_from_account_info_unchecked_mut, Archive, Block, Epoch, Mine, Miner, PoA, PoW, Spool,
+        Tape, ADJUSTMENT_INTERVAL, BLOCK_DURATION_SECONDS, EPOCH_BLOCKS,
     },
 };
 use brine_tree::{verify, Leaf};
@@ -13,16 +17,27 @@ use pinocchio::{
     sysvars::{clock::Clock, Sysvar},
     ProgramResult,
 };
+use pinocchio_log::log;
 use tape_api::{
-    error::TapeError, pda::miner_pda, EMPTY_SEGMENT, MAX_CONSISTENCY_MULTIPLIER,
-    MAX_PARTICIPATION_TARGET, MIN_CONSISTENCY_MULTIPLIER, MIN_MINING_DIFFICULTY,
-    MIN_PARTICIPATION_TARGET, SEGMENT_PROOF_LEN,
+    emission::scaled_reward,
+    error::TapeError,
+    event::{ChainAdvancedEvent, MineEvent, SlashEvent},
+    rent::block_from_slot,
+    EMPTY_SEGMENT, EPOCHS_PER_YEAR, MAX_CONSISTENCY_MULTIPLIER, MAX_DIFFICULTY_STEP,
+    MAX_MINING_DIFFICULTY, MAX_PARTICIPATION_TARGET, MAX_RECENCY_BONUS_BPS, MINER_SLASH_AMOUNT,
+    MIN_CONSISTENCY_MULTIPLIER, MIN_MINING_DIFFICULTY, MIN_PARTICIPATION_TARGET,
+    RECENCY_WINDOW_SLOTS, SEGMENT_PROOF_LEN, TREASURY_ADDRESS,
+};
+use tape_utils::{
+    error::BrineTreeError,
+    leaf::Hash,
+    tree::{check_proof_length, verify_at},
 };
-
-const EPOCHS_PER_YEAR: u64 = 365 * 24 * 60 / EPOCH_BLOCKS;
 
 pub fn process_mine(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
-    let [signer_info, epoch_info, block_info, miner_info, tape_info, archive_info, slot_hashes_info] =
+    let args = Mine::try_from_bytes(data)?;
+
+    let [signer_info, epoch_info, block_info, miner_info, tape_info, archive_info, slot_hashes_info, spool_info, treasury_info] =
         accounts
     else {
         return Err(ProgramError::NotEnoughAccountKeys);
@@ -52,23 +67,59 @@ pub fn process_mine(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
         return Err(ProgramError::InvalidAccountData);
     }
 
+    if spool_info.owner() != &crate::id() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Slashed lamports go straight to the treasury
+    if treasury_info.key() != &TREASURY_ADDRESS {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // The singletons from initialize carry a type byte; tapes, miners and
+    // spools are stored bare
     let archive = unsafe { try_from_account_info_mut::<Archive>(archive_info)? };
     let epoch = unsafe { try_from_account_info_mut::<Epoch>(epoch_info)? };
     let block = unsafe { try_from_account_info_mut::<Block>(block_info)? };
-    let tape = unsafe { try_from_account_info_mut::<Tape>(tape_info)? };
-    let miner = unsafe { try_from_account_info_mut::<Miner>(miner_info)? };
+    let tape = unsafe { try_from_account_info_unchecked_mut::<Tape>(tape_info)? };
+    let miner = unsafe { try_from_account_info_unchecked_mut::<Miner>(miner_info)? };
+    let spool = unsafe { try_from_account_info_unchecked::<Spool>(spool_info)? };
 
-    let (miner_address, _miner_bump) = miner_pda(miner.authority, miner.name);
+    // The recorded balance must be backed by actual lamports
+    check_tape_balance(tape.balance, tape_info)?;
+
+    // Derive with the cached bump instead of searching for it
+    let miner_address = miner_derive_pda(miner.authority, &miner.name, miner.bump as u8);
 
     if miner_info.key() != &miner_address {
         return Err(ProgramError::InvalidSeeds);
     }
 
+    // Any program account can hold a copy of a tape's data, only the PDA counts
+    let tape_address = tape_derive_pda(tape.creator, &tape.name, tape.bump as u8);
+
+    if tape_info.key() != &tape_address {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
     if signer_info.key() != &miner.authority {
         return Err(ProgramError::InvalidAccountOwner);
     }
 
-    let current_time = Clock::get()?.unix_timestamp;
+    if spool.authority != miner.authority {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    // The commitment must have been made against the spool as it is now
+    let spool_root = spool.state.get_root().to_bytes();
+    let commitment = compute_commitment(&args.value, &spool.contains, &spool_root);
+    check_condition(
+        Hash::from(miner.commitment).ct_eq(&Hash::from(commitment)),
+        TapeError::CommitmentMismatch,
+    )?;
+
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
     check_submission(miner, block, epoch, current_time)?;
 
     let miner_challenge = compute_challenge(&block.challenge, &miner.challenge);
@@ -79,44 +130,111 @@ pub fn process_mine(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
         return Err(TapeError::UnexpectedTape.into());
     }
 
-    let args = Mine::try_from_bytes(data)?;
+    // A finalized tape always has segments, but never recall from an empty one
+    check_condition(tape.total_segments > 0, TapeError::EmptyTape)?;
+
+    // The recalled tape must be one the miner actually packed into their spool
+    let spool_leaf = Leaf::new(&[tape.number.to_le_bytes().as_ref(), &args.tape_value]);
+    check_condition(
+        verify(spool_root, &args.tape_proof, spool_leaf),
+        TapeError::SpoolMissingTape,
+    )?;
 
-    verify_solution(
+    let verified = verify_solution(
         epoch,
         tape,
         &miner.authority,
         &miner_challenge,
         args.pow,
         args.poa,
-    )?;
+    );
+
+    // An invalid proof forfeits part of the deposit to the treasury. A failed
+    // instruction would roll the slash back, so it ends the proof successfully
+    // and reports `SolutionSlashed` through its own event instead.
+    if let Err(err) = verified {
+        if err != TapeError::SolutionInvalid.into() {
+            return Err(err);
+        }
+
+        let amount = slash_deposit(miner);
+        // The commitment was spent on this attempt
+        miner.commitment = [0; 32];
+
+        *miner_info.try_borrow_mut_lamports()? -= amount;
+        *treasury_info.try_borrow_mut_lamports()? += amount;
+
+        log!("Error: {}", TapeError::SolutionSlashed as u64);
+        SlashEvent {
+            error: TapeError::SolutionSlashed as u64,
+            amount,
+            deposit: miner.deposit,
+            miner: miner_address,
+        }
+        .log();
+
+        return Ok(());
+    }
 
     // Update miner
     update_multiplier(miner, block);
 
     let next_challenge = compute_next_challenge(&miner.challenge, slot_hashes_info)?;
 
-    let reward = calculate_reward(epoch, tape, miner.multiplier);
+    let reward = calculate_reward(epoch, tape, miner.multiplier, clock.slot)?;
+    record_reward(epoch, reward);
 
     update_miner_state(miner, block, reward, current_time, next_challenge);
 
-    update_tape_balance(tape, block.number);
+    update_tape_balance(tape, block_from_slot(clock.slot));
 
-    block.progress = block.progress.saturating_add(1);
+    MineEvent {
+        tape: tape.number,
+        reward,
+        block: block.number,
+        miner: miner_address,
+    }
+    .log();
 
-    if block.progress >= epoch.target_participation {
-        advance_block(block, current_time)?;
+    let event = advance_chain(epoch, block, archive, current_time)?;
 
+    if event.block_advanced == 1 {
         let next_block_challenges = compute_next_challenge(&block.challenge, slot_hashes_info)?;
 
         block.challenge = next_block_challenges;
         block.challenge_set = archive.tapes_stored;
     }
 
-    update_epoch(epoch, archive, current_time)?;
+    event.log();
 
     Ok(())
 }
 
+// Helper: count the proof towards the block and epoch, advancing either when
+// it completes them, and describe the resulting chain state
+fn advance_chain(
+    epoch: &mut Epoch,
+    block: &mut Block,
+    archive: &Archive,
+    current_time: i64,
+) -> Result<ChainAdvancedEvent, ProgramError> {
+    block.progress = block.progress.saturating_add(1);
+
+    let block_advanced = block.progress >= epoch.target_participation;
+    if block_advanced {
+        advance_block(block, current_time)?;
+    }
+
+    let epoch_advanced = update_epoch(epoch, archive, current_time)?;
+
+    Ok(ChainAdvancedEvent {
+        new_block: block.number,
+        new_epoch: epoch.number,
+        block_advanced: block_advanced as u64,
+        epoch_advanced: epoch_advanced as u64,
+    })
+}
+
 // Helper: Advance the block state
 fn advance_block(block: &mut Block, current_time: i64) -> ProgramResult {
     //  reset the block state
@@ -202,22 +320,31 @@ fn verify_solution(
     )?;
 
     // Check if the tape can be mined.
-    if tape.has_minimum_rent() {
+    if uses_recall_segment(tape) {
         let segment_number = compute_recall_segment(miner_challenge, tape.total_segments);
 
         let merkle_proof = poa.path.as_ref();
         let merkle_root = tape.merkle_root;
         let recall_segment = poa_solution.unpack(&miner_address);
 
-        assert!(merkle_proof.len() == SEGMENT_PROOF_LEN);
-
-        let leaf = Leaf::new(&[
-            segment_number.to_le_bytes().as_ref(),
-            recall_segment.as_ref(),
-        ]);
+        // Name both depths so a client sending the wrong tree's proof can tell.
+        // A malformed proof fails the instruction rather than slashing.
+        if let Err(BrineTreeError::ProofLength { expected, got }) =
+            check_proof_length(merkle_proof.len(), SEGMENT_PROOF_LEN)
+        {
+            log!("Proof length mismatch: expected {}, got {}", expected, got);
+            return Err(TapeError::ProofLength.into());
+        }
 
+        // The proof must place the segment at the recalled index, not just
+        // anywhere in the tape
         check_condition(
-            verify(merkle_root, merkle_proof, leaf),
+            verify_at(
+                merkle_root,
+                merkle_proof,
+                recall_segment.as_ref(),
+                segment_number,
+            ),
             TapeError::SolutionInvalid,
         )?;
 
@@ -243,6 +370,13 @@ fn verify_solution(
     Ok(())
 }
 
+/// Whether a solution must prove a recalled segment of `tape`, rather than
+/// the fixed `EMPTY_SEGMENT`. A tape with no segments has nothing to recall,
+/// even if it is funded, so it takes the same path as an expired tape.
+fn uses_recall_segment(tape: &Tape) -> bool {
+    tape.total_segments > 0 && tape.has_minimum_rent()
+}
+
 fn update_multiplier(miner: &mut Miner, block: &Block) {
     if miner.last_proof_block.saturating_add(1) == block.number {
         miner.multiplier = miner
@@ -269,28 +403,65 @@ where
     Ok(())
 }
 
-// Helper: Get the scaled reward based on miner's consistency multiplier.
-fn get_scaled_reward(reward: u64, multiplier: u64) -> u64 {
-    assert!(multiplier >= MIN_CONSISTENCY_MULTIPLIER);
-    assert!(multiplier <= MAX_CONSISTENCY_MULTIPLIER);
+/// Bonus on `reward` for a tape last written `tail_slot`, decaying linearly
+/// from `MAX_RECENCY_BONUS_BPS` at the write to nothing after
+/// `RECENCY_WINDOW_SLOTS`. The caller reserves room for it out of the
+/// per-proof share of `reward_rate`.
+fn recency_bonus(reward: u64, tail_slot: u64, current_slot: u64) -> u64 {
+    let age = current_slot.saturating_sub(tail_slot);
+    if age >= RECENCY_WINDOW_SLOTS {
+        return 0;
+    }
 
-    reward
-        .saturating_mul(multiplier)
-        .saturating_div(MAX_CONSISTENCY_MULTIPLIER)
+    let bonus_bps = MAX_RECENCY_BONUS_BPS * (RECENCY_WINDOW_SLOTS - age) / RECENCY_WINDOW_SLOTS;
+    reward.saturating_mul(bonus_bps).saturating_div(10_000)
 }
 
-fn calculate_reward(epoch: &Epoch, tape: &Tape, multiplier: u64) -> u64 {
+fn calculate_reward(
+    epoch: &Epoch,
+    tape: &Tape,
+    multiplier: u64,
+    current_slot: u64,
+) -> Result<u64, ProgramError> {
+    // saturating_div panics on a zero divisor, fail with a clear error instead
+    check_condition(epoch.target_participation > 0, TapeError::InvalidEpochState)?;
+
     // divide the scaled reward by the target participation, each miner gets an equal share
     let available_reward = epoch.reward_rate.saturating_div(epoch.target_participation);
 
-    let scaled_reward = get_scaled_reward(available_reward, multiplier);
+    // hold back enough of the share for the largest recency bonus, so a full
+    // reward plus bonus never pays out more than the share itself
+    let base_reward =
+        (available_reward as u128 * 10_000 / (10_000 + MAX_RECENCY_BONUS_BPS) as u128) as u64;
+
+    let scaled = scaled_reward(base_reward, multiplier);
 
     // if the tape is subsidized, miner will get full rewards
-    if tape.has_minimum_rent() {
-        scaled_reward
+    let reward = if tape.has_minimum_rent() {
+        scaled
     } else {
-        scaled_reward.saturating_div(2)
-    }
+        scaled.saturating_div(2)
+    };
+
+    // keeping actively written tapes available earns a little extra, paid
+    // from the reserved part of the share, so the bonus never adds to emissions
+    let bonus = recency_bonus(reward, tape.tail_slot, current_slot);
+    Ok(reward.saturating_add(bonus))
+}
+
+/// Adds a paid reward to the epoch's running total, so emissions can be
+/// audited against the schedule.
+fn record_reward(epoch: &mut Epoch, reward: u64) {
+    epoch.epoch_rewards_distributed = epoch.epoch_rewards_distributed.saturating_add(reward);
+}
+
+/// Takes up to `MINER_SLASH_AMOUNT` off the deposit and adds it to the
+/// forfeited total, returning the lamports the caller pays to the treasury.
+fn slash_deposit(miner: &mut Miner) -> u64 {
+    let amount = miner.deposit.min(MINER_SLASH_AMOUNT);
+    miner.deposit -= amount;
+    miner.forfeited = miner.forfeited.saturating_add(amount);
+    amount
 }
 
 fn update_miner_state(
@@ -300,33 +471,50 @@ fn update_miner_state(
     current_time: i64,
     next_miner_challenge: [u8; 32],
 ) {
-    miner.unclaimed_rewards += final_reward;
-    miner.total_rewards += final_reward;
-    miner.total_proofs += 1;
+    miner.unclaimed_rewards = miner.unclaimed_rewards.saturating_add(final_reward);
+    miner.total_rewards = miner.total_rewards.saturating_add(final_reward);
+    miner.total_proofs = miner.total_proofs.saturating_add(1);
     miner.last_proof_block = block.number;
     miner.challenge = next_miner_challenge;
     miner.last_proof_at = current_time;
+    // A commitment backs a single proof, the next one needs a fresh commit
+    miner.commitment = [0; 32];
 }
 
-fn update_tape_balance(tape: &mut Tape, block_number: u64) {
-    let rent = tape.rent_owed(block_number);
-    tape.balance = tape.balance.saturating_sub(rent);
+/// Charges the rent owed up to `current_block`, a slot-derived block from
+/// `block_from_slot`, and moves `last_rent_block` there so it isn't owed twice.
+fn update_tape_balance(tape: &mut Tape, current_block: u64) {
+    // Expired tapes are mined against the empty segment and owe nothing more
+    if tape.has_minimum_rent() {
+        let rent = tape.rent_owed(current_block);
+        tape.balance = tape.balance.saturating_sub(rent);
+    }
+
+    tape.last_rent_block = tape.last_rent_block.max(current_block);
 }
 
-fn update_epoch(epoch: &mut Epoch, archive: &Archive, current_time: i64) -> ProgramResult {
-    // check if we need to advance the epoch
+// Returns whether the epoch advanced
+fn update_epoch(
+    epoch: &mut Epoch,
+    archive: &Archive,
+    current_time: i64,
+) -> Result<bool, ProgramError> {
+    // Count this proof first, so the epoch advances on the EPOCH_BLOCKS-th
+    // proof rather than the one after it
+    epoch.progress = epoch.progress.saturating_add(1);
+
     if epoch.progress >= EPOCH_BLOCKS {
         advance_epoch(epoch, current_time)?;
 
-        let base_rate = get_base_rate(epoch.number);
-        let storage_rate = archive.block_reward();
-
-        epoch.reward_rate = storage_rate.saturating_add(base_rate);
-    // Epoch is still in progress, increment the progress
-    } else {
-        epoch.progress = epoch.progress.saturating_add(1);
+        // An admin override holds until it is cleared
+        epoch.reward_rate = if epoch.reward_rate_override != 0 {
+            epoch.reward_rate_override
+        } else {
+            get_reward_rate(epoch.number, archive)
+        };
+        return Ok(true);
     }
-    Ok(())
+    Ok(false)
 }
 
 // helper - advance epoch state
@@ -338,6 +526,7 @@ fn advance_epoch(epoch: &mut Epoch, current_time: i64) -> ProgramResult {
     epoch.last_epoch_at = current_time;
     epoch.progress = 0;
     epoch.duplicates = 0;
+    epoch.epoch_rewards_distributed = 0;
     epoch.mining_difficulty = epoch.mining_difficulty.max(MIN_MINING_DIFFICULTY);
     epoch.target_participation = epoch.target_participation.max(MIN_PARTICIPATION_TARGET);
 
@@ -360,16 +549,27 @@ fn adjust_participation(epoch: &mut Epoch) {
     }
 }
 
+/// Retargets the mining difficulty by a step proportional to how far the
+/// epoch was from its target duration, e.g. an epoch twice as fast as
+/// `BLOCK_DURATION_SECONDS` per block moves it up by 2, clamped to
+/// `MAX_DIFFICULTY_STEP`.
 fn adjust_difficulty(epoch: &mut Epoch, current_time: i64) {
-    let elapsed_time = current_time.saturating_sub(epoch.last_epoch_at);
-    let average_time_per_block = elapsed_time / EPOCH_BLOCKS as i64;
+    // Compare whole epochs rather than a truncated per-block average, and
+    // treat a stalled clock as the fastest possible epoch
+    let elapsed_time = current_time.saturating_sub(epoch.last_epoch_at).max(1) as u64;
+    let target_time = BLOCK_DURATION_SECONDS * EPOCH_BLOCKS;
 
-    if average_time_per_block < BLOCK_DURATION_SECONDS as i64 {
-        epoch.mining_difficulty = epoch.mining_difficulty.saturating_add(1);
+    if elapsed_time < target_time {
+        let step = (target_time / elapsed_time).clamp(1, MAX_DIFFICULTY_STEP);
+        epoch.mining_difficulty = epoch
+            .mining_difficulty
+            .saturating_add(step)
+            .min(MAX_MINING_DIFFICULTY);
     } else {
+        let step = (elapsed_time / target_time).clamp(1, MAX_DIFFICULTY_STEP);
         epoch.mining_difficulty = epoch
             .mining_difficulty
-            .saturating_sub(1)
+            .saturating_sub(step)
             .max(MIN_MINING_DIFFICULTY);
     }
 }
@@ -409,3 +609,563 @@ pub fn get_base_rate(current_epoch: u64) -> u64 {
         _ => 0,
     }
 }
+
+/// Reward rate for an epoch: the base rate plus the archive storage fees.
+#[inline(always)]
+pub fn get_reward_rate(current_epoch: u64, archive: &Archive) -> u64 {
+    let base_rate = get_base_rate(current_epoch);
+    let storage_rate = archive.block_reward();
+
+    storage_rate.saturating_add(base_rate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytemuck::Zeroable;
+    use tape_api::{rent::blocks_elapsed, SLOTS_PER_BLOCK};
+
+    #[test]
+    fn difficulty_caps_at_maximum_under_fast_epochs() {
+        let mut epoch = Epoch::zeroed();
+        epoch.number = 1;
+        epoch.mining_difficulty = MIN_MINING_DIFFICULTY;
+        epoch.target_participation = MIN_PARTICIPATION_TARGET;
+
+        // Every epoch completes in a single second, far below the block duration
+        let mut current_time = 0;
+        for _ in 0..(MAX_MINING_DIFFICULTY * 4) {
+            current_time += 1;
+            advance_epoch(&mut epoch, current_time).unwrap();
+            assert!(epoch.mining_difficulty <= MAX_MINING_DIFFICULTY);
+        }
+
+        assert_eq!(epoch.mining_difficulty, MAX_MINING_DIFFICULTY);
+    }
+
+    #[test]
+    fn difficulty_decreases_from_maximum_when_blocks_slow_down() {
+        let mut epoch = Epoch::zeroed();
+        epoch.mining_difficulty = MAX_MINING_DIFFICULTY;
+
+        let slow_epoch = (BLOCK_DURATION_SECONDS * EPOCH_BLOCKS * 2) as i64;
+        adjust_difficulty(&mut epoch, slow_epoch);
+
+        assert_eq!(epoch.mining_difficulty, MAX_MINING_DIFFICULTY - 2);
+    }
+
+    #[test]
+    fn difficulty_step_scales_with_epoch_speed() {
+        let target_epoch = (BLOCK_DURATION_SECONDS * EPOCH_BLOCKS) as i64;
+        let mut epoch = Epoch::zeroed();
+
+        // Twice too fast
+        epoch.mining_difficulty = 10;
+        adjust_difficulty(&mut epoch, target_epoch / 2);
+        assert_eq!(epoch.mining_difficulty, 12);
+
+        // Twice too slow
+        epoch.mining_difficulty = 10;
+        adjust_difficulty(&mut epoch, target_epoch * 2);
+        assert_eq!(epoch.mining_difficulty, 8);
+
+        // Just off target in either direction still moves by one
+        epoch.mining_difficulty = 10;
+        adjust_difficulty(&mut epoch, target_epoch - 1);
+        assert_eq!(epoch.mining_difficulty, 11);
+
+        epoch.mining_difficulty = 10;
+        adjust_difficulty(&mut epoch, target_epoch);
+        assert_eq!(epoch.mining_difficulty, 9);
+    }
+
+    #[test]
+    fn difficulty_step_is_clamped() {
+        let target_epoch = (BLOCK_DURATION_SECONDS * EPOCH_BLOCKS) as i64;
+        let mut epoch = Epoch::zeroed();
+
+        // A stalled clock counts as the fastest possible epoch
+        epoch.mining_difficulty = 10;
+        adjust_difficulty(&mut epoch, 0);
+        assert_eq!(epoch.mining_difficulty, 10 + MAX_DIFFICULTY_STEP);
+
+        epoch.mining_difficulty = 10;
+        adjust_difficulty(&mut epoch, target_epoch * 100);
+        assert_eq!(epoch.mining_difficulty, 10 - MAX_DIFFICULTY_STEP);
+
+        // The minimum still holds as the floor
+        epoch.mining_difficulty = MIN_MINING_DIFFICULTY + 1;
+        adjust_difficulty(&mut epoch, target_epoch * 100);
+        assert_eq!(epoch.mining_difficulty, MIN_MINING_DIFFICULTY);
+    }
+
+    #[test]
+    fn expired_tape_balance_is_not_charged() {
+        let mut tape = Tape::zeroed();
+        tape.total_segments = 1;
+        tape.balance = tape.rent_per_block() - 1;
+        assert!(!tape.has_minimum_rent());
+
+        update_tape_balance(&mut tape, 10);
+        assert_eq!(tape.balance, tape.rent_per_block() - 1);
+
+        // An expired tape with nothing left stays at zero
+        tape.balance = 0;
+        update_tape_balance(&mut tape, 10);
+        assert_eq!(tape.balance, 0);
+    }
+
+    #[test]
+    fn funded_empty_tape_uses_empty_segment() {
+        let mut tape = Tape::zeroed();
+        tape.balance = tape_api::RENT_PER_SEGMENT * 10;
+
+        // Rent is zero with no segments, so the tape always looks funded
+        assert!(tape.has_minimum_rent());
+        assert!(!uses_recall_segment(&tape));
+
+        tape.total_segments = 10;
+        assert!(uses_recall_segment(&tape));
+
+        tape.balance = 0;
+        assert!(!uses_recall_segment(&tape));
+    }
+
+    #[test]
+    fn subsidized_tape_balance_is_charged() {
+        let mut tape = Tape::zeroed();
+        tape.total_segments = 1;
+        tape.balance = tape.rent_per_block() * 20;
+
+        update_tape_balance(&mut tape, 10);
+        assert_eq!(tape.balance, tape.rent_per_block() * 10);
+        assert_eq!(tape.last_rent_block, 10);
+
+        // Rent already paid up to a block isn't charged again
+        update_tape_balance(&mut tape, 10);
+        assert_eq!(tape.balance, tape.rent_per_block() * 10);
+
+        update_tape_balance(&mut tape, 12);
+        assert_eq!(tape.balance, tape.rent_per_block() * 8);
+    }
+
+    #[test]
+    fn tape_rent_is_timed_in_slot_blocks() {
+        let mut tape = Tape::zeroed();
+        tape.total_segments = 1;
+        tape.balance = tape.rent_per_block() * 20;
+
+        // A block of slots owes a block of rent, a few slots more owe nothing
+        let slot = SLOTS_PER_BLOCK * 3 + 1;
+        update_tape_balance(&mut tape, block_from_slot(slot));
+        assert_eq!(tape.balance, tape.rent_per_block() * 17);
+        assert_eq!(tape.last_rent_block, blocks_elapsed(0, slot));
+    }
+
+    #[test]
+    fn genesis_reward_rate_continues_into_next_epoch() {
+        // The rate initialize sets for epoch 1 with an empty archive
+        let archive = Archive::zeroed();
+        let mut epoch = Epoch::zeroed();
+        epoch.number = 1;
+        epoch.reward_rate = get_reward_rate(1, &archive);
+        assert_eq!(epoch.reward_rate, get_base_rate(1));
+
+        for _ in 0..EPOCH_BLOCKS {
+            update_epoch(&mut epoch, &archive, 0).unwrap();
+        }
+
+        // Epoch 2 is still in the first year, so an unchanged archive keeps
+        // the same rate across the boundary
+        assert_eq!(epoch.number, 2);
+        assert_eq!(epoch.reward_rate, get_reward_rate(1, &archive));
+    }
+
+    #[test]
+    fn reward_rate_override_survives_epoch_advance() {
+        let archive = Archive::zeroed();
+        let mut epoch = Epoch::zeroed();
+        epoch.number = 1;
+        epoch.reward_rate = 1234;
+        epoch.reward_rate_override = 1234;
+
+        for _ in 0..EPOCH_BLOCKS * 2 {
+            update_epoch(&mut epoch, &archive, 0).unwrap();
+        }
+        assert_eq!(epoch.number, 3);
+        assert_eq!(epoch.reward_rate, 1234);
+
+        // Once cleared, the next epoch is back on the schedule
+        epoch.reward_rate_override = 0;
+        for _ in 0..EPOCH_BLOCKS {
+            update_epoch(&mut epoch, &archive, 0).unwrap();
+        }
+        assert_eq!(epoch.reward_rate, get_reward_rate(4, &archive));
+    }
+
+    #[test]
+    fn reward_rate_adds_storage_fees_to_base_rate() {
+        let archive = Archive {
+            tapes_stored: 3,
+            segments_stored: 1_000,
+        };
+        assert_eq!(
+            archive.total_storage_fees(),
+            1_000 * tape_api::RENT_PER_SEGMENT
+        );
+        assert_eq!(
+            get_reward_rate(1, &archive),
+            get_base_rate(1) + archive.block_reward()
+        );
+
+        // Matches the api's copy integrators use to predict rates
+        let api_archive = crate::api::state::Archive {
+            tapes_stored: archive.tapes_stored,
+            segments_stored: archive.segments_stored,
+        };
+        assert_eq!(archive.block_reward(), api_archive.block_reward());
+    }
+
+    #[test]
+    fn epoch_advances_exactly_once_after_epoch_blocks() {
+        let mut epoch = Epoch::zeroed();
+        epoch.number = 1;
+        let archive = Archive::zeroed();
+
+        for i in 1..EPOCH_BLOCKS {
+            update_epoch(&mut epoch, &archive, 0).unwrap();
+            assert_eq!(epoch.number, 1, "advanced early at proof {}", i);
+            assert_eq!(epoch.progress, i);
+        }
+
+        // The EPOCH_BLOCKS-th proof closes the epoch
+        update_epoch(&mut epoch, &archive, 0).unwrap();
+        assert_eq!(epoch.number, 2);
+        assert_eq!(epoch.progress, 0);
+
+        // The next proof starts counting the new epoch
+        update_epoch(&mut epoch, &archive, 0).unwrap();
+        assert_eq!(epoch.number, 2);
+        assert_eq!(epoch.progress, 1);
+    }
+
+    #[test]
+    fn proof_consumes_commitment() {
+        let mut miner = Miner::zeroed();
+        miner.commitment = [9; 32];
+        let block = Block::zeroed();
+
+        update_miner_state(&mut miner, &block, 0, 0, [0; 32]);
+        assert_eq!(miner.commitment, [0; 32]);
+    }
+
+    #[test]
+    fn miner_rewards_saturate_instead_of_overflowing() {
+        let mut miner = Miner::zeroed();
+        miner.unclaimed_rewards = u64::MAX - 10;
+        miner.total_rewards = u64::MAX - 10;
+        let block = Block::zeroed();
+
+        update_miner_state(&mut miner, &block, 100, 0, [0; 32]);
+        assert_eq!(miner.unclaimed_rewards, u64::MAX);
+        assert_eq!(miner.total_rewards, u64::MAX);
+        assert_eq!(miner.total_proofs, 1);
+    }
+
+    #[test]
+    fn slashing_drains_deposit_without_underflow() {
+        let mut miner = Miner::zeroed();
+        miner.deposit = MINER_SLASH_AMOUNT * 2 + 1;
+
+        assert_eq!(slash_deposit(&mut miner), MINER_SLASH_AMOUNT);
+        assert_eq!(slash_deposit(&mut miner), MINER_SLASH_AMOUNT);
+        assert_eq!(miner.deposit, 1);
+        assert_eq!(miner.forfeited, MINER_SLASH_AMOUNT * 2);
+
+        // Only what is left can be forfeited
+        assert_eq!(slash_deposit(&mut miner), 1);
+        assert_eq!(slash_deposit(&mut miner), 0);
+        assert_eq!(miner.deposit, 0);
+        assert_eq!(miner.forfeited, MINER_SLASH_AMOUNT * 2 + 1);
+    }
+
+    #[test]
+    fn participation_and_difficulty_adjust_independently() {
+        let archive = Archive::zeroed();
+        let mut block = Block::zeroed();
+        let mut epoch = Epoch::zeroed();
+        // Participation may only grow on an adjustment-interval epoch
+        epoch.number = ADJUSTMENT_INTERVAL;
+        epoch.mining_difficulty = 5;
+        epoch.target_participation = 2;
+
+        // Epoch 1: fast proofs, no duplicates, on an adjustment interval.
+        // Both participation and difficulty go up.
+        let mut current_time = 0;
+        for _ in 0..EPOCH_BLOCKS {
+            current_time += 1;
+            advance_chain(&mut epoch, &mut block, &archive, current_time).unwrap();
+        }
+        assert_eq!(epoch.number, ADJUSTMENT_INTERVAL + 1);
+        assert_eq!(epoch.target_participation, 3);
+        assert_eq!(epoch.mining_difficulty, 5 + MAX_DIFFICULTY_STEP);
+        // Two proofs per block
+        assert_eq!(block.number, 5);
+        assert_eq!(block.progress, 0);
+
+        // Epoch 2: slow proofs, no duplicates, off the interval.
+        // Only difficulty moves, back down.
+        for _ in 0..EPOCH_BLOCKS {
+            current_time += 2 * BLOCK_DURATION_SECONDS as i64;
+            advance_chain(&mut epoch, &mut block, &archive, current_time).unwrap();
+        }
+        assert_eq!(epoch.number, ADJUSTMENT_INTERVAL + 2);
+        assert_eq!(epoch.target_participation, 3);
+        assert_eq!(epoch.mining_difficulty, 5 + MAX_DIFFICULTY_STEP - 2);
+        // Three proofs per block now, with one proof carried into block 8
+        assert_eq!(block.number, 8);
+        assert_eq!(block.progress, 1);
+
+        // Epoch 3: fast proofs, but a stalled block accepted a duplicate
+        // (see `check_submission`). Participation drops while difficulty rises.
+        epoch.duplicates = 1;
+        for _ in 0..EPOCH_BLOCKS {
+            current_time += 1;
+            advance_chain(&mut epoch, &mut block, &archive, current_time).unwrap();
+        }
+        assert_eq!(epoch.number, ADJUSTMENT_INTERVAL + 3);
+        assert_eq!(epoch.target_participation, 2);
+        assert_eq!(epoch.mining_difficulty, 5 + 2 * MAX_DIFFICULTY_STEP - 2);
+        assert_eq!(epoch.duplicates, 0);
+        assert_eq!(block.number, 11);
+        assert_eq!(block.progress, 2);
+    }
+
+    #[test]
+    fn epoch_rewards_distributed_sums_rewards_until_epoch_advances() {
+        let archive = Archive::zeroed();
+        let mut epoch = Epoch::zeroed();
+        epoch.number = 1;
+        epoch.target_participation = MIN_PARTICIPATION_TARGET;
+        epoch.reward_rate = get_reward_rate(1, &archive);
+
+        let mut tape = Tape::zeroed();
+        tape.total_segments = 1;
+
+        // Alternate subsidized and expired tapes and varying multipliers
+        let mut expected = 0;
+        for i in 1..EPOCH_BLOCKS {
+            tape.balance = if i % 2 == 0 { tape.rent_per_block() } else { 0 };
+            let reward = calculate_reward(&epoch, &tape, i, RECENCY_WINDOW_SLOTS).unwrap();
+            record_reward(&mut epoch, reward);
+            update_epoch(&mut epoch, &archive, 0).unwrap();
+
+            expected += reward;
+            assert_eq!(epoch.epoch_rewards_distributed, expected);
+        }
+        assert!(expected > 0);
+
+        // The proof closing the epoch starts a fresh total
+        record_reward(&mut epoch, 1);
+        update_epoch(&mut epoch, &archive, 0).unwrap();
+        assert_eq!(epoch.number, 2);
+        assert_eq!(epoch.epoch_rewards_distributed, 0);
+    }
+
+    #[test]
+    fn chain_advanced_reports_block_and_epoch_at_boundary() {
+        let archive = Archive::zeroed();
+        let mut block = Block::zeroed();
+        block.number = 7;
+        let mut epoch = Epoch::zeroed();
+        epoch.number = 3;
+        epoch.target_participation = 2;
+
+        // A proof that fills neither the block nor the epoch
+        let event = advance_chain(&mut epoch, &mut block, &archive, 1).unwrap();
+        assert_eq!(
+            event,
+            ChainAdvancedEvent {
+                new_block: 7,
+                new_epoch: 3,
+                block_advanced: 0,
+                epoch_advanced: 0,
+            }
+        );
+
+        // Bring both to one proof short of completing
+        block.progress = 1;
+        epoch.progress = EPOCH_BLOCKS - 1;
+
+        let event = advance_chain(&mut epoch, &mut block, &archive, 2).unwrap();
+        assert_eq!(
+            event,
+            ChainAdvancedEvent {
+                new_block: 8,
+                new_epoch: 4,
+                block_advanced: 1,
+                epoch_advanced: 1,
+            }
+        );
+        assert_eq!(event.new_block, block.number);
+        assert_eq!(event.new_epoch, epoch.number);
+
+        // Indexers decode the logged bytes back into the same state
+        let bytes = event.to_bytes();
+        assert_eq!(ChainAdvancedEvent::try_from_bytes(&bytes), Ok(&event));
+    }
+
+    #[test]
+    fn zero_participation_target_is_rejected() {
+        let archive = Archive::zeroed();
+        let mut epoch = Epoch::zeroed();
+        epoch.number = 1;
+        epoch.reward_rate = get_reward_rate(1, &archive);
+        let mut tape = Tape::zeroed();
+        tape.total_segments = 1;
+
+        assert_eq!(
+            calculate_reward(&epoch, &tape, 1, RECENCY_WINDOW_SLOTS),
+            Err(TapeError::InvalidEpochState.into())
+        );
+
+        epoch.target_participation = MIN_PARTICIPATION_TARGET;
+        assert!(calculate_reward(&epoch, &tape, 1, RECENCY_WINDOW_SLOTS).unwrap() > 0);
+    }
+
+    #[test]
+    fn participation_adjustment_never_reaches_zero() {
+        for target in MIN_PARTICIPATION_TARGET..=MAX_PARTICIPATION_TARGET {
+            for duplicates in [0, 1] {
+                for number in [ADJUSTMENT_INTERVAL, ADJUSTMENT_INTERVAL + 1] {
+                    let mut epoch = Epoch::zeroed();
+                    epoch.number = number;
+                    epoch.target_participation = target;
+                    epoch.duplicates = duplicates;
+
+                    adjust_participation(&mut epoch);
+                    assert!(epoch.target_participation >= MIN_PARTICIPATION_TARGET);
+                }
+            }
+        }
+
+        // Advancing also repairs an epoch that somehow reached zero
+        let mut epoch = Epoch::zeroed();
+        epoch.duplicates = 1;
+        advance_epoch(&mut epoch, 0).unwrap();
+        assert_eq!(epoch.target_participation, MIN_PARTICIPATION_TARGET);
+    }
+
+    #[test]
+    fn recently_written_tape_earns_capped_bonus() {
+        let archive = Archive::zeroed();
+        let mut epoch = Epoch::zeroed();
+        epoch.number = 1;
+        epoch.target_participation = MIN_PARTICIPATION_TARGET;
+        epoch.reward_rate = get_reward_rate(1, &archive);
+
+        let mut tape = Tape::zeroed();
+        tape.total_segments = 1;
+        tape.tail_slot = 1_000;
+
+        let reward_at = |tape: &Tape, slot| calculate_reward(&epoch, tape, 1, slot).unwrap();
+        let old = reward_at(&tape, tape.tail_slot + RECENCY_WINDOW_SLOTS);
+        let fresh = reward_at(&tape, tape.tail_slot);
+        let half = reward_at(&tape, tape.tail_slot + RECENCY_WINDOW_SLOTS / 2);
+
+        // A tape written this slot earns the full bonus, an old one none
+        assert!(old > 0);
+        assert_eq!(fresh, old + old * MAX_RECENCY_BONUS_BPS / 10_000);
+        assert_eq!(half, old + old * (MAX_RECENCY_BONUS_BPS / 2) / 10_000);
+
+        // The bonus only decays, and never exceeds the cap
+        let cap = old + old * MAX_RECENCY_BONUS_BPS / 10_000;
+        let mut previous = fresh;
+        for age in 0..=RECENCY_WINDOW_SLOTS + 1 {
+            let reward = reward_at(&tape, tape.tail_slot + age);
+            assert!(reward <= cap && reward <= previous && reward >= old);
+            previous = reward;
+        }
+
+        // A tail slot ahead of the clock counts as just written
+        assert_eq!(reward_at(&tape, tape.tail_slot - 1), fresh);
+    }
+
+    #[test]
+    fn recency_bonus_stays_within_reward_rate() {
+        let archive = Archive::zeroed();
+        let mut epoch = Epoch::zeroed();
+        epoch.number = 1;
+        // One epoch of proofs fills exactly one block, sharing its reward_rate
+        epoch.target_participation = EPOCH_BLOCKS;
+        epoch.reward_rate = get_reward_rate(1, &archive);
+
+        // Rent paid and just written: the full share plus the full bonus
+        let mut tape = Tape::zeroed();
+        tape.total_segments = 1;
+        tape.balance = tape.rent_per_block();
+        tape.tail_slot = 1_000;
+        assert!(recency_bonus(1_000_000, tape.tail_slot, tape.tail_slot) > 0);
+
+        for _ in 0..EPOCH_BLOCKS {
+            let reward =
+                calculate_reward(&epoch, &tape, MAX_CONSISTENCY_MULTIPLIER, tape.tail_slot)
+                    .unwrap();
+            record_reward(&mut epoch, reward);
+        }
+
+        assert!(epoch.epoch_rewards_distributed > 0);
+        assert!(epoch.epoch_rewards_distributed <= epoch.reward_rate);
+    }
+
+    #[test]
+    fn full_multiplier_miner_still_earns_recency_bonus() {
+        let archive = Archive::zeroed();
+        let mut epoch = Epoch::zeroed();
+        epoch.number = 1;
+        epoch.target_participation = MIN_PARTICIPATION_TARGET;
+        epoch.reward_rate = get_reward_rate(1, &archive);
+        let share = epoch.reward_rate / epoch.target_participation;
+
+        let mut tape = Tape::zeroed();
+        tape.total_segments = 1;
+        tape.balance = tape.rent_per_block();
+        tape.tail_slot = 1_000;
+
+        let reward_at =
+            |slot| calculate_reward(&epoch, &tape, MAX_CONSISTENCY_MULTIPLIER, slot).unwrap();
+        let old = reward_at(tape.tail_slot + RECENCY_WINDOW_SLOTS);
+        let fresh = reward_at(tape.tail_slot);
+
+        // The bonus is paid on top of a full reward, and both fit the share
+        assert!(fresh > old);
+        assert_eq!(fresh, old + old * MAX_RECENCY_BONUS_BPS / 10_000);
+        assert!(fresh <= share);
+    }
+
+    #[test]
+    fn recall_matches_api_vectors() {
+        use crate::api::utils::{
+            compute_recall_segment as api_recall_segment, compute_recall_tape as api_recall_tape,
+        };
+
+        // The api pins the recall mapping with fixed vectors; the on-chain
+        // copy must never drift from it
+        for seed in 0..=u8::MAX {
+            let challenge: [u8; 32] =
+                core::array::from_fn(|i| (i as u8).wrapping_mul(seed).wrapping_add(seed));
+            for (total_tapes, total_segments) in
+                [(0, 0), (1, 1), (7, 1024), (1000, 65536), (1 << 20, 3)]
+            {
+                assert_eq!(
+                    compute_recall_tape(&challenge, total_tapes),
+                    api_recall_tape(&challenge, total_tapes)
+                );
+                assert_eq!(
+                    compute_recall_segment(&challenge, total_segments),
+                    api_recall_segment(&challenge, total_segments)
+                );
+            }
+        }
+    }
+}
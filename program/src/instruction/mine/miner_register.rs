@@ -7,6 +7,7 @@ use pinocchio::{
     ProgramResult,
 };
 
+use pinocchio_log::log;
 use pinocchio_system::instructions::CreateAccount;
 
 use crate::state::utils::try_from_account_info_mut;
@@ -64,12 +65,23 @@ pub fn process_register(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult
     ];
     let signers = [Signer::from(&signer_seeds[..])];
 
+    // The deposit sits in the miner account on top of rent until unregister
+    let lamports = rent.minimum_balance(<Miner as ApiDataLen>::LEN) + MINER_DEPOSIT;
+    let available = signer_info.lamports();
+    if available < lamports {
+        log!(
+            "Insufficient funds, short {} lamports",
+            lamports - available
+        );
+        return Err(TapeError::InsufficientFunds.into());
+    }
+
     CreateAccount {
         from: signer_info,
         to: miner_info,
         space: <Miner as ApiDataLen>::LEN as u64,
         owner: &crate::ID,
-        lamports: rent.minimum_balance(<Miner as ApiDataLen>::LEN),
+        lamports,
     }
     .invoke_signed(&signers)?;
 
@@ -81,6 +93,7 @@ pub fn process_register(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult
         ix_data.name,
         (*signer_info.key()).into(),
         next_challenge,
+        miner_bump,
     )?;
 
     // Update last_proof_at to current time to match native implementation
@@ -88,6 +101,7 @@ pub fn process_register(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult
     let mut miner_data = miner_info.try_borrow_mut_data()?;
     let miner = Miner::unpack_mut(&mut miner_data)?;
     miner.last_proof_at = current_time;
+    miner.deposit = MINER_DEPOSIT;
 
     Ok(())
 }
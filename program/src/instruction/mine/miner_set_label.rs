@@ -0,0 +1,36 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+use tape_api::state::Miner;
+
+use crate::instruction::SetLabel;
+use crate::utils::ByteConversion;
+
+/// Sets the miner's display name. The `name` seeding the miner PDA never
+/// changes, so the miner keeps its address, rewards and multiplier.
+pub fn process_miner_set_label(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let args = SetLabel::try_from_bytes(data)?;
+
+    let [signer_info, miner_info, _remaining @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate signer
+    if !signer_info.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if !miner_info.is_owned_by(&tape_api::ID) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let mut miner_data = miner_info.try_borrow_mut_data()?;
+    let miner = Miner::unpack_mut(&mut miner_data)?;
+
+    // Check miner authority matches signer
+    if miner.authority.ne(signer_info.key()) {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    miner.display_name = args.label;
+
+    Ok(())
+}
@@ -44,7 +44,8 @@ pub fn process_unregister(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResu
         return Err(ProgramError::IllegalOwner);
     }
 
-    // Close the miner account and return rent to signer
+    // Close the miner account and return rent and what is left of the
+    // deposit to signer, slashed lamports already went to the treasury
     close_miner_account(miner_info, signer_info)?;
 
     Ok(())
@@ -1,9 +1,11 @@
 pub mod miner_claim;
 pub mod miner_mine;
 pub mod miner_register;
+pub mod miner_set_label;
 pub mod miner_unregister;
 
 pub use miner_claim::*;
 pub use miner_mine::*;
 pub use miner_register::*;
+pub use miner_set_label::*;
 pub use miner_unregister::*;
@@ -1,7 +1,10 @@
 use {
     bytemuck::{Pod, Zeroable},
-    pinocchio::program_error::ProgramError,
-    tape_api::consts::{HEADER_SIZE, NAME_LEN, SEGMENT_SIZE},
+    pinocchio::{program_error::ProgramError, pubkey::Pubkey},
+    tape_api::consts::{
+        HEADER_SIZE, METADATA_NAME_LEN, METADATA_SYMBOL_LEN, METADATA_URI_LEN, NAME_LEN,
+        SEGMENT_SIZE,
+    },
     tape_api::types::ProofPath,
 };
 
@@ -15,6 +18,33 @@ pub use mine::*;
 pub use spool::*;
 pub use tape::*;
 
+/// Optional token metadata for Initialize. Fields are zero-padded; an empty
+/// field keeps the default from `tape_api::consts`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct InitializeMetadata {
+    pub name: [u8; METADATA_NAME_LEN],
+    pub symbol: [u8; METADATA_SYMBOL_LEN],
+    pub uri: [u8; METADATA_URI_LEN],
+}
+
+/// Genesis epoch parameters for InitializeWithConfig, each a little-endian u64
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct InitializeConfig {
+    pub target_participation: [u8; 8],
+    pub mining_difficulty: [u8; 8],
+    pub packing_difficulty: [u8; 8],
+    pub reward_rate: [u8; 8],
+}
+
+/// Treasury authority override of the epoch reward rate, a little-endian u64
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct SetRewardRate {
+    pub reward_rate: [u8; 8],
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct Create {
@@ -40,6 +70,17 @@ pub struct Update {
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct Finalize {}
 
+/// Finalize, also setting the tape header in the same call
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct FinalizeWithHeader {
+    pub header: [u8; HEADER_SIZE],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct Close {}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct SetHeader {
@@ -52,39 +93,68 @@ pub struct Subsidize {
     pub amount: [u8; 8],
 }
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct TransferAuthority {
+    pub new_authority: Pubkey,
+}
+
+/// Index of the writer shard to open, a little-endian u64
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct CreateShard {
+    pub shard: [u8; 8],
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Pod, Zeroable)]
 pub struct Claim {
     pub amount: [u8; 8],
 }
 
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+pub struct SetLabel {
+    pub label: [u8; NAME_LEN],
+}
+
 #[repr(u8)]
 pub enum TapeInstruction {
     // ProgramInstruction variants
     Unknown = 0,
-    Initialize = 1, // ProgramInstruction::Initialize
-    Airdrop = 2,    // ProgramInstruction::Airdrop
+    Initialize = 1,           // ProgramInstruction::Initialize
+    Airdrop = 2,              // ProgramInstruction::Airdrop
+    EpochReport = 3,          // ProgramInstruction::EpochReport
+    InitializeWithConfig = 4, // ProgramInstruction::InitializeWithConfig
+    SetRewardRate = 5,        // ProgramInstruction::SetRewardRate
 
     // TapeInstruction variants
-    TapeCreate = 0x10,    // TapeInstruction::Create = 0x10
-    TapeWrite = 0x11,     // TapeInstruction::Write
-    TapeUpdate = 0x12,    // TapeInstruction::Update
-    TapeFinalize = 0x13,  // TapeInstruction::Finalize
-    TapeSetHeader = 0x14, // TapeInstruction::SetHeader
-    TapeSubsidize = 0x15, // TapeInstruction::Subsidize
+    TapeCreate = 0x10,            // TapeInstruction::Create = 0x10
+    TapeWrite = 0x11,             // TapeInstruction::Write
+    TapeUpdate = 0x12,            // TapeInstruction::Update
+    TapeFinalize = 0x13,          // TapeInstruction::Finalize
+    TapeSetHeader = 0x14,         // TapeInstruction::SetHeader
+    TapeSubsidize = 0x15,         // TapeInstruction::Subsidize
+    TapeClose = 0x17,             // TapeInstruction::Close
+    TapeTransferAuthority = 0x18, // TapeInstruction::TransferAuthority
+    TapeCreateShard = 0x19,       // TapeInstruction::CreateShard
 
     // MinerInstruction variants
     MinerRegister = 0x20,   // MinerInstruction::Register = 0x20
     MinerUnregister = 0x21, // MinerInstruction::Unregister
     MinerMine = 0x22,       // MinerInstruction::Mine
     MinerClaim = 0x23,      // MinerInstruction::Claim
+    MinerSetLabel = 0x24,   // MinerInstruction::SetLabel
 
     // SpoolInstruction variants
-    SpoolCreate = 0x40,  // SpoolInstruction::Create = 0x40
-    SpoolDestroy = 0x41, // SpoolInstruction::Destroy
-    SpoolPack = 0x42,    // SpoolInstruction::Pack
-    SpoolUnpack = 0x43,  // SpoolInstruction::Unpack
-    SpoolCommit = 0x44,  // SpoolInstruction::Commit
+    SpoolCreate = 0x40,    // SpoolInstruction::Create = 0x40
+    SpoolDestroy = 0x41,   // SpoolInstruction::Destroy
+    SpoolPack = 0x42,      // SpoolInstruction::Pack
+    SpoolUnpack = 0x43,    // SpoolInstruction::Unpack
+    SpoolCommit = 0x44,    // SpoolInstruction::Commit
+    SpoolRemove = 0x45,    // SpoolInstruction::Remove
+    SpoolRollover = 0x46,  // SpoolInstruction::Rollover
+    SpoolPackBatch = 0x47, // SpoolInstruction::PackBatch
 }
 
 impl TryFrom<&u8> for TapeInstruction {
@@ -96,6 +166,9 @@ impl TryFrom<&u8> for TapeInstruction {
             0 => Ok(TapeInstruction::Unknown),
             1 => Ok(TapeInstruction::Initialize),
             2 => Ok(TapeInstruction::Airdrop),
+            3 => Ok(TapeInstruction::EpochReport),
+            4 => Ok(TapeInstruction::InitializeWithConfig),
+            5 => Ok(TapeInstruction::SetRewardRate),
 
             // TapeInstruction variants
             0x10 => Ok(TapeInstruction::TapeCreate),
@@ -104,12 +177,16 @@ impl TryFrom<&u8> for TapeInstruction {
             0x13 => Ok(TapeInstruction::TapeFinalize),
             0x14 => Ok(TapeInstruction::TapeSetHeader),
             0x15 => Ok(TapeInstruction::TapeSubsidize),
+            0x17 => Ok(TapeInstruction::TapeClose),
+            0x18 => Ok(TapeInstruction::TapeTransferAuthority),
+            0x19 => Ok(TapeInstruction::TapeCreateShard),
 
             // MinerInstruction variants
             0x20 => Ok(TapeInstruction::MinerRegister),
             0x21 => Ok(TapeInstruction::MinerUnregister),
             0x22 => Ok(TapeInstruction::MinerMine),
             0x23 => Ok(TapeInstruction::MinerClaim),
+            0x24 => Ok(TapeInstruction::MinerSetLabel),
 
             // SpoolInstruction variants
             0x40 => Ok(TapeInstruction::SpoolCreate),
@@ -117,6 +194,9 @@ impl TryFrom<&u8> for TapeInstruction {
             0x42 => Ok(TapeInstruction::SpoolPack),
             0x43 => Ok(TapeInstruction::SpoolUnpack),
             0x44 => Ok(TapeInstruction::SpoolCommit),
+            0x45 => Ok(TapeInstruction::SpoolRemove),
+            0x46 => Ok(TapeInstruction::SpoolRollover),
+            0x47 => Ok(TapeInstruction::SpoolPackBatch),
 
             _ => Err(ProgramError::InvalidInstructionData),
         }
@@ -2,10 +2,14 @@ pub mod spool_commit;
 pub mod spool_create;
 pub mod spool_destroy;
 pub mod spool_pack;
+pub mod spool_remove;
+pub mod spool_rollover;
 pub mod spool_unpack;
 
 pub use spool_commit::*;
 pub use spool_create::*;
 pub use spool_destroy::*;
 pub use spool_pack::*;
+pub use spool_remove::*;
+pub use spool_rollover::*;
 pub use spool_unpack::*;
@@ -60,12 +60,24 @@ pub fn process_spool_commit(accounts: &[AccountInfo], data: &[u8]) -> ProgramRes
 
     let leaf = Leaf::from(commit_args.value);
 
+    // Only values provably stored in the spool can be committed
     check_condition(
         verify_no_std(*merkle_root, merkle_proof, leaf),
-        TapeError::SpoolCommitFailed,
+        TapeError::SolutionInvalid,
     )?;
 
-    miner.commitment = commit_args.value;
+    // Tie the commitment to the spool as it is right now
+    miner.commitment = compute_commitment(
+        &commit_args.value,
+        &spool.contains,
+        &spool.state.get_root().to_bytes(),
+    );
+
+    SpoolCommitEvent {
+        value: commit_args.value,
+        address: *spool_info.key(),
+    }
+    .log();
 
     Ok(())
 }
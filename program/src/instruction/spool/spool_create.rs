@@ -53,12 +53,16 @@ pub fn process_spool_create(accounts: &[AccountInfo], data: &[u8]) -> ProgramRes
         return Err(ProgramError::IncorrectProgramId);
     }
 
+    // Accounts carry no discriminator, unpack only accepts data of exactly
+    // the miner's size
     let miner_data = miner_info.try_borrow_data()?;
     let miner = Miner::unpack(&miner_data)?;
 
-    if miner.authority != *signer_info.key() {
-        return Err(ProgramError::MissingRequiredSignature);
-    }
+    // The spool is seeded by the miner, so only its authority may create one
+    check_condition(
+        miner.authority == *signer_info.key(),
+        TapeError::Unauthorized,
+    )?;
 
     let ix_data = unsafe { load_ix_data::<CreateSpoolIxData>(&data)? };
 
@@ -95,12 +99,24 @@ pub fn process_spool_create(accounts: &[AccountInfo], data: &[u8]) -> ProgramRes
 
     spool.number = spool_number;
     spool.authority = *signer_info.key();
-    spool.last_proof_at = current_time;
+    // No proofs yet: the block stays 0, and the time starts at creation. A
+    // zero clock (as in local test validators) is bumped to 1 so the spool
+    // never looks uninitialized.
+    spool.last_proof_at = current_time.max(1);
     spool.last_proof_block = 0;
     // spool.seed =
     spool.state = TapeTree::new(&[spool_info.key().as_ref()]);
     spool.contains = [0; 32];
     spool.total_tapes = 0;
+    spool.live_tapes = 0;
+    spool.history = [0; 32];
+
+    SpoolCreateEvent {
+        number: spool_number,
+        authority: spool.authority,
+        address: *spool_info.key(),
+    }
+    .log();
 
     Ok(())
 }
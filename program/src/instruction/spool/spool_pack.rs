@@ -1,11 +1,13 @@
 use crate::api::prelude::*;
-use bytemuck::{try_from_bytes, Pod, Zeroable};
-use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+use bytemuck::{try_cast_slice, try_from_bytes, Pod, Zeroable};
+use pinocchio::{
+    account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult,
+};
 use tape_api::{
     error::TapeError,
     state::{Spool, TapeState},
     utils::check_condition,
-    MAX_TAPES_PER_SPOOL,
+    MAX_TAPES_PER_PACK, MAX_TAPES_PER_SPOOL,
 };
 use tape_utils::leaf::Leaf;
 
@@ -27,6 +29,56 @@ pub fn process_spool_pack(accounts: &[AccountInfo], data: &[u8]) -> ProgramResul
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
+    let mut spool_data = spool_info.try_borrow_mut_data()?;
+    let spool = load_spool(signer_info, spool_info, &mut spool_data)?;
+
+    pack_tape(spool, spool_info.key(), tape_info, &pack_args.value)
+}
+
+/// Packs up to `MAX_TAPES_PER_PACK` finalized tapes in one call.
+///
+/// `data` is a count byte followed by that many 32-byte values, and the tapes
+/// follow the spool in the accounts in the same order. Each tape is packed
+/// exactly as a single pack would, so any invalid tape fails the whole batch.
+pub fn process_spool_pack_batch(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let (&count, values) = data
+        .split_first()
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    let count = count as usize;
+
+    check_condition(
+        count > 0 && count <= MAX_TAPES_PER_PACK,
+        ProgramError::InvalidInstructionData,
+    )?;
+
+    let values: &[[u8; 32]] =
+        try_cast_slice(values).map_err(|_| ProgramError::InvalidInstructionData)?;
+    check_condition(values.len() == count, ProgramError::InvalidInstructionData)?;
+
+    let [signer_info, spool_info, tape_infos @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if tape_infos.len() < count {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    let mut spool_data = spool_info.try_borrow_mut_data()?;
+    let spool = load_spool(signer_info, spool_info, &mut spool_data)?;
+
+    for (tape_info, value) in tape_infos.iter().zip(values) {
+        pack_tape(spool, spool_info.key(), tape_info, value)?;
+    }
+
+    Ok(())
+}
+
+/// Loads the spool, checking it belongs to the program and the signer
+fn load_spool<'a>(
+    signer_info: &AccountInfo,
+    spool_info: &AccountInfo,
+    spool_data: &'a mut [u8],
+) -> Result<&'a mut Spool, ProgramError> {
     if !signer_info.is_signer() {
         return Err(ProgramError::MissingRequiredSignature);
     }
@@ -35,19 +87,28 @@ pub fn process_spool_pack(accounts: &[AccountInfo], data: &[u8]) -> ProgramResul
         return Err(ProgramError::IncorrectProgramId);
     }
 
-    let mut spool_data = spool_info.try_borrow_mut_data()?;
-    let spool = Spool::unpack_mut(&mut spool_data)?;
+    let spool = Spool::unpack_mut(spool_data)?;
 
     if spool.authority != *signer_info.key() {
         return Err(ProgramError::MissingRequiredSignature);
     }
 
+    Ok(spool)
+}
+
+/// Adds a finalized tape's value to the spool and logs the new root
+fn pack_tape(
+    spool: &mut Spool,
+    spool_address: &Pubkey,
+    tape_info: &AccountInfo,
+    value: &[u8; 32],
+) -> ProgramResult {
     if !tape_info.is_owned_by(&tape_api::ID) {
         return Err(ProgramError::IncorrectProgramId);
     }
 
-    let mut tape_data = tape_info.try_borrow_mut_data()?;
-    let tape = Tape::unpack_mut(&mut tape_data)?;
+    let tape_data = tape_info.try_borrow_data()?;
+    let tape = Tape::unpack(&tape_data)?;
 
     if tape.state != (TapeState::Finalized as u64) {
         return Err(TapeError::UnexpectedState.into());
@@ -57,13 +118,14 @@ pub fn process_spool_pack(accounts: &[AccountInfo], data: &[u8]) -> ProgramResul
         return Err(TapeError::UnexpectedState.into());
     }
 
+    // total_tapes carries over a rollover, the tree's own count doesn't
     check_condition(
-        spool.total_tapes as usize <= MAX_TAPES_PER_SPOOL,
+        spool.state.get_leaf_count() as usize <= MAX_TAPES_PER_SPOOL,
         TapeError::SpoolTooManyTapes,
     )?;
 
     let tape_id = tape.number.to_le_bytes();
-    let leaf = Leaf::new(&[tape_id.as_ref(), &pack_args.value]);
+    let leaf = Leaf::new(&[tape_id.as_ref(), value]);
 
     check_condition(
         spool.state.try_add_leaf(leaf).is_ok(),
@@ -71,6 +133,15 @@ pub fn process_spool_pack(accounts: &[AccountInfo], data: &[u8]) -> ProgramResul
     )?;
 
     spool.total_tapes += 1;
+    spool.live_tapes += 1;
+
+    SpoolPackEvent {
+        tape: tape.number,
+        total_tapes: spool.total_tapes,
+        root: spool.state.get_root().to_bytes(),
+        address: *spool_address,
+    }
+    .log();
 
     Ok(())
 }
@@ -0,0 +1,65 @@
+use crate::api::prelude::*;
+use bytemuck::{try_from_bytes, Pod, Zeroable};
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+use tape_api::{consts::TAPE_PROOF_LEN, error::TapeError, state::Spool, utils::check_condition};
+use tape_utils::leaf::Leaf;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, shank::ShankType, Pod, Zeroable)]
+pub struct SpoolRemoveIxData {
+    pub index: [u8; 8],
+    pub proof: [[u8; 32]; TAPE_PROOF_LEN],
+    pub value: [u8; 32],
+}
+
+impl DataLen for SpoolRemoveIxData {
+    const LEN: usize = core::mem::size_of::<SpoolRemoveIxData>();
+}
+
+/// Removes a packed tape from the spool, the inverse of `process_spool_pack`.
+///
+/// The leaf is replaced with the empty leaf, so the proof must verify against
+/// the current spool root and proofs for the remaining tapes change with it.
+pub fn process_spool_remove(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    if data.len() != SpoolRemoveIxData::LEN {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let remove_args = try_from_bytes::<SpoolRemoveIxData>(data)
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    let [signer_info, spool_info, _remaining @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !signer_info.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if !spool_info.is_owned_by(&tape_api::ID) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let mut spool_data = spool_info.try_borrow_mut_data()?;
+    let spool = Spool::unpack_mut(&mut spool_data)?;
+
+    if spool.authority != *signer_info.key() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let tape_id = remove_args.index;
+    let leaf = Leaf::new(&[tape_id.as_ref(), &remove_args.value]);
+
+    check_condition(
+        spool
+            .state
+            .try_remove_leaf_no_std(&remove_args.proof, leaf)
+            .is_ok(),
+        TapeError::SpoolUnpackFailed,
+    )?;
+
+    // total_tapes counts every tape ever packed, only the live count drops
+    spool.live_tapes = spool.live_tapes.saturating_sub(1);
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+use tape_api::{state::Spool, types::TapeTree};
+use tape_utils::leaf::hashv;
+
+/// Retires the spool's tree so a long-running miner can keep packing into the
+/// same spool PDA.
+///
+/// The current root is folded into `history`, a hash chain of every retired
+/// root, and the tree starts over empty. `total_tapes` is left as is, so it
+/// counts every tape the spool has held, while `live_tapes` drops to zero.
+pub fn process_spool_rollover(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
+    let [signer_info, spool_info, _remaining @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !signer_info.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if !spool_info.is_owned_by(&tape_api::ID) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let mut spool_data = spool_info.try_borrow_mut_data()?;
+    let spool = Spool::unpack_mut(&mut spool_data)?;
+
+    if spool.authority != *signer_info.key() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let root = spool.state.get_root().to_bytes();
+    spool.history = hashv(&[&spool.history, &root]).to_bytes();
+
+    // Same seeds as spool create, so the empty tree looks like a new spool's
+    spool.state = TapeTree::new(&[spool_info.key().as_ref()]);
+    spool.contains = [0; 32];
+    spool.live_tapes = 0;
+
+    Ok(())
+}
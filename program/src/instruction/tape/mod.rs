@@ -1,13 +1,19 @@
+pub mod tape_close;
 pub mod tape_create;
+pub mod tape_create_shard;
 pub mod tape_finalize;
 pub mod tape_set_header;
 pub mod tape_subsidize;
+pub mod tape_transfer_authority;
 pub mod tape_update;
 pub mod tape_write;
 
+pub use tape_close::*;
 pub use tape_create::*;
+pub use tape_create_shard::*;
 pub use tape_finalize::*;
 pub use tape_set_header::*;
 pub use tape_subsidize::*;
+pub use tape_transfer_authority::*;
 pub use tape_update::*;
 pub use tape_write::*;
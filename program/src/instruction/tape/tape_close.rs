@@ -0,0 +1,118 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+use tape_api::{
+    error::TapeError,
+    state::{Tape, TapeState, Writer},
+    utils::check_condition,
+};
+
+use crate::instruction::Close;
+use crate::state::pda::{tape_derive_pda, writer_derive_pda, writer_shard_derive_pda};
+use crate::utils::ByteConversion;
+
+/// Closes an unfinalized tape and its writer, returning their lamports to the author.
+///
+/// Any writer shards past the first follow the writer and are closed too.
+pub fn process_tape_close(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let _args = Close::try_from_bytes(data)?;
+
+    let [signer_info, tape_info, writer_info, shard_infos @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate signer
+    if !signer_info.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Validate both accounts belong to this program before closing them
+    if !tape_info.is_owned_by(&tape_api::ID) || !writer_info.is_owned_by(&tape_api::ID) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // Load and validate tape account
+    let tape_data = tape_info.try_borrow_data()?;
+    let tape = Tape::unpack(&tape_data)?;
+
+    // Validate tape authority matches signer
+    check_condition(
+        tape.authority.eq(signer_info.key()),
+        TapeError::Unauthorized,
+    )?;
+
+    // A finalized tape is part of the archive and can be mined, keep it
+    check_condition(
+        tape.state != TapeState::Finalized as u64,
+        TapeError::UnexpectedState,
+    )?;
+
+    // Load and validate writer account
+    let writer_data = writer_info.try_borrow_data()?;
+    let writer = Writer::unpack(&writer_data)?;
+
+    // Validate writer tape matches tape account
+    check_condition(writer.tape.eq(tape_info.key()), ProgramError::InvalidSeeds)?;
+
+    // Derive and validate PDAs with the cached bumps
+    let tape_address = tape_derive_pda(tape.creator, &tape.name, tape.bump as u8);
+    let writer_address = writer_derive_pda(tape_address, writer.bump as u8);
+
+    if tape_info.key().ne(&tape_address) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if writer_info.key().ne(&writer_address) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    for shard_info in shard_infos {
+        if !shard_info.is_owned_by(&tape_api::ID) {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        let shard_data = shard_info.try_borrow_data()?;
+        let shard_writer = Writer::unpack(&shard_data)?;
+
+        check_condition(
+            shard_writer.tape.eq(tape_info.key()),
+            ProgramError::InvalidSeeds,
+        )?;
+
+        let shard_address =
+            writer_shard_derive_pda(tape_address, shard_writer.shard, shard_writer.bump as u8);
+        if shard_writer.shard == 0 || shard_info.key().ne(&shard_address) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+    }
+
+    // Drop borrows before closing
+    drop(tape_data);
+    drop(writer_data);
+
+    // Close every account and return rent to signer
+    close_account(writer_info, signer_info)?;
+    for shard_info in shard_infos {
+        close_account(shard_info, signer_info)?;
+    }
+    close_account(tape_info, signer_info)?;
+
+    Ok(())
+}
+
+/// Close account and return rent to destination
+#[inline(always)]
+fn close_account(account: &AccountInfo, destination: &AccountInfo) -> ProgramResult {
+    // Set first byte to 0xff to prevent reinitialization
+    {
+        let mut data = account.try_borrow_mut_data()?;
+        if !data.is_empty() {
+            data[0] = 0xff;
+        }
+    }
+
+    // Transfer all lamports to destination
+    *destination.try_borrow_mut_lamports()? += *account.try_borrow_lamports()?;
+
+    // Resize and close account
+    account.realloc(1, true)?;
+    account.close()
+}
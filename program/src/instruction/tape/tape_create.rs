@@ -8,9 +8,12 @@ use {
         sysvars::{clock::Clock, rent::Rent, Sysvar},
         ProgramResult,
     },
+    pinocchio_log::log,
     pinocchio_system::instructions::CreateAccount,
     tape_api::{
         consts::{HEADER_SIZE, TAPE, WRITER},
+        error::TapeError,
+        event::CreateEvent,
         pda::{tape_pda, writer_pda},
         state::{DataLen, Tape, TapeState, Writer},
         types::SegmentTree,
@@ -22,6 +25,11 @@ pub fn process_tape_create(accounts: &[AccountInfo], data: &[u8]) -> ProgramResu
 
     let args = Create::try_from_bytes(data)?;
 
+    // An all-zero name is indistinguishable from an empty one
+    if args.name.iter().all(|&b| b == 0) {
+        return Err(TapeError::InvalidName.into());
+    }
+
     // dev : ignore system_program_info and rent_sysvar_info
     let [signer_info, tape_info, writer_info, _remaining @ ..] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
@@ -31,8 +39,8 @@ pub fn process_tape_create(accounts: &[AccountInfo], data: &[u8]) -> ProgramResu
         return Err(ProgramError::MissingRequiredSignature);
     };
 
-    let (tape_address, _tape_bump) = tape_pda(*signer_info.key(), &args.name);
-    let (writer_address, _writer_bump) = writer_pda(tape_address);
+    let (tape_address, tape_bump) = tape_pda(*signer_info.key(), &args.name);
+    let (writer_address, writer_bump) = writer_pda(tape_address);
 
     if !tape_info.data_is_empty() {
         return Err(ProgramError::AccountAlreadyInitialized);
@@ -62,10 +70,27 @@ pub fn process_tape_create(accounts: &[AccountInfo], data: &[u8]) -> ProgramResu
     //   - system program
     //   - rent sysvar
 
-    // create tape_info PDA
+    // Read the rent sysvar once for both allocations
+    let rent = Rent::get()?;
+
     let tape_info_space = Tape::LEN;
-    let tape_info_rent = Rent::get()?.minimum_balance(tape_info_space);
-    let tape_bump_binding = [_tape_bump];
+    let tape_info_rent = rent.minimum_balance(tape_info_space);
+    let writer_info_space = Writer::LEN;
+    let writer_info_rent = rent.minimum_balance(writer_info_space);
+
+    // Fail with a clear error instead of a system program CPI failure
+    let required = tape_info_rent + writer_info_rent;
+    let available = signer_info.lamports();
+    if available < required {
+        log!(
+            "Insufficient funds, short {} lamports",
+            required - available
+        );
+        return Err(TapeError::InsufficientFunds.into());
+    }
+
+    // create tape_info PDA
+    let tape_bump_binding = [tape_bump];
 
     let tape_info_seeds = &[
         Seed::from(TAPE),
@@ -86,9 +111,7 @@ pub fn process_tape_create(accounts: &[AccountInfo], data: &[u8]) -> ProgramResu
     .invoke_signed(&[tape_info_signature])?;
 
     // create writer_info pda
-    let writer_info_space = Writer::LEN;
-    let writer_info_rent = Rent::get()?.minimum_balance(writer_info_space);
-    let writer_bump_binding = [_writer_bump];
+    let writer_bump_binding = [writer_bump];
 
     let writer_info_seeds = &[
         Seed::from(WRITER),
@@ -114,6 +137,7 @@ pub fn process_tape_create(accounts: &[AccountInfo], data: &[u8]) -> ProgramResu
     *tape = Tape {
         number: 0,
         authority: *signer_info.key(),
+        creator: *signer_info.key(),
         name: args.name,
         state: TapeState::Created as u64,
         total_segments: 0,
@@ -121,6 +145,7 @@ pub fn process_tape_create(accounts: &[AccountInfo], data: &[u8]) -> ProgramResu
         header: [0; HEADER_SIZE],
         first_slot: current_slot,
         tail_slot: current_slot,
+        bump: tape_bump as u64,
         ..Tape::zeroed()
     };
 
@@ -129,9 +154,18 @@ pub fn process_tape_create(accounts: &[AccountInfo], data: &[u8]) -> ProgramResu
     let writer = Writer::unpack_mut(&mut writer_info_raw_data)?;
 
     writer.tape = *tape_info.key();
+    writer.bump = writer_bump as u64;
+    writer.shard = 0;
 
     // Use pre-computed zeros to avoid expensive Blake3 hash computations
     writer.state = SegmentTree::from_zeros(tape_utils::tree::SEGMENT_TREE_ZEROS_18);
 
+    CreateEvent {
+        first_slot: current_slot,
+        authority: *signer_info.key(),
+        address: tape_address,
+    }
+    .log();
+
     Ok(())
 }
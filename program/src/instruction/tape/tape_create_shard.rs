@@ -0,0 +1,119 @@
+use {
+    crate::{instruction::CreateShard, state::pda::tape_derive_pda, utils::ByteConversion},
+    pinocchio::{
+        account_info::AccountInfo,
+        instruction::{Seed, Signer},
+        program_error::ProgramError,
+        sysvars::{rent::Rent, Sysvar},
+        ProgramResult,
+    },
+    pinocchio_system::instructions::CreateAccount,
+    tape_api::{
+        consts::{MAX_WRITER_SHARDS, WRITER},
+        error::TapeError,
+        pda::writer_shard_pda,
+        state::{DataLen, Tape, TapeState, Writer},
+        types::SegmentTree,
+        utils::check_condition,
+    },
+};
+
+/// Opens writer shard `shard` of a tape so its segments can be written in
+/// parallel with the tape's own writer.
+///
+/// Shard `k` holds the segments from `k * WRITER_SHARD_SEGMENTS` on, and
+/// finalize combines the shard roots into the tape root. Shard 0 is the
+/// writer made by create, so only shards `1..MAX_WRITER_SHARDS` can be opened.
+pub fn process_tape_create_shard(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let args = CreateShard::try_from_bytes(data)?;
+    let shard = u64::from_le_bytes(args.shard);
+
+    // dev : ignore system_program_info and rent_sysvar_info
+    let [signer_info, tape_info, writer_info, _remaining @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !signer_info.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    };
+
+    let tape_data = tape_info.try_borrow_data()?;
+    let tape = Tape::unpack(&tape_data)?;
+
+    check_condition(
+        tape.authority.eq(signer_info.key()),
+        TapeError::Unauthorized,
+    )?;
+
+    let tape_address = tape_derive_pda(tape.creator, &tape.name, tape.bump as u8);
+    if tape_info.key().ne(&tape_address) {
+        return Err(ProgramError::InvalidAccountData);
+    };
+
+    // A finalized tape takes no more writes, so it needs no more shards
+    check_condition(
+        tape.can_transition_to(TapeState::Writing),
+        TapeError::UnexpectedState,
+    )?;
+
+    drop(tape_data);
+
+    check_condition(
+        shard > 0 && shard < MAX_WRITER_SHARDS as u64,
+        TapeError::InvalidShard,
+    )?;
+
+    let (writer_address, writer_bump) = writer_shard_pda(tape_address, shard);
+
+    if !writer_info.data_is_empty() {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    };
+
+    if !writer_info.is_writable() {
+        return Err(ProgramError::MissingRequiredSignature);
+    };
+
+    if writer_info.key().ne(&writer_address) {
+        return Err(ProgramError::InvalidAccountData);
+    };
+
+    let writer_info_space = Writer::LEN;
+    let writer_info_rent = Rent::get()?.minimum_balance(writer_info_space);
+
+    if signer_info.lamports() < writer_info_rent {
+        return Err(TapeError::InsufficientFunds.into());
+    }
+
+    let shard_binding = args.shard;
+    let writer_bump_binding = [writer_bump];
+
+    let writer_info_seeds = &[
+        Seed::from(WRITER),
+        Seed::from(tape_info.key().as_ref()),
+        Seed::from(&shard_binding),
+        Seed::from(&writer_bump_binding),
+    ];
+
+    let writer_info_signature = Signer::from(writer_info_seeds);
+
+    CreateAccount {
+        from: signer_info,
+        to: writer_info,
+        lamports: writer_info_rent,
+        space: writer_info_space as u64,
+        owner: &tape_api::ID,
+    }
+    .invoke_signed(&[writer_info_signature])?;
+
+    let mut writer_info_raw_data = writer_info.try_borrow_mut_data()?;
+    let writer = Writer::unpack_mut(&mut writer_info_raw_data)?;
+
+    writer.tape = *tape_info.key();
+    writer.bump = writer_bump as u64;
+    writer.shard = shard;
+
+    // Use pre-computed zeros to avoid expensive Blake3 hash computations
+    writer.state = SegmentTree::from_zeros(tape_utils::tree::SEGMENT_TREE_ZEROS_18);
+
+    Ok(())
+}
@@ -1,20 +1,44 @@
-use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+use pinocchio::{
+    account_info::AccountInfo,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, Sysvar},
+    ProgramResult,
+};
 use tape_api::{
-    consts::ARCHIVE_ADDRESS,
-    pda::{tape_pda, writer_pda},
+    consts::{ARCHIVE_ADDRESS, MAX_WRITER_SHARDS, WRITER_SHARD_HEIGHT, WRITER_SHARD_SEGMENTS},
+    error::TapeError,
+    event::{FinalizeEvent, HeaderUpdateEvent},
+    rent::block_from_slot,
     state::{Archive, Tape, TapeState, Writer},
+    utils::{check_condition, check_tape_balance},
 };
 
-use crate::instruction::Finalize;
-use crate::utils::ByteConversion;
+use crate::instruction::FinalizeWithHeader;
+use crate::state::pda::{tape_derive_pda, writer_derive_pda, writer_shard_derive_pda};
+use crate::utils::{check_tape_root, combine_shard_roots, ByteConversion};
 
+/// Finalizes a tape, archiving it so it can be mined.
+///
+/// A tape written through shards passes writers 1.. in shard order after the
+/// system program and rent sysvar. Their roots are combined with the tape's
+/// own writer into the tape root, and all of them are closed.
 pub fn process_tape_finalize(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
-    let _args = Finalize::try_from_bytes(data)?;
+    // A header may be passed to set it in the same call, saving a separate
+    // set_header; anything else that isn't empty is rejected
+    let header = if data.is_empty() {
+        None
+    } else {
+        Some(FinalizeWithHeader::try_from_bytes(data)?.header)
+    };
 
-    let [signer_info, tape_info, writer_info, archive_info, _remaining @ ..] = accounts else {
+    let [signer_info, tape_info, writer_info, archive_info, remaining @ ..] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
+    // Skip the system program and rent sysvar
+    let shard_infos = remaining.get(2..).unwrap_or(&[]);
+
     // Validate signer
     if !signer_info.is_signer() {
         return Err(ProgramError::MissingRequiredSignature);
@@ -25,25 +49,32 @@ pub fn process_tape_finalize(accounts: &[AccountInfo], data: &[u8]) -> ProgramRe
     let tape = Tape::unpack_mut(&mut tape_data)?;
 
     // Validate tape authority matches signer
-    if tape.authority.ne(signer_info.key()) {
-        return Err(ProgramError::MissingRequiredSignature);
-    }
+    check_condition(
+        tape.authority.eq(signer_info.key()),
+        TapeError::Unauthorized,
+    )?;
 
     // Load and validate writer account
     let writer_data = writer_info.try_borrow_data()?;
     let writer = Writer::unpack(&writer_data)?;
 
     // Validate writer tape matches tape account
-    if writer.tape.ne(tape_info.key()) {
-        return Err(ProgramError::InvalidAccountData);
+    check_condition(writer.tape.eq(tape_info.key()), ProgramError::InvalidSeeds)?;
+    let writer_bump = writer.bump as u8;
+
+    // The root is what miners recall against; a desynced one is unminable
+    check_tape_root(tape, writer)?;
+
+    if !shard_infos.is_empty() {
+        fold_writer_shards(tape, tape_info.key(), writer, shard_infos)?;
     }
 
     // Drop writer borrow before we close it
     drop(writer_data);
 
-    // Derive and validate PDAs
-    let (tape_address, _tape_bump) = tape_pda(tape.authority, &tape.name);
-    let (writer_address, _writer_bump) = writer_pda(tape_address);
+    // Derive and validate PDAs with the cached bumps
+    let tape_address = tape_derive_pda(tape.creator, &tape.name, tape.bump as u8);
+    let writer_address = writer_derive_pda(tape_address, writer_bump);
 
     if tape_info.key().ne(&tape_address) {
         return Err(ProgramError::InvalidAccountData);
@@ -63,14 +94,19 @@ pub fn process_tape_finalize(accounts: &[AccountInfo], data: &[u8]) -> ProgramRe
     let archive = Archive::unpack_mut(&mut archive_data)?;
 
     // Can't finalize if the tape is not in Writing state
-    if tape.state != (TapeState::Writing as u64) {
-        return Err(ProgramError::InvalidAccountData); // UnexpectedState
-    }
+    check_condition(
+        tape.can_transition_to(TapeState::Finalized),
+        TapeError::UnexpectedState,
+    )?;
 
-    // Can't finalize the tape if it doesn't have enough rent
-    if !tape.can_finalize() {
-        return Err(ProgramError::InvalidAccountData); // InsufficientRent
-    }
+    // Can't finalize the tape without a year of rent up front
+    check_condition(
+        tape.balance >= tape.required_finalize_balance(),
+        TapeError::InsufficientRent,
+    )?;
+
+    // The recorded balance must be backed by actual lamports
+    check_tape_balance(tape.balance, tape_info)?;
 
     // Update archive counters
     archive.tapes_stored = archive.tapes_stored.saturating_add(1);
@@ -79,7 +115,24 @@ pub fn process_tape_finalize(accounts: &[AccountInfo], data: &[u8]) -> ProgramRe
     // Update tape
     tape.number = archive.tapes_stored;
     tape.state = TapeState::Finalized as u64;
-    // merkle_root is already set from writer's state during write operations
+    // Rent accrues from here, mine charges it up to the current block
+    tape.last_rent_block = block_from_slot(Clock::get()?.slot);
+
+    if let Some(header) = header {
+        tape.header = header;
+
+        HeaderUpdateEvent {
+            address: tape_address,
+        }
+        .log();
+    }
+
+    FinalizeEvent {
+        tape: tape.number,
+        merkle_root: tape.merkle_root,
+        address: tape_address,
+    }
+    .log();
 
     // Drop borrows before closing writer
     drop(tape_data);
@@ -87,8 +140,65 @@ pub fn process_tape_finalize(accounts: &[AccountInfo], data: &[u8]) -> ProgramRe
 
     // Close the writer account and return rent to signer
     close_writer_account(writer_info, signer_info)?;
+    for shard_info in shard_infos {
+        close_writer_account(shard_info, signer_info)?;
+    }
+
+    Ok(())
+}
+
+/// Sets the tape's root and segment count from its writer and shards 1..
+///
+/// Every shard but the last must be full, so the segments stay contiguous.
+/// Shard 0 must stop at its own slice, or it would overlap shard 1.
+fn fold_writer_shards(
+    tape: &mut Tape,
+    tape_address: &Pubkey,
+    writer: &Writer,
+    shard_infos: &[AccountInfo],
+) -> ProgramResult {
+    check_condition(
+        shard_infos.len() < MAX_WRITER_SHARDS,
+        TapeError::InvalidShard,
+    )?;
+
+    let shard_segments = WRITER_SHARD_SEGMENTS as u64;
+    let writer_segments = writer.state.get_leaf_count();
+    check_condition(writer_segments >= shard_segments, TapeError::ShardNotFull)?;
+    check_condition(writer_segments == shard_segments, TapeError::InvalidShard)?;
+
+    let mut roots = [writer.state.filled_subtrees[WRITER_SHARD_HEIGHT]; MAX_WRITER_SHARDS];
+    let mut total_segments = shard_segments;
+
+    for (i, shard_info) in shard_infos.iter().enumerate() {
+        let shard = i as u64 + 1;
+
+        let shard_data = shard_info.try_borrow_data()?;
+        let shard_writer = Writer::unpack(&shard_data)?;
+
+        check_condition(
+            shard_writer.tape.eq(tape_address),
+            ProgramError::InvalidSeeds,
+        )?;
+        check_condition(shard_writer.shard == shard, TapeError::InvalidShard)?;
+
+        let shard_address = writer_shard_derive_pda(*tape_address, shard, shard_writer.bump as u8);
+        if shard_info.key().ne(&shard_address) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let segments = shard_writer.state.get_leaf_count();
+        if i + 1 < shard_infos.len() {
+            check_condition(segments == shard_segments, TapeError::ShardNotFull)?;
+        }
+
+        roots[i + 1] = shard_writer.state.filled_subtrees[WRITER_SHARD_HEIGHT];
+        total_segments += segments;
+    }
 
-    // Note: Native logs FinalizeEvent here, but we'll skip logging for now
+    tape.merkle_root =
+        combine_shard_roots(&roots[..shard_infos.len() + 1], &writer.state.zero_values).to_bytes();
+    tape.total_segments = total_segments;
 
     Ok(())
 }
@@ -1,11 +1,9 @@
 use {
-    crate::{instruction::SetHeader, utils::ByteConversion},
+    crate::{instruction::SetHeader, state::pda::tape_derive_pda, utils::ByteConversion},
     pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult},
     tape_api::{
-        error::TapeError,
-        pda::tape_pda,
+        event::HeaderUpdateEvent,
         state::{Tape, TapeState},
-        utils::check_condition,
     },
 };
 
@@ -26,18 +24,20 @@ pub fn process_tape_set_header(accounts: &[AccountInfo], data: &[u8]) -> Program
         return Err(ProgramError::MissingRequiredSignature);
     };
 
-    let (tape_address, _) = tape_pda(*signer_info.key(), &tape.name);
+    let tape_address = tape_derive_pda(tape.creator, &tape.name, tape.bump as u8);
 
     if tape_info.key().ne(&tape_address) {
         return Err(ProgramError::InvalidAccountData);
     };
 
-    check_condition(
-        tape.state.eq(&(TapeState::Writing as u64)),
-        TapeError::UnexpectedState,
-    )?;
+    tape.require_state(TapeState::Writing)?;
 
     tape.header = args.header;
 
+    HeaderUpdateEvent {
+        address: tape_address,
+    }
+    .log();
+
     Ok(())
 }
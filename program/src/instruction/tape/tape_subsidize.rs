@@ -1,50 +1,42 @@
 use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
-use pinocchio_token::instructions::Transfer;
-use tape_api::{consts::TREASURY_ATA, state::Tape};
+use pinocchio_system::instructions::Transfer;
+use tape_api::state::Tape;
 
 use crate::instruction::Subsidize;
 use crate::utils::ByteConversion;
 
+/// Funds an existing tape with lamports from the signer, in any state. The
+/// lamports are paid into the tape itself and credited to `tape.balance`, so
+/// the balance stays backed by the account's lamports and the tape can still
+/// be finalized and mined.
 pub fn process_tape_subsidize_rent(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
     let args = Subsidize::try_from_bytes(data)?;
+    let amount = u64::from_le_bytes(args.amount);
 
-    let [signer_info, ata_info, tape_info, treasury_ata_info, _token_program_info] = accounts
-    else {
+    let [signer_info, tape_info, _system_program_info] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
     };
 
-    // Validate signer
     if !signer_info.is_signer() {
         return Err(ProgramError::MissingRequiredSignature);
     }
 
-    // Load tape account
-    let mut tape_data = tape_info.try_borrow_mut_data()?;
-    let tape = Tape::unpack_mut(&mut tape_data)?;
-
-    // Validate treasury ATA address
-    if treasury_ata_info.key().ne(&TREASURY_ATA) {
-        return Err(ProgramError::InvalidAccountData);
+    if !tape_info.is_owned_by(&tape_api::ID) {
+        return Err(ProgramError::IncorrectProgramId);
     }
 
-    // Validate treasury ATA is writable
-    if !treasury_ata_info.is_writable() {
-        return Err(ProgramError::InvalidAccountData);
-    }
+    // Make sure this is a tape before paying into it
+    Tape::unpack(&tape_info.try_borrow_data()?)?;
 
-    // Parse amount
-    let amount = u64::from_le_bytes(args.amount);
-
-    // Transfer tokens from signer's ATA to treasury ATA
     Transfer {
-        from: ata_info,
-        to: treasury_ata_info,
-        authority: signer_info,
-        amount,
+        from: signer_info,
+        to: tape_info,
+        lamports: amount,
     }
     .invoke()?;
 
-    // Update tape balance
+    let mut tape_data = tape_info.try_borrow_mut_data()?;
+    let tape = Tape::unpack_mut(&mut tape_data)?;
     tape.balance = tape.balance.saturating_add(amount);
 
     Ok(())
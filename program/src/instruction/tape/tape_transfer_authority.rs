@@ -0,0 +1,58 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, ProgramResult};
+use tape_api::{
+    error::TapeError,
+    state::{Tape, TapeState},
+    utils::check_condition,
+};
+
+use crate::instruction::TransferAuthority;
+use crate::state::pda::tape_derive_pda;
+use crate::utils::ByteConversion;
+
+/// Hands control of a tape to a new authority, e.g. a DAO or multisig.
+///
+/// The tape address stays derived from `tape.creator`, so every handler keeps
+/// working for the new authority without recreating the tape.
+pub fn process_tape_transfer_authority(accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let args = TransferAuthority::try_from_bytes(data)?;
+
+    let [signer_info, tape_info, _remaining @ ..] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    // Validate signer
+    if !signer_info.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if !tape_info.is_owned_by(&tape_api::ID) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let mut tape_data = tape_info.try_borrow_mut_data()?;
+    let tape = Tape::unpack_mut(&mut tape_data)?;
+
+    // Only the current authority can hand the tape over
+    check_condition(
+        tape.authority.eq(signer_info.key()),
+        TapeError::Unauthorized,
+    )?;
+
+    // Authority is immutable once the tape is finalized, there is nothing
+    // left for it to control besides rent, which anyone can top up
+    check_condition(
+        tape.state != TapeState::Finalized as u64,
+        TapeError::UnexpectedState,
+    )?;
+
+    // Validate the tape PDA with the cached bump
+    let tape_address = tape_derive_pda(tape.creator, &tape.name, tape.bump as u8);
+
+    if tape_info.key().ne(&tape_address) {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    tape.authority = args.new_authority;
+
+    Ok(())
+}
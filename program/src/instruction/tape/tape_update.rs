@@ -1,5 +1,9 @@
 use {
-    crate::{instruction::Update, utils::ByteConversion},
+    crate::{
+        instruction::Update,
+        state::pda::{tape_derive_pda, writer_derive_pda},
+        utils::{sync_tape_root, ByteConversion},
+    },
     pinocchio::{
         account_info::AccountInfo,
         program_error::ProgramError,
@@ -9,8 +13,7 @@ use {
     tape_api::{
         consts::{SEGMENT_PROOF_LEN, SEGMENT_SIZE},
         error::TapeError,
-        event::UpdateEvent,
-        pda::{tape_pda, writer_pda},
+        event::{SegmentUpdateEvent, UpdateEvent},
         state::{Tape, TapeState, Writer},
         utils::check_condition,
     },
@@ -43,8 +46,9 @@ pub fn process_tape_update(accounts: &[AccountInfo], data: &[u8]) -> ProgramResu
         return Err(ProgramError::InvalidAccountData);
     }
 
-    let (tape_address, _) = tape_pda(*signer_info.key(), &tape.name);
-    let (writer_address, _) = writer_pda(tape_address);
+    // Derive with the cached bumps instead of searching for them
+    let tape_address = tape_derive_pda(tape.creator, &tape.name, tape.bump as u8);
+    let writer_address = writer_derive_pda(tape_address, writer.bump as u8);
 
     if tape_info.key().ne(&tape_address) {
         return Err(ProgramError::InvalidAccountData);
@@ -54,8 +58,9 @@ pub fn process_tape_update(accounts: &[AccountInfo], data: &[u8]) -> ProgramResu
         return Err(ProgramError::InvalidAccountData);
     };
 
+    // An update is a write, so it is allowed wherever a write is
     check_condition(
-        tape.state == TapeState::Created as u64 || tape.state == TapeState::Writing as u64,
+        tape.can_transition_to(TapeState::Writing),
         TapeError::UnexpectedState,
     )?;
 
@@ -75,32 +80,36 @@ pub fn process_tape_update(accounts: &[AccountInfo], data: &[u8]) -> ProgramResu
         ProgramError::InvalidInstructionData,
     )?;
 
-    let old_leaf = Leaf::new(&[
-        segment_number.as_ref(), // u64_le_bytes
-        args.old_data.as_ref(),
-    ]);
-
-    let new_leaf = Leaf::new(&[
-        segment_number.as_ref(), // u64_le_bytes
-        args.new_data.as_ref(),
-    ]);
+    let segment_id = u64::from_le_bytes(segment_number);
+    let old_leaf = Leaf::from_segment(segment_id, &args.old_data);
+    let new_leaf = Leaf::from_segment(segment_id, &args.new_data);
 
-    writer
+    let old_root = writer
         .state
-        .try_replace_leaf_no_std(merkle_proof, old_leaf, new_leaf)
+        .try_replace_leaf_prev_root_no_std(merkle_proof, old_leaf, new_leaf)
         .map_err(|_| TapeError::WriteFailed)?;
 
     let prev_slot = tape.tail_slot;
 
-    tape.merkle_root = writer.state.get_root().to_bytes();
+    sync_tape_root(tape, writer);
     tape.tail_slot = current_slot;
 
     UpdateEvent {
         prev_slot,
-        segment_number: u64::from_le_bytes(segment_number),
+        segment_number: segment_id,
         address: tape_address,
     }
     .log();
 
+    SegmentUpdateEvent {
+        segment_number: segment_id,
+        old_root: old_root.to_bytes(),
+        new_root: tape.merkle_root,
+        address: tape_address,
+    }
+    .log();
+
+    debug_assert_eq!(tape.merkle_root, writer.state.get_root().to_bytes());
+
     Ok(())
 }
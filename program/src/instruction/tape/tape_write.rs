@@ -1,25 +1,33 @@
+use bytemuck::Zeroable;
 use pinocchio::{
     account_info::AccountInfo,
+    cpi::set_return_data,
     program_error::ProgramError,
     sysvars::{clock::Clock, Sysvar},
     ProgramResult,
 };
 use tape_api::{
-    consts::{MAX_SEGMENTS_PER_TAPE, SEGMENT_SIZE},
+    consts::{MAX_SEGMENTS_PER_TAPE, MAX_SEGMENTS_PER_WRITE, SEGMENT_SIZE, WRITER_SHARD_SEGMENTS},
     error::TapeError,
-    pda::{tape_pda, writer_pda},
     state::{Tape, TapeState, Writer},
-    utils::{check_condition, padded_array},
+    utils::{check_condition, to_segment},
 };
 use tape_utils::leaf::Leaf;
 
-// Helper function to compute leaf - same logic as tape_api::utils::compute_leaf
-#[inline(always)]
-fn compute_leaf(segment_id: u64, segment: &[u8; SEGMENT_SIZE]) -> Leaf {
-    let segment_id_bytes = segment_id.to_le_bytes();
-    Leaf::new(&[segment_id_bytes.as_ref(), segment])
-}
-
+use crate::state::pda::{tape_derive_pda, writer_shard_derive_pda};
+use crate::utils::sync_tape_root;
+
+/// Appends the instruction data to the tape as consecutive segments, the last
+/// one zero-padded, and returns the index of the first as little-endian u64.
+///
+/// Data is never truncated: bytes past a segment boundary start the next
+/// segment, and a write needing more than `MAX_SEGMENTS_PER_WRITE` fails.
+/// Each chunk goes through `to_segment`, which zero-pads a short segment and
+/// rejects one over `SEGMENT_SIZE` with `SegmentTooLarge`.
+///
+/// The writer may be any shard of the tape. Only shard 0 keeps the tape's
+/// root and segment count current; the others fill their own slice of
+/// `WRITER_SHARD_SEGMENTS` segments and are combined at finalize.
 pub fn process_tape_write(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResult {
     let [signer_info, tape_info, writer_info] = accounts else {
         return Err(ProgramError::NotEnoughAccountKeys);
@@ -29,8 +37,8 @@ pub fn process_tape_write(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResu
         return Err(ProgramError::MissingRequiredSignature);
     };
 
-    let mut tape_info_raw_data = tape_info.try_borrow_mut_data()?;
-    let tape = Tape::unpack_mut(&mut tape_info_raw_data)?;
+    let tape_info_raw_data = tape_info.try_borrow_data()?;
+    let tape = Tape::unpack(&tape_info_raw_data)?;
 
     if signer_info.key().ne(&tape.authority) {
         return Err(ProgramError::MissingRequiredSignature);
@@ -43,8 +51,9 @@ pub fn process_tape_write(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResu
         return Err(ProgramError::InvalidAccountData);
     };
 
-    let (tape_address, _) = tape_pda(*signer_info.key(), &tape.name);
-    let (writer_address, _) = writer_pda(tape_address);
+    // Derive with the cached bumps instead of searching for them
+    let tape_address = tape_derive_pda(tape.creator, &tape.name, tape.bump as u8);
+    let writer_address = writer_shard_derive_pda(tape_address, writer.shard, writer.bump as u8);
 
     if tape_info.key().ne(&tape_address) {
         return Err(ProgramError::InvalidAccountData);
@@ -54,53 +63,69 @@ pub fn process_tape_write(accounts: &[AccountInfo], _data: &[u8]) -> ProgramResu
     };
 
     check_condition(
-        tape.state.eq(&(TapeState::Created as u64)) || tape.state.eq(&(TapeState::Writing as u64)),
+        tape.can_transition_to(TapeState::Writing),
         TapeError::UnexpectedState,
     )?;
 
+    drop(tape_info_raw_data);
+
     // Convert the data to canonical segments and write to Merkle tree
     let write_data = _data;
 
+    // A write must add at least one segment
+    check_condition(!write_data.is_empty(), TapeError::EmptyWrite)?;
+
     // Calculate number of segments
-    let segment_count = if write_data.is_empty() {
-        0
+    let segment_count = ((write_data.len() + SEGMENT_SIZE - 1) / SEGMENT_SIZE) as u64;
+
+    // Keep a single write well inside the compute budget
+    check_condition(
+        segment_count <= MAX_SEGMENTS_PER_WRITE as u64,
+        TapeError::WriteTooLarge,
+    )?;
+
+    // The tape's own writer can run past its shard, the others stop at theirs
+    let shard_capacity = if writer.shard == 0 {
+        MAX_SEGMENTS_PER_TAPE
     } else {
-        ((write_data.len() + SEGMENT_SIZE - 1) / SEGMENT_SIZE) as u64
+        WRITER_SHARD_SEGMENTS
     };
 
     check_condition(
-        tape.total_segments + segment_count <= MAX_SEGMENTS_PER_TAPE as u64,
+        writer.state.get_leaf_count() + segment_count <= shard_capacity as u64,
         TapeError::TapeTooLong,
     )?;
 
-    // Process each segment
-    let mut offset = 0;
-    for i in 0..segment_count {
-        let end = core::cmp::min(offset + SEGMENT_SIZE, write_data.len());
-        let segment_slice = &write_data[offset..end];
-        let canonical_segment = padded_array::<SEGMENT_SIZE>(segment_slice);
+    // Compute every leaf first so the tree is only walked to the root once
+    let start_segment = writer.shard * WRITER_SHARD_SEGMENTS as u64 + writer.state.get_leaf_count();
+    let mut leaves = [Leaf::zeroed(); MAX_SEGMENTS_PER_WRITE];
+    for (i, chunk) in write_data.chunks(SEGMENT_SIZE).enumerate() {
+        let canonical_segment = to_segment(chunk)?;
+        leaves[i] = Leaf::from_segment(start_segment + i as u64, &canonical_segment);
+    }
 
-        // Compute leaf and add to merkle tree
-        let segment_number = tape.total_segments + i;
-        let leaf = compute_leaf(segment_number, &canonical_segment);
+    writer
+        .state
+        .try_add_leaves(&leaves[..segment_count as usize])
+        .map_err(|_| TapeError::WriteFailed)?;
 
-        writer
-            .state
-            .try_add_leaf(leaf)
-            .map_err(|_| TapeError::WriteFailed)?;
+    // Other shards leave the tape alone so their writes don't contend for
+    // it, finalize folds them into the tape root
+    if writer.shard == 0 {
+        let mut tape_info_raw_data = tape_info.try_borrow_mut_data()?;
+        let tape = Tape::unpack_mut(&mut tape_info_raw_data)?;
 
-        offset = end;
-    }
+        let current_slot = Clock::get()?.slot;
 
-    let _prev_slot = tape.tail_slot;
-    let current_slot = Clock::get()?.slot;
+        tape.total_segments += segment_count;
+        sync_tape_root(tape, writer);
+        tape.state = TapeState::Writing as u64;
+        tape.tail_slot = current_slot;
 
-    tape.total_segments += segment_count;
-    tape.merkle_root = writer.state.get_root().to_bytes();
-    tape.state = TapeState::Writing as u64;
-    tape.tail_slot = current_slot;
+        debug_assert_eq!(tape.merkle_root, writer.state.get_root().to_bytes());
+    }
 
-    // No event logging in Pinocchio for now
+    set_return_data(&start_segment.to_le_bytes());
 
     Ok(())
 }
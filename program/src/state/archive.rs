@@ -21,9 +21,17 @@ impl DataLen for Archive {
 }
 
 impl Archive {
-    /// Global reward to miners for the current block.
+    /// Rent every stored tape pays together each block, `RENT_PER_SEGMENT`
+    /// per segment.
     #[inline]
-    pub fn block_reward(&self) -> u64 {
+    pub fn total_storage_fees(&self) -> u64 {
         self.segments_stored.saturating_mul(RENT_PER_SEGMENT)
     }
+
+    /// Global reward to miners for the current block, the storage fees
+    /// passed through one for one. See `tape_api`'s `Archive::block_reward`.
+    #[inline]
+    pub fn block_reward(&self) -> u64 {
+        self.total_storage_fees()
+    }
 }
\ No newline at end of file
@@ -17,10 +17,7 @@ pub const HEADER_SIZE: usize = 64;
 // Use the ID from tape_api instead of hardcoding to ensure they match
 pub const TAPE_ID: Pubkey = tape_api::ID;
 
-pub const MPL_TOKEN_METADATA_ID: Pubkey = [
-    11, 112, 101, 177, 227, 209, 124, 69, 56, 157, 82, 127, 107, 4, 195, 205, 88, 184, 108, 115,
-    26, 160, 253, 181, 73, 182, 209, 188, 3, 248, 41, 70,
-];
+pub const MPL_TOKEN_METADATA_ID: Pubkey = tape_api::MPL_TOKEN_METADATA_ID;
 pub const ARCHIVE: &[u8] = b"archive";
 pub const BLOCK: &[u8] = b"block";
 pub const EPOCH: &[u8] = b"epoch";
@@ -35,6 +32,18 @@ pub const METADATA: &[u8] = b"metadata";
 /// Mint PDA seed (raw bytes)
 pub const MINT_SEED: &[u8] = &[152, 68, 212, 200, 25, 113, 221, 71];
 
+pub const ARCHIVE_ADDRESS: Pubkey = ed25519::derive_program_address(&[ARCHIVE], &TAPE_ID).0;
+
+pub const ARCHIVE_BUMP: u8 = ed25519::derive_program_address(&[ARCHIVE], &TAPE_ID).1;
+
+pub const EPOCH_ADDRESS: Pubkey = ed25519::derive_program_address(&[EPOCH], &TAPE_ID).0;
+
+pub const EPOCH_BUMP: u8 = ed25519::derive_program_address(&[EPOCH], &TAPE_ID).1;
+
+pub const BLOCK_ADDRESS: Pubkey = ed25519::derive_program_address(&[BLOCK], &TAPE_ID).0;
+
+pub const BLOCK_BUMP: u8 = ed25519::derive_program_address(&[BLOCK], &TAPE_ID).1;
+
 pub const MINT_ADDRESS: Pubkey = ed25519::derive_program_address(&[MINT, MINT_SEED], &TAPE_ID).0;
 
 pub const MINT_BUMP: u8 = ed25519::derive_program_address(&[MINT, MINT_SEED], &TAPE_ID).1;
@@ -43,6 +52,13 @@ pub const TREASURY_ADDRESS: Pubkey = ed25519::derive_program_address(&[TREASURY]
 
 pub const TREASURY_BUMP: u8 = ed25519::derive_program_address(&[TREASURY], &TAPE_ID).1;
 
+/// Metaplex metadata PDA for the mint (owned by the metadata program)
+pub const METADATA_ADDRESS: Pubkey = ed25519::derive_program_address(
+    &[METADATA, &MPL_TOKEN_METADATA_ID, &MINT_ADDRESS],
+    &MPL_TOKEN_METADATA_ID,
+)
+.0;
+
 /// Duration of one block in seconds (~1 minute)
 pub const BLOCK_DURATION_SECONDS: u64 = 60;
 /// Number of blocks per epoch (~10 minutes)
@@ -13,8 +13,14 @@ pub struct Epoch {
     pub target_participation: u64,
     pub reward_rate: u64,
     pub duplicates: u64,
+    /// Rewards paid out so far this epoch, reset when the epoch advances
+    pub epoch_rewards_distributed: u64,
 
     pub last_epoch_at: i64,
+
+    /// Rate set by the treasury authority through SetRewardRate, kept in place
+    /// of the schedule when the epoch advances. Zero follows the schedule.
+    pub reward_rate_override: u64,
 }
 
 impl AccountDiscriminator for Epoch {
@@ -24,5 +30,5 @@ impl AccountDiscriminator for Epoch {
 }
 
 impl DataLen for Epoch {
-    const LEN: usize = 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8; // 64 bytes
+    const LEN: usize = 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8; // 72 bytes
 }
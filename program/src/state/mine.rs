@@ -1,4 +1,5 @@
 use pinocchio::program_error::ProgramError;
+use tape_api::TAPE_PROOF_LEN;
 
 use crate::state::{DataLen, PoA, PoW};
 
@@ -7,18 +8,25 @@ use crate::state::{DataLen, PoA, PoW};
 pub struct Mine {
     pub pow: PoW,
     pub poa: PoA,
+    /// The value previously committed via spool_commit
+    pub value: [u8; 32],
+    /// The value the recalled tape was packed into the spool with
+    pub tape_value: [u8; 32],
+    /// Proof that the recalled tape is in the spool
+    pub tape_proof: [[u8; 32]; TAPE_PROOF_LEN],
 }
 
 impl DataLen for Mine {
-    const LEN: usize = PoW::LEN + PoA::LEN;
+    const LEN: usize = PoW::LEN + PoA::LEN + 32 + 32 + 32 * TAPE_PROOF_LEN;
 }
 
 impl Mine {
     pub fn try_from_bytes(
         data: &[u8],
     ) -> Result<&mut Self, ProgramError> {
+        // Reject truncated or oversized data before reinterpreting it
         if data.len() != Self::LEN {
-            return Err(ProgramError::InvalidAccountData);
+            return Err(ProgramError::InvalidInstructionData);
         }
         // SAFETY: Caller provides a mutable slice with exact size Self::LEN; we transmute to &mut Self.
         Ok(unsafe { &mut *(data.as_ptr() as *mut Self) })
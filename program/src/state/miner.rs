@@ -23,6 +23,17 @@ pub struct Miner {
 
     pub total_proofs: u64,
     pub total_rewards: u64,
+
+    /// Bump of the miner PDA, cached so handlers don't search for it again
+    pub bump: u64,
+
+    /// Refundable lamports held on top of rent, see `MINER_DEPOSIT`
+    pub deposit: u64,
+    /// Lamports slashed from the deposit, paid to the treasury by mine
+    pub forfeited: u64,
+
+    /// Mutable label for display, `name` stays the PDA seed
+    pub display_name: [u8; NAME_LEN],
 }
 
 impl AccountDiscriminator for Miner {
@@ -32,5 +43,5 @@ impl AccountDiscriminator for Miner {
 }
 
 impl DataLen for Miner {
-    const LEN: usize = 32 + 32 + 8 + 32 + 32 + 8 + 8 + 8 + 8 + 8; // 176 bytes
+    const LEN: usize = 32 + 32 + 8 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 32; // 232 bytes
 }
@@ -1,5 +1,6 @@
 use crate::state::constant::{
-    MINT_ADDRESS, MINT_BUMP, NAME_LEN, TAPE, TAPE_ID, TREASURY_ADDRESS, TREASURY_BUMP, WRITER,
+    MINER, MINT_ADDRESS, MINT_BUMP, NAME_LEN, TAPE, TAPE_ID, TREASURY_ADDRESS, TREASURY_BUMP,
+    WRITER,
 };
 use core::mem::MaybeUninit;
 use pinocchio::pubkey::{self, Pubkey};
@@ -89,6 +90,27 @@ pub fn writer_derive_pda(tape: Pubkey, bump: u8) -> Pubkey {
     pda_derive_address(&[WRITER, tape.as_ref()], Some(bump), &TAPE_ID)
 }
 
+/// Writer for shard `shard` of `tape`, shard 0 being the tape's own writer
+pub fn writer_shard_derive_pda(tape: Pubkey, shard: u64, bump: u8) -> Pubkey {
+    if shard == 0 {
+        return writer_derive_pda(tape, bump);
+    }
+
+    pda_derive_address(
+        &[WRITER, tape.as_ref(), shard.to_le_bytes().as_ref()],
+        Some(bump),
+        &TAPE_ID,
+    )
+}
+
+pub fn miner_derive_pda(authority: Pubkey, name: &[u8; NAME_LEN], bump: u8) -> Pubkey {
+    pda_derive_address(
+        &[MINER, authority.as_ref(), name.as_ref()],
+        Some(bump),
+        &TAPE_ID,
+    )
+}
+
 #[inline(always)]
 pub const fn treasury_pda() -> (Pubkey, u8) {
     (TREASURY_ADDRESS, TREASURY_BUMP)
@@ -1,4 +1,5 @@
 use crate::state::AccountType;
+use crate::state::DataLen;
 use crate::utils::AccountDiscriminator;
 use bytemuck::{Pod, Zeroable};
 use pinocchio::pubkey::Pubkey;
@@ -15,9 +16,12 @@ pub struct Spool {
     pub contains: [u8; 32],
 
     pub total_tapes: u64,
+    pub live_tapes: u64,
 
     pub last_proof_block: u64,
     pub last_proof_at: i64,
+
+    pub history: [u8; 32],
 }
 
 impl AccountDiscriminator for Spool {
@@ -25,3 +29,7 @@ impl AccountDiscriminator for Spool {
         AccountType::Spool as u8
     }
 }
+
+impl DataLen for Spool {
+    const LEN: usize = core::mem::size_of::<Spool>();
+}
@@ -16,6 +16,8 @@ pub struct Tape {
     pub state: u64,
 
     pub authority: Pubkey,
+    /// Original authority, kept as the PDA seed when authority is transferred
+    pub creator: Pubkey,
 
     pub name: [u8; NAME_LEN],
     pub merkle_root: [u8; 32],
@@ -26,6 +28,8 @@ pub struct Tape {
     pub balance: u64,
     pub last_rent_block: u64,
     pub total_segments: u64,
+    /// Bump of the tape PDA, cached so handlers don't search for it again
+    pub bump: u64,
     // +Phantom Vec<Hash> for merkle subtree nodes (up to 4096).
 }
 
@@ -45,13 +49,13 @@ impl AccountDiscriminator for Tape {
 }
 
 impl DataLen for Tape {
-    const LEN: usize = 8 + 8 + 32 + NAME_LEN + 32 + HEADER_SIZE + 8 + 8 + 8 + 8 + 8; // 216 bytes (matches native)
+    const LEN: usize = 8 + 8 + 32 + 32 + NAME_LEN + 32 + HEADER_SIZE + 8 + 8 + 8 + 8 + 8 + 8; // 256 bytes
 }
 
 impl Tape {
     // check if this tape is subsidized.
     pub fn has_minimum_rent(&self) -> bool {
-        self.balance >= self.rent_per_block()
+        !self.is_expired(self.last_rent_block)
     }
 
     pub fn rent_per_block(&self) -> u64 {
@@ -68,4 +72,20 @@ impl Tape {
         let blocks = current_block.saturating_sub(self.last_rent_block) as u128;
         (self.rent_per_block() as u128 * blocks) as u64
     }
+
+    // whole blocks of rent the balance covers after paying what is owed.
+    pub fn blocks_remaining(&self, current_block: u64) -> u64 {
+        let rent = self.rent_per_block();
+        if rent == 0 {
+            return u64::MAX;
+        }
+
+        let balance = self.balance.saturating_sub(self.rent_owed(current_block));
+        balance / rent
+    }
+
+    // check if the balance can no longer cover a block of rent.
+    pub fn is_expired(&self, current_block: u64) -> bool {
+        self.blocks_remaining(current_block) == 0
+    }
 }
@@ -4,7 +4,10 @@ use bytemuck::{Pod, Zeroable};
 
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
-pub struct Treasury {}
+pub struct Treasury {
+    /// Signer of initialize, the only key allowed to override the reward rate
+    pub authority: [u8; 32],
+}
 
 impl AccountDiscriminator for Treasury {
     fn discriminator() -> u8 {
@@ -1,6 +1,8 @@
+use bytemuck::Pod;
 use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
 
 use crate::error::MyProgramError;
+use crate::utils::AccountDiscriminator;
 
 pub trait DataLen {
     const LEN: usize;
@@ -66,7 +68,49 @@ pub unsafe fn to_mut_bytes<T: DataLen>(data: &mut T) -> &mut [u8] {
     core::slice::from_raw_parts_mut(data as *mut T as *mut u8, T::LEN)
 }
 
-pub unsafe fn try_from_account_info<T: DataLen>(acc: &AccountInfo) -> Result<&T, ProgramError> {
+/// Loads an account created with a discriminator, as `create_program_account`
+/// lays it out: the type byte, 7 bytes of padding, then `T`.
+///
+/// Fails with `InvalidAccountData` if the type byte isn't `T`'s, so one
+/// account type can't be passed off as another of the same size.
+pub unsafe fn try_from_account_info<T: AccountDiscriminator + Pod>(
+    acc: &AccountInfo,
+) -> Result<&T, ProgramError> {
+    if acc.owner() != &crate::ID {
+        return Err(ProgramError::IllegalOwner);
+    }
+    let bytes = acc.try_borrow_data()?;
+
+    check_discriminator::<T>(&bytes)?;
+    Ok(&*(bytes.as_ptr().add(8) as *const T))
+}
+
+/// Mutable counterpart of [`try_from_account_info`].
+pub unsafe fn try_from_account_info_mut<T: AccountDiscriminator + Pod>(
+    acc: &AccountInfo,
+) -> Result<&mut T, ProgramError> {
+    if acc.owner() != &crate::ID {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let mut bytes = acc.try_borrow_mut_data()?;
+
+    check_discriminator::<T>(&bytes)?;
+    Ok(&mut *(bytes.as_mut_ptr().add(8) as *mut T))
+}
+
+#[inline(always)]
+fn check_discriminator<T: AccountDiscriminator + Pod>(bytes: &[u8]) -> Result<(), ProgramError> {
+    if bytes.len() != 8 + core::mem::size_of::<T>() || bytes[0] != T::discriminator() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(())
+}
+
+/// Loads an account stored as a bare `T`, without a type byte to check.
+pub unsafe fn try_from_account_info_unchecked<T: DataLen>(
+    acc: &AccountInfo,
+) -> Result<&T, ProgramError> {
     if acc.owner() != &crate::ID {
         return Err(ProgramError::IllegalOwner);
     }
@@ -78,7 +122,8 @@ pub unsafe fn try_from_account_info<T: DataLen>(acc: &AccountInfo) -> Result<&T,
     Ok(&*(bytes.as_ptr() as *const T))
 }
 
-pub unsafe fn try_from_account_info_mut<T: DataLen>(
+/// Mutable counterpart of [`try_from_account_info_unchecked`].
+pub unsafe fn try_from_account_info_unchecked_mut<T: DataLen>(
     acc: &AccountInfo,
 ) -> Result<&mut T, ProgramError> {
     if acc.owner() != &crate::ID {
@@ -9,6 +9,10 @@ use tape_api::types::SegmentTree;
 pub struct Writer {
     pub tape: Pubkey,
     pub state: SegmentTree,
+    /// Bump of the writer PDA, cached so handlers don't search for it again
+    pub bump: u64,
+    /// Which slice of the tape this writer appends to, 0 for the tape's own writer
+    pub shard: u64,
 }
 
 impl AccountDiscriminator for Writer {
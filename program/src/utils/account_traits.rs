@@ -6,6 +6,11 @@ use pinocchio::{account_info::AccountInfo, ProgramResult};
 pub trait AccountInfoExt {
     fn check_account(&self, seed: &[u8]) -> ProgramResult;
     fn check_account_with_address(&self, address: &Pubkey) -> ProgramResult;
+    fn check_account_or_existing(
+        &self,
+        address: &Pubkey,
+        owner: &Pubkey,
+    ) -> Result<bool, ProgramError>;
     fn is_program_check(&self) -> ProgramResult;
 }
 
@@ -39,6 +44,29 @@ impl AccountInfoExt for AccountInfo {
         Ok(())
     }
 
+    /// Like `check_account_with_address`, but accepts an account that was
+    /// already created by `owner`, e.g. one made outside the calling
+    /// instruction. Returns `true` if the account exists.
+    fn check_account_or_existing(
+        &self,
+        address: &Pubkey,
+        owner: &Pubkey,
+    ) -> Result<bool, ProgramError> {
+        if self.key().ne(address) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        if !self.is_writable() {
+            return Err(ProgramError::Immutable);
+        }
+        if self.data_is_empty() {
+            return Ok(false);
+        }
+        if !self.is_owned_by(owner) {
+            return Err(ProgramError::IllegalOwner);
+        }
+        Ok(true)
+    }
+
     fn is_program_check(&self) -> ProgramResult {
         if self.key().ne(&TAPE_ID) {
             return Err(ProgramError::InvalidAccountData);
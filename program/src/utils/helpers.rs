@@ -10,6 +10,11 @@ use pinocchio::{
     ProgramResult,
 };
 use pinocchio_system::instructions::CreateAccount;
+use tape_api::consts::{MAX_WRITER_SHARDS, SEGMENT_TREE_HEIGHT, WRITER_SHARD_HEIGHT};
+use tape_api::error::TapeError;
+use tape_api::state::{Tape, Writer};
+use tape_utils::leaf::Hash;
+use tape_utils::tree::hash_left_right;
 
 /// Creates a new program account (PDA) with discriminator.
 ///
@@ -45,6 +50,47 @@ pub fn create_program_account<T: AccountDiscriminator + Pod>(
         return Err(pinocchio::program_error::ProgramError::InvalidAccountData);
     }
 
+    create_program_account_with_bump::<T>(
+        target_account,
+        _system_program,
+        payer,
+        owner,
+        seeds,
+        bump,
+    )
+}
+
+/// Creates a new program account (PDA) with discriminator using a known bump.
+///
+/// Same as [`create_program_account`] but skips the `find_program_address`
+/// bump search. Intended for singletons whose address and bump are
+/// compile-time constants (archive, epoch, block, treasury).
+///
+/// The caller is responsible for checking `target_account` against the
+/// expected address. A wrong bump still fails: the runtime re-derives the
+/// PDA with `create_program_address` when verifying the signer seeds.
+///
+/// # Example
+/// ```rust
+/// epoch_info.check_account_with_address(&EPOCH_ADDRESS)?;
+/// create_program_account_with_bump::<Epoch>(
+///     epoch_info,
+///     system_program_info,
+///     signer_info,
+///     &tape_api::ID,
+///     &[EPOCH],
+///     EPOCH_BUMP,
+/// )?;
+/// ```
+#[inline(always)]
+pub fn create_program_account_with_bump<T: AccountDiscriminator + Pod>(
+    target_account: &AccountInfo,
+    _system_program: &AccountInfo,
+    payer: &AccountInfo,
+    owner: &Pubkey,
+    seeds: &[&[u8]],
+    bump: u8,
+) -> ProgramResult {
     // Calculate space: 8 bytes for discriminator + struct size
     let space = 8 + core::mem::size_of::<T>();
     let rent = Rent::get()?;
@@ -155,3 +201,63 @@ pub fn cast_account_data_mut<T: Pod>(data: &mut [u8]) -> Result<&mut T, ProgramE
     // Safe cast using bytemuck (no unsafe!)
     bytemuck::try_from_bytes_mut::<T>(&mut data[8..]).map_err(|_| ProgramError::InvalidAccountData)
 }
+
+/// Read-only counterpart of [`cast_account_data_mut`].
+#[inline(always)]
+pub fn cast_account_data<T: Pod>(data: &[u8]) -> Result<&T, ProgramError> {
+    // Validate length: 8 bytes for discriminator + struct size
+    let expected_len = 8 + core::mem::size_of::<T>();
+    if data.len() != expected_len {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    bytemuck::try_from_bytes::<T>(&data[8..]).map_err(|_| ProgramError::InvalidAccountData)
+}
+
+/// Copies the writer's segment tree root into the tape.
+///
+/// Call after every mutation of `writer.state` so the two roots can't drift
+/// apart.
+#[inline(always)]
+pub fn sync_tape_root(tape: &mut Tape, writer: &Writer) {
+    tape.merkle_root = writer.state.get_root().to_bytes();
+}
+
+/// Combines the subtree roots of the writer shards, in shard order, into the
+/// root of the full segment tree.
+///
+/// Shard `k` is the node at `WRITER_SHARD_HEIGHT` and index `k` of the tape's
+/// tree, so the result matches appending every segment to a single writer.
+/// Missing shards are padded with `zero_values`, as the tree pads them.
+pub fn combine_shard_roots(shards: &[Hash], zero_values: &[Hash; SEGMENT_TREE_HEIGHT]) -> Hash {
+    let mut nodes = [zero_values[WRITER_SHARD_HEIGHT]; MAX_WRITER_SHARDS];
+    nodes[..shards.len()].copy_from_slice(shards);
+
+    let mut width = MAX_WRITER_SHARDS;
+    for level in WRITER_SHARD_HEIGHT..SEGMENT_TREE_HEIGHT {
+        for i in 0..width.div_ceil(2) {
+            let right = if 2 * i + 1 < width {
+                nodes[2 * i + 1]
+            } else {
+                zero_values[level]
+            };
+            nodes[i] = hash_left_right(nodes[2 * i], right);
+        }
+        width = width.div_ceil(2);
+    }
+
+    nodes[0]
+}
+
+/// Checks the tape's root still matches the writer's segment tree.
+///
+/// A tape archived with a stale root can never be mined, so finalize must
+/// refuse it.
+#[inline(always)]
+pub fn check_tape_root(tape: &Tape, writer: &Writer) -> ProgramResult {
+    if tape.merkle_root != writer.state.get_root().to_bytes() {
+        return Err(TapeError::RootMismatch.into());
+    }
+
+    Ok(())
+}
@@ -16,7 +16,6 @@ use solana_sdk::{
 use spl_token::state::Mint;
 
 use tape_api::consts::*;
-use tape_api::utils::to_name;
 
 // Program IDs
 fn program_id() -> SolanaPubkey {
@@ -36,11 +35,7 @@ fn spl_ata_id() -> SolanaPubkey {
 }
 
 fn mpl_metadata_id() -> SolanaPubkey {
-    // Must match MPL_TOKEN_METADATA_ID constant in program/src/state/constant.rs
-    SolanaPubkey::new_from_array([
-        11, 112, 101, 177, 227, 209, 124, 69, 56, 157, 82, 127, 107, 4, 195, 205, 88, 184, 108,
-        115, 26, 160, 253, 181, 73, 182, 209, 188, 3, 248, 41, 70,
-    ])
+    SolanaPubkey::from(MPL_TOKEN_METADATA_ID)
 }
 
 /// Complete test that runs through the ENTIRE initialize instruction
@@ -69,26 +64,11 @@ fn test_pinocchio_initialize_complete() {
     let treasury_pda = SolanaPubkey::from(TREASURY_ADDRESS);
     let treasury_ata_pda = SolanaPubkey::from(TREASURY_ATA);
 
-    let metadata_program = mpl_metadata_id();
-    let metadata_pda = {
-        let seeds = &[b"metadata", metadata_program.as_ref(), mint_pda.as_ref()];
-        let (pda, _) = SolanaPubkey::find_program_address(seeds, &metadata_program);
-        pda
-    };
+    let metadata_pda = SolanaPubkey::from(METADATA_ADDRESS);
 
     let prog_id = program_id();
-    let tape_pda = {
-        let name = to_name("genesis");
-        let seeds = &[b"tape", payer_pubkey.as_ref(), &name];
-        let (pda, _) = SolanaPubkey::find_program_address(seeds, &prog_id);
-        pda
-    };
-
-    let writer_pda = {
-        let seeds = &[b"writer", tape_pda.as_ref()];
-        let (pda, _) = SolanaPubkey::find_program_address(seeds, &prog_id);
-        pda
-    };
+    let tape_pda = SolanaPubkey::from(GENESIS_TAPE_ADDRESS);
+    let writer_pda = SolanaPubkey::from(GENESIS_WRITER_ADDRESS);
 
     println!("PDAs derived:");
     println!("  Archive:      {}", archive_pda);
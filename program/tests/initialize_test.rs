@@ -1,22 +1,28 @@
 #![cfg(test)]
 
+use base64::{engine::general_purpose::STANDARD, Engine};
 use litesvm::LiteSVM;
 use solana_program::program_pack::Pack;
 use solana_sdk::{
-    instruction::{AccountMeta, Instruction},
+    instruction::{AccountMeta, Instruction, InstructionError},
     pubkey::Pubkey,
     signature::Keypair,
     signer::Signer,
     system_program,
     sysvar::{rent, slot_hashes},
-    transaction::Transaction,
+    transaction::{Transaction, TransactionError},
 };
 use spl_token::state::Mint;
 
 // Import from the source directly (like pinocchio-multisig does)
-use pinnochio_tape_program::state::{Archive, Block, Epoch, Tape, TapeState};
+use pinnochio_tape_program::instruction::mine::miner_mine::get_base_rate;
+use pinnochio_tape_program::state::{AccountType, Archive, Block, Epoch, Tape, TapeState};
+use tape_api::client;
 use tape_api::consts::*;
-use tape_api::utils::to_name;
+use tape_api::error::TapeError;
+use tape_api::event::EpochReportEvent;
+use tape_api::pda::{protocol_pdas, tape_and_writer};
+use tape_api::utils::{padded_array, to_name};
 
 /// Test basic initialization of the pinocchio tape program
 #[test]
@@ -35,7 +41,7 @@ fn test_pinocchio_initialize_basic() {
     verify_mint_account(&svm);
     verify_metadata_account(&svm);
     verify_treasury_ata(&svm);
-    verify_genesis_tape(&svm, &payer);
+    verify_genesis_tape(&svm);
 
     println!("Successfully initialized pinocchio tape program!");
 }
@@ -58,6 +64,196 @@ fn test_pinocchio_initialize_already_initialized() {
     println!("Correctly rejected double initialization!");
 }
 
+/// Test that initialize completes around an epoch that already exists
+#[test]
+fn test_pinocchio_initialize_resumes_partial_init() {
+    let (mut svm, payer, program_id) = setup_environment();
+
+    let epoch_address = Pubkey::from(EPOCH_ADDRESS);
+    let data = set_existing_epoch(&mut svm, program_id);
+
+    // Initialize skips the epoch and completes the rest
+    initialize_program(&mut svm, &payer, program_id);
+
+    verify_archive_account(&svm);
+    verify_block_account(&svm);
+    verify_treasury_account(&svm);
+    verify_mint_account(&svm);
+    verify_metadata_account(&svm);
+    verify_treasury_ata(&svm);
+
+    let epoch_account = svm.get_account(&epoch_address).unwrap();
+    assert_eq!(
+        epoch_account.data, data,
+        "Existing epoch should be untouched"
+    );
+
+    let ata_balance = get_ata_balance(&svm, &Pubkey::from(TREASURY_ATA));
+    assert_eq!(ata_balance, MAX_SUPPLY, "Treasury should have max supply");
+
+    println!("Partial initialization completed on re-run!");
+}
+
+/// Test that only a fully initialized program rejects initialize
+#[test]
+fn test_pinocchio_initialize_precreated_epoch_then_double_init() {
+    let (mut svm, payer, program_id) = setup_environment();
+    set_existing_epoch(&mut svm, program_id);
+
+    // Every step but the epoch still runs
+    initialize_program(&mut svm, &payer, program_id);
+    verify_block_account(&svm);
+    verify_treasury_ata(&svm);
+
+    // Now nothing is left to do
+    let ix = build_initialize_ix(payer.pubkey(), program_id);
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[&payer], blockhash);
+    let res = svm.send_transaction(tx).map_err(|e| e.err);
+    assert_eq!(
+        res.err(),
+        Some(TransactionError::InstructionError(
+            0,
+            InstructionError::AccountAlreadyInitialized
+        ))
+    );
+}
+
+/// Test that initialize only mints into the treasury's own ATA
+#[test]
+fn test_pinocchio_initialize_rejects_other_treasury_ata() {
+    let (mut svm, payer, program_id) = setup_environment();
+
+    // A token account that is not the treasury ATA
+    let decoy = Pubkey::new_unique();
+    let mut data = vec![0u8; spl_token::state::Account::LEN];
+    spl_token::state::Account {
+        mint: MINT_ADDRESS.into(),
+        owner: payer.pubkey().to_bytes().into(),
+        state: spl_token::state::AccountState::Initialized,
+        ..Default::default()
+    }
+    .pack_into_slice(&mut data);
+    svm.set_account(
+        decoy,
+        solana_sdk::account::Account {
+            lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+            data,
+            owner: Pubkey::from(spl_token::ID.to_bytes()),
+            executable: false,
+            rent_epoch: 0,
+        }
+        .into(),
+    )
+    .unwrap();
+
+    let mut ix = build_initialize_ix(payer.pubkey(), program_id);
+    ix.accounts[7].pubkey = decoy;
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[&payer], blockhash);
+    let res = svm.send_transaction(tx).map_err(|e| e.err);
+    assert_eq!(
+        res.err(),
+        Some(TransactionError::InstructionError(
+            0,
+            InstructionError::InvalidAccountData
+        ))
+    );
+    assert_eq!(get_ata_balance(&svm, &decoy), 0);
+}
+
+/// Test initialize reports the genesis tape and writer via return data
+#[test]
+fn test_pinocchio_initialize_returns_genesis_pdas() {
+    let (mut svm, payer, program_id) = setup_environment();
+
+    let ix = build_initialize_ix(payer.pubkey(), program_id);
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[&payer], blockhash);
+    let meta = svm
+        .send_transaction(tx)
+        .expect("Initialization should succeed");
+
+    let return_data = meta.return_data;
+    assert_eq!(return_data.program_id, program_id);
+    assert_eq!(return_data.data.len(), 64);
+
+    // The genesis tape is the treasury's, wherever initialize was run from
+    let (tape_address, _, writer_address, _) =
+        tape_and_writer(TREASURY_ADDRESS, &to_name(GENESIS_NAME));
+    assert_eq!(return_data.data[..32], tape_address);
+    assert_eq!(return_data.data[32..], writer_address);
+    assert_eq!(tape_address, GENESIS_TAPE_ADDRESS);
+    assert_eq!(writer_address, GENESIS_WRITER_ADDRESS);
+}
+
+/// Test initialize rejects a genesis tape other than the fixed one
+#[test]
+fn test_pinocchio_initialize_rejects_other_genesis_tape() {
+    let (mut svm, payer, program_id) = setup_environment();
+
+    let mut ix = build_initialize_ix(payer.pubkey(), program_id);
+    let (tape_address, _, writer_address, _) =
+        tape_and_writer(payer.pubkey().to_bytes(), &to_name(GENESIS_NAME));
+    ix.accounts[8].pubkey = tape_address.into();
+    ix.accounts[9].pubkey = writer_address.into();
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[&payer], blockhash);
+    let res = svm.send_transaction(tx).map_err(|e| e.err);
+    assert_eq!(
+        res.err(),
+        Some(TransactionError::InstructionError(
+            0,
+            InstructionError::InvalidAccountData
+        ))
+    );
+}
+
+/// Test the client helpers reproduce a tape's PDAs as derived on-chain
+#[test]
+fn test_client_helpers_match_genesis_pdas() {
+    let payer = Keypair::new();
+    let program_id = Pubkey::from(tape_api::ID);
+
+    let name = to_name(GENESIS_NAME);
+    let (tape_address, tape_bump) =
+        Pubkey::find_program_address(&[TAPE, payer.pubkey().as_ref(), &name], &program_id);
+    let (writer_address, writer_bump) =
+        Pubkey::find_program_address(&[WRITER, tape_address.as_ref()], &program_id);
+
+    let (client_tape, client_tape_bump) =
+        client::tape_address(payer.pubkey().to_bytes(), GENESIS_NAME);
+    assert_eq!(Pubkey::from(client_tape), tape_address);
+    assert_eq!(client_tape_bump, tape_bump);
+
+    let (client_writer, client_writer_bump) = client::writer_address(client_tape);
+    assert_eq!(Pubkey::from(client_writer), writer_address);
+    assert_eq!(client_writer_bump, writer_bump);
+}
+
+/// Test the client parses tape bytes and rejects anything else
+#[test]
+fn test_client_parse_tape() {
+    let mut data = vec![0u8; core::mem::size_of::<Tape>()];
+    {
+        let tape = tape_api::state::Tape::unpack_mut(&mut data).unwrap();
+        tape.number = 1;
+        tape.state = TapeState::Finalized as u64;
+    }
+
+    let tape = client::parse_tape(&data).unwrap();
+    assert_eq!(tape.number, 1);
+    assert_eq!(tape.state, TapeState::Finalized as u64);
+
+    // A closed account keeps a single 0xff byte
+    assert_eq!(client::parse_tape(&[0xff]), Err(TapeError::UnexpectedState));
+
+    tape_api::state::Tape::unpack_mut(&mut data).unwrap().state = u64::MAX;
+    assert_eq!(client::parse_tape(&data), Err(TapeError::UnexpectedState));
+}
+
 /// Test archive account state after initialization
 #[test]
 fn test_pinocchio_initialize_archive_state() {
@@ -107,6 +303,10 @@ fn test_pinocchio_initialize_epoch_state() {
     assert_eq!(epoch.mining_difficulty, MIN_MINING_DIFFICULTY);
     assert_eq!(epoch.packing_difficulty, MIN_PACKING_DIFFICULTY);
     assert_eq!(epoch.duplicates, 0, "Duplicates should start at 0");
+    assert_eq!(
+        epoch.epoch_rewards_distributed, 0,
+        "No rewards should be distributed yet"
+    );
     assert_eq!(epoch.last_epoch_at, 0, "Last epoch should start at 0");
 
     println!(
@@ -194,13 +394,7 @@ fn test_pinocchio_initialize_genesis_tape() {
     let (mut svm, payer, program_id) = setup_environment();
     initialize_program(&mut svm, &payer, program_id);
 
-    let genesis_name = "genesis";
-    let name_bytes = to_name(genesis_name);
-    // Derive tape PDA: seeds = ["tape", payer_pubkey, name]
-    let (tape_address, _) = Pubkey::find_program_address(
-        &[b"tape", payer.pubkey().as_ref(), &name_bytes],
-        &program_id,
-    );
+    let tape_address = Pubkey::from(GENESIS_TAPE_ADDRESS);
 
     let account = svm
         .get_account(&tape_address)
@@ -227,20 +421,7 @@ fn test_pinocchio_initialize_metadata() {
     let (mut svm, payer, program_id) = setup_environment();
     initialize_program(&mut svm, &payer, program_id);
 
-    let mint_address = Pubkey::from(MINT_ADDRESS);
-    // Must match MPL_TOKEN_METADATA_ID in constant.rs
-    let metadata_program = Pubkey::new_from_array([
-        11, 112, 101, 177, 227, 209, 124, 69, 56, 157, 82, 127, 107, 4, 195, 205, 88, 184, 108,
-        115, 26, 160, 253, 181, 73, 182, 209, 188, 3, 248, 41, 70,
-    ]);
-    let (metadata_address, _) = Pubkey::find_program_address(
-        &[
-            b"metadata",
-            metadata_program.as_ref(),
-            mint_address.as_ref(),
-        ],
-        &metadata_program,
-    );
+    let metadata_address = Pubkey::from(protocol_pdas().metadata);
 
     let account = svm
         .get_account(&metadata_address)
@@ -250,6 +431,51 @@ fn test_pinocchio_initialize_metadata() {
     println!("Metadata account verified");
 }
 
+/// Test initialize uses token metadata passed in the instruction data
+#[test]
+fn test_pinocchio_initialize_custom_metadata() {
+    let (mut svm, payer, program_id) = setup_environment();
+
+    let mut ix = build_initialize_ix(payer.pubkey(), program_id);
+    let uri = b"https://example.com/fork.json";
+    ix.data
+        .extend_from_slice(&padded_array::<METADATA_NAME_LEN>(b"Fork Tape"));
+    ix.data
+        .extend_from_slice(&padded_array::<METADATA_SYMBOL_LEN>(b"FTAPE"));
+    ix.data
+        .extend_from_slice(&padded_array::<METADATA_URI_LEN>(uri));
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[&payer], blockhash);
+    let res = svm.send_transaction(tx);
+    assert!(res.is_ok(), "Initialization should succeed");
+
+    let metadata_address = Pubkey::from(protocol_pdas().metadata);
+
+    let account = svm
+        .get_account(&metadata_address)
+        .expect("Metadata account should exist");
+
+    let contains = |needle: &str| {
+        account
+            .data
+            .windows(needle.len())
+            .any(|window| window == needle.as_bytes())
+    };
+    assert!(contains("Fork Tape"), "Metadata should use the custom name");
+    assert!(contains("FTAPE"), "Metadata should use the custom symbol");
+    assert!(
+        contains("https://example.com/fork.json"),
+        "Metadata should use the custom uri"
+    );
+    assert!(
+        !contains(METADATA_URI),
+        "Metadata should not fall back to the default uri"
+    );
+
+    println!("Custom metadata verified");
+}
+
 /// Test all PDAs have correct addresses
 #[test]
 fn test_pinocchio_initialize_pda_addresses() {
@@ -309,10 +535,266 @@ fn test_pinocchio_initialize_compute_units() {
     println!("\nPINOCCHIO Initialize Compute Units: {}", cu_used);
     println!("  Max CU limit: 1,000,000");
     println!("  Usage: {:.2}%\n", (cu_used as f64 / 1_000_000.0) * 100.0);
+
+    // Initialize builds no segment tree (writers start from the precomputed
+    // SEGMENT_TREE_ZEROS_18), so it fits the default per-instruction budget
+    // without a compute budget instruction
+    const INITIALIZE_CU_BUDGET: u64 = 200_000;
+    assert!(
+        cu_used < INITIALIZE_CU_BUDGET,
+        "Initialize used {} CU, over the {} CU budget",
+        cu_used,
+        INITIALIZE_CU_BUDGET
+    );
+}
+
+/// Measure the CU saved by using precomputed singleton bumps instead of
+/// `find_program_address` during initialization
+#[test]
+fn test_pinocchio_initialize_known_bumps_compute_units() {
+    // Each rejected bump in find_program_address costs one create_program_address
+    // syscall (1500 CU), so a search ending at `bump` costs (256 - bump) * 1500.
+    const CREATE_PROGRAM_ADDRESS_CU: u64 = 1500;
+    let search_cost = |bump: u8| (256 - bump as u64) * CREATE_PROGRAM_ADDRESS_CU;
+
+    // Before: archive/epoch/block were searched twice (check + create),
+    // treasury twice (lookup + create), mint and metadata once.
+    let metadata_program = Pubkey::from(MPL_TOKEN_METADATA_ID);
+    let (_, metadata_bump) = Pubkey::find_program_address(
+        &[
+            b"metadata",
+            metadata_program.as_ref(),
+            Pubkey::from(MINT_ADDRESS).as_ref(),
+        ],
+        &metadata_program,
+    );
+    let saved = 2 * search_cost(ARCHIVE_BUMP)
+        + 2 * search_cost(EPOCH_BUMP)
+        + 2 * search_cost(BLOCK_BUMP)
+        + 2 * search_cost(TREASURY_BUMP)
+        + search_cost(MINT_BUMP)
+        + search_cost(metadata_bump);
+
+    let (mut svm, payer, program_id) = setup_environment();
+
+    let ix = build_initialize_ix(payer.pubkey(), program_id);
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[&payer], blockhash);
+
+    let res = svm.send_transaction(tx);
+    assert!(res.is_ok(), "Initialization should succeed");
+
+    let cu_used = res.unwrap().compute_units_consumed;
+
+    println!(
+        "\nPINOCCHIO Initialize Compute Units (known bumps): {}",
+        cu_used
+    );
+    println!("  Bump search avoided: ~{} CU", saved);
+    println!("  Equivalent with bump search: ~{} CU\n", cu_used + saved);
+
+    // Searching alone would have cost at least one syscall per lookup
+    assert!(saved >= 10 * CREATE_PROGRAM_ADDRESS_CU);
+    assert!(
+        cu_used < 200_000,
+        "Initialize should fit in the default compute budget"
+    );
 }
 
 // Helper functions
 
+/// Builds InitializeWithConfig from the Initialize accounts and the given genesis values
+fn build_initialize_with_config_ix(
+    signer: Pubkey,
+    program_id: Pubkey,
+    target_participation: u64,
+    mining_difficulty: u64,
+    packing_difficulty: u64,
+    reward_rate: u64,
+) -> Instruction {
+    let mut ix = build_initialize_ix(signer, program_id);
+    ix.data = vec![4]; // InitializeWithConfig discriminator
+    ix.data
+        .extend_from_slice(&target_participation.to_le_bytes());
+    ix.data.extend_from_slice(&mining_difficulty.to_le_bytes());
+    ix.data.extend_from_slice(&packing_difficulty.to_le_bytes());
+    ix.data.extend_from_slice(&reward_rate.to_le_bytes());
+    ix
+}
+
+/// Test initialize with config starts the epoch at the given genesis values
+#[test]
+fn test_pinocchio_initialize_with_config() {
+    let (mut svm, payer, program_id) = setup_environment();
+
+    let reward_rate = ONE_TAPE / 10;
+    let ix = build_initialize_with_config_ix(payer.pubkey(), program_id, 3, 2, 0, reward_rate);
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[&payer], blockhash);
+    let res = svm.send_transaction(tx);
+    assert!(res.is_ok(), "Initialization with config should succeed");
+
+    let account = svm.get_account(&Pubkey::from(EPOCH_ADDRESS)).unwrap();
+    let epoch: &Epoch = bytemuck::from_bytes(&account.data[8..]);
+    assert_eq!(epoch.number, 1);
+    assert_eq!(epoch.target_participation, 3);
+    assert_eq!(epoch.mining_difficulty, 2);
+    assert_eq!(epoch.packing_difficulty, 0);
+    assert_eq!(epoch.reward_rate, reward_rate);
+
+    verify_mint_account(&svm);
+}
+
+/// Test initialize with config rejects genesis values outside their bounds
+#[test]
+fn test_pinocchio_initialize_with_config_rejects_out_of_bounds() {
+    let (mut svm, payer, program_id) = setup_environment();
+
+    let ix = build_initialize_with_config_ix(
+        payer.pubkey(),
+        program_id,
+        MIN_PARTICIPATION_TARGET,
+        MAX_MINING_DIFFICULTY + 1,
+        MIN_PACKING_DIFFICULTY,
+        ONE_TAPE,
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[&payer], blockhash);
+    let result = svm.send_transaction(tx).map_err(|e| e.err);
+    assert_eq!(
+        result.map(|_| ()),
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::InvalidInstructionData
+        ))
+    );
+    assert!(svm.get_account(&Pubkey::from(EPOCH_ADDRESS)).is_none());
+}
+
+/// Test that epoch report logs the epoch fields without touching the account
+#[test]
+fn test_pinocchio_epoch_report() {
+    let (mut svm, payer, program_id) = setup_environment();
+    initialize_program(&mut svm, &payer, program_id);
+
+    let epoch_address = Pubkey::from(EPOCH_ADDRESS);
+    let before = svm.get_account(&epoch_address).unwrap();
+
+    let ix = Instruction {
+        program_id,
+        accounts: vec![AccountMeta::new_readonly(epoch_address, false)],
+        data: vec![3], // EpochReport discriminator
+    };
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[&payer], blockhash);
+    let meta = svm.send_transaction(tx).unwrap();
+
+    let events: Vec<Vec<u8>> = meta
+        .logs
+        .iter()
+        .filter_map(|log| log.strip_prefix("Program data: "))
+        .map(|data| STANDARD.decode(data).unwrap())
+        .collect();
+    assert_eq!(events.len(), 1);
+    let report = EpochReportEvent::try_from_bytes(&events[0]).unwrap();
+
+    let epoch: &Epoch = bytemuck::from_bytes(&before.data[8..]);
+    assert_eq!(report.number, epoch.number);
+    assert_eq!(report.progress, epoch.progress);
+    assert_eq!(report.mining_difficulty, MIN_MINING_DIFFICULTY);
+    assert_eq!(report.packing_difficulty, MIN_PACKING_DIFFICULTY);
+    assert_eq!(report.target_participation, MIN_PARTICIPATION_TARGET);
+    assert_eq!(report.reward_rate, epoch.reward_rate);
+    assert_eq!(report.epoch_rewards_distributed, 0);
+    assert_eq!(report.last_epoch_at, epoch.last_epoch_at);
+
+    assert_eq!(svm.get_account(&epoch_address).unwrap(), before);
+}
+
+/// Test that epoch report refuses a writable epoch account
+#[test]
+fn test_pinocchio_epoch_report_rejects_writable() {
+    let (mut svm, payer, program_id) = setup_environment();
+    initialize_program(&mut svm, &payer, program_id);
+
+    let ix = Instruction {
+        program_id,
+        accounts: vec![AccountMeta::new(Pubkey::from(EPOCH_ADDRESS), false)],
+        data: vec![3], // EpochReport discriminator
+    };
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[&payer], blockhash);
+    let result = svm.send_transaction(tx).map_err(|e| e.err);
+    assert_eq!(
+        result.map(|_| ()),
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::InvalidArgument
+        ))
+    );
+}
+
+/// Test that the treasury authority can override the epoch reward rate,
+/// clamped to the base rate, and clear the override again
+#[test]
+fn test_pinocchio_set_reward_rate_by_admin() {
+    let (mut svm, payer, program_id) = setup_environment();
+    initialize_program(&mut svm, &payer, program_id);
+
+    // The signer of initialize holds the treasury authority
+    let treasury = svm.get_account(&Pubkey::from(TREASURY_ADDRESS)).unwrap();
+    let authority: &[u8; 32] = bytemuck::from_bytes(&treasury.data[8..]);
+    assert_eq!(authority, &payer.pubkey().to_bytes());
+
+    let epoch_address = Pubkey::from(EPOCH_ADDRESS);
+    let genesis_rate = get_base_rate(1);
+
+    // Zero clears the override and leaves the current rate alone
+    for (requested, rate, reward_rate_override) in [
+        (1234, 1234, 1234),
+        (u64::MAX, genesis_rate, genesis_rate),
+        (0, genesis_rate, 0),
+    ] {
+        let ix = build_set_reward_rate_ix(payer.pubkey(), program_id, requested);
+        let blockhash = svm.latest_blockhash();
+        let tx =
+            Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[&payer], blockhash);
+        svm.send_transaction(tx).unwrap();
+        svm.expire_blockhash();
+
+        let account = svm.get_account(&epoch_address).unwrap();
+        let epoch: &Epoch = bytemuck::from_bytes(&account.data[8..]);
+        assert_eq!(epoch.reward_rate, rate);
+        assert_eq!(epoch.reward_rate_override, reward_rate_override);
+    }
+}
+
+/// Test that anyone but the treasury authority is refused, even when signing
+#[test]
+fn test_pinocchio_set_reward_rate_rejects_non_admin() {
+    let (mut svm, payer, program_id) = setup_environment();
+    initialize_program(&mut svm, &payer, program_id);
+
+    let epoch_address = Pubkey::from(EPOCH_ADDRESS);
+    let before = svm.get_account(&epoch_address).unwrap();
+
+    let other = Keypair::new();
+    svm.airdrop(&other.pubkey(), 1_000_000_000).unwrap();
+
+    let ix = build_set_reward_rate_ix(other.pubkey(), program_id, 1234);
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&other.pubkey()), &[&other], blockhash);
+    let result = svm.send_transaction(tx).map_err(|e| e.err);
+    assert_eq!(
+        result.map(|_| ()),
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::MissingRequiredSignature
+        ))
+    );
+    assert_eq!(svm.get_account(&epoch_address).unwrap(), before);
+}
+
 fn setup_environment() -> (LiteSVM, Keypair, Pubkey) {
     let mut svm = LiteSVM::new();
 
@@ -338,45 +820,46 @@ fn setup_environment() -> (LiteSVM, Keypair, Pubkey) {
     (svm, payer, program_id)
 }
 
+/// Stores an epoch made outside initialize, as when a local validator clones
+/// it from another cluster; a failed initialize never leaves one behind.
+/// Returns the account data.
+fn set_existing_epoch(svm: &mut LiteSVM, program_id: Pubkey) -> Vec<u8> {
+    let mut data = vec![0u8; 8 + core::mem::size_of::<Epoch>()];
+    data[0] = AccountType::Epoch as u8;
+    {
+        let epoch: &mut Epoch = bytemuck::from_bytes_mut(&mut data[8..]);
+        epoch.number = 1;
+        epoch.target_participation = MIN_PARTICIPATION_TARGET;
+        epoch.mining_difficulty = MIN_MINING_DIFFICULTY;
+    }
+    let lamports = svm.minimum_balance_for_rent_exemption(data.len());
+    svm.set_account(
+        Pubkey::from(EPOCH_ADDRESS),
+        solana_sdk::account::Account {
+            lamports,
+            data: data.clone(),
+            owner: program_id,
+            executable: false,
+            rent_epoch: 0,
+        }
+        .into(),
+    )
+    .unwrap();
+    data
+}
+
 fn load_metadata_program(svm: &mut LiteSVM) {
     // Load the metadata program from elfs directory
     let metadata_bytes = std::fs::read("tests/elfs/metadata.so")
         .expect("Failed to read metadata program. Run: solana program dump --url mainnet-beta metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s tests/elfs/metadata.so");
 
-    // Metadata program ID: metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s
-    // Must match the ID in pinocchio-tapedrive/program/src/state/constant.rs
-    let metadata_program_id = Pubkey::new_from_array([
-        11, 112, 101, 177, 227, 209, 124, 69, 56, 157, 82, 127, 107, 4, 195, 205, 88, 184, 108,
-        115, 26, 160, 253, 181, 73, 182, 209, 188, 3, 248, 41, 70,
-    ]);
+    let metadata_program_id = Pubkey::from(MPL_TOKEN_METADATA_ID);
     svm.add_program(metadata_program_id, &metadata_bytes);
 }
 
 fn build_initialize_ix(signer: Pubkey, program_id: Pubkey) -> Instruction {
-    // Use constant PDAs
-    let archive_pda = Pubkey::from(ARCHIVE_ADDRESS);
-    let epoch_pda = Pubkey::from(EPOCH_ADDRESS);
-    let block_pda = Pubkey::from(BLOCK_ADDRESS);
-    let mint_pda = Pubkey::from(MINT_ADDRESS);
-    let treasury_pda = Pubkey::from(TREASURY_ADDRESS);
-    let treasury_ata_pda = Pubkey::from(TREASURY_ATA);
-
-    // Derive metadata PDA (must match MPL_TOKEN_METADATA_ID in constant.rs)
-    let metadata_program = Pubkey::new_from_array([
-        11, 112, 101, 177, 227, 209, 124, 69, 56, 157, 82, 127, 107, 4, 195, 205, 88, 184, 108,
-        115, 26, 160, 253, 181, 73, 182, 209, 188, 3, 248, 41, 70,
-    ]);
-    let (metadata_pda, _) = Pubkey::find_program_address(
-        &[b"metadata", metadata_program.as_ref(), mint_pda.as_ref()],
-        &metadata_program,
-    );
-
-    // Derive tape and writer PDAs
-    let name = to_name("genesis");
-    let (tape_pda, _) =
-        Pubkey::find_program_address(&[b"tape", signer.as_ref(), &name], &program_id);
-    let (writer_pda, _) =
-        Pubkey::find_program_address(&[b"writer", tape_pda.as_ref()], &program_id);
+    let pdas = protocol_pdas();
+    let metadata_program = Pubkey::from(MPL_TOKEN_METADATA_ID);
 
     // Token program IDs
     let spl_token_id = Pubkey::new_from_array([
@@ -392,15 +875,15 @@ fn build_initialize_ix(signer: Pubkey, program_id: Pubkey) -> Instruction {
         program_id,
         accounts: vec![
             AccountMeta::new(signer, true),
-            AccountMeta::new(archive_pda, false),
-            AccountMeta::new(epoch_pda, false),
-            AccountMeta::new(block_pda, false),
-            AccountMeta::new(metadata_pda, false),
-            AccountMeta::new(mint_pda, false),
-            AccountMeta::new(treasury_pda, false),
-            AccountMeta::new(treasury_ata_pda, false),
-            AccountMeta::new(tape_pda, false),
-            AccountMeta::new(writer_pda, false),
+            AccountMeta::new(pdas.archive.into(), false),
+            AccountMeta::new(pdas.epoch.into(), false),
+            AccountMeta::new(pdas.block.into(), false),
+            AccountMeta::new(pdas.metadata.into(), false),
+            AccountMeta::new(pdas.mint.into(), false),
+            AccountMeta::new(pdas.treasury.into(), false),
+            AccountMeta::new(pdas.treasury_ata.into(), false),
+            AccountMeta::new(pdas.genesis_tape.into(), false),
+            AccountMeta::new(pdas.genesis_writer.into(), false),
             AccountMeta::new_readonly(program_id, false),
             AccountMeta::new_readonly(system_program::ID, false),
             AccountMeta::new_readonly(spl_token_id, false),
@@ -413,6 +896,21 @@ fn build_initialize_ix(signer: Pubkey, program_id: Pubkey) -> Instruction {
     }
 }
 
+fn build_set_reward_rate_ix(signer: Pubkey, program_id: Pubkey, reward_rate: u64) -> Instruction {
+    let mut data = vec![5]; // SetRewardRate discriminator
+    data.extend_from_slice(&reward_rate.to_le_bytes());
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new_readonly(signer, true),
+            AccountMeta::new(Pubkey::from(EPOCH_ADDRESS), false),
+            AccountMeta::new_readonly(Pubkey::from(TREASURY_ADDRESS), false),
+        ],
+        data,
+    }
+}
+
 fn initialize_program(svm: &mut LiteSVM, payer: &Keypair, program_id: Pubkey) {
     let ix = build_initialize_ix(payer.pubkey(), program_id);
     let blockhash = svm.latest_blockhash();
@@ -471,20 +969,7 @@ fn verify_mint_account(svm: &LiteSVM) {
 }
 
 fn verify_metadata_account(svm: &LiteSVM) {
-    // Derive metadata PDA: seeds = ["metadata", metadata_program_id, mint_address]
-    let mint_address = Pubkey::from(MINT_ADDRESS);
-    let metadata_program = Pubkey::new_from_array([
-        11, 112, 132, 119, 193, 192, 49, 38, 73, 174, 55, 16, 196, 233, 99, 165, 38, 132, 214, 37,
-        185, 184, 179, 237, 235, 186, 165, 87, 32, 172, 79, 238,
-    ]);
-    let (metadata_address, _) = Pubkey::find_program_address(
-        &[
-            b"metadata",
-            metadata_program.as_ref(),
-            mint_address.as_ref(),
-        ],
-        &metadata_program,
-    );
+    let metadata_address = Pubkey::from(protocol_pdas().metadata);
     let account = svm
         .get_account(&metadata_address)
         .expect("Metadata account should exist");
@@ -499,14 +984,8 @@ fn verify_treasury_ata(svm: &LiteSVM) {
     assert!(!account.data.is_empty(), "Treasury ATA should have data");
 }
 
-fn verify_genesis_tape(svm: &LiteSVM, payer: &Keypair) {
-    let genesis_name = "genesis";
-    let name_bytes = to_name(genesis_name);
-    let program_id = Pubkey::from(tape_api::ID);
-    let (tape_address, _) = Pubkey::find_program_address(
-        &[b"tape", payer.pubkey().as_ref(), &name_bytes],
-        &program_id,
-    );
+fn verify_genesis_tape(svm: &LiteSVM) {
+    let tape_address = Pubkey::from(GENESIS_TAPE_ADDRESS);
     let account = svm
         .get_account(&tape_address)
         .expect("Genesis tape should exist");
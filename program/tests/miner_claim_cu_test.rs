@@ -0,0 +1,240 @@
+#![cfg(test)]
+
+use litesvm::LiteSVM;
+use solana_program::{program_option::COption, program_pack::Pack};
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::{Transaction, TransactionError},
+};
+use tape_api::{
+    consts::{MINT_ADDRESS, TOKEN_DECIMALS, TREASURY_ADDRESS, TREASURY_ATA},
+    error::TapeError,
+    state::Miner,
+};
+
+const TREASURY_SUPPLY: u64 = 1_000_000_000;
+
+fn setup() -> (LiteSVM, Keypair, Pubkey) {
+    let mut svm = LiteSVM::new();
+
+    let program_id = Pubkey::from(tape_api::ID);
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .expect("Failed to load program");
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+    // The TAPE mint and a funded treasury ATA, as initialize leaves them
+    let mint = spl_token::state::Mint {
+        mint_authority: COption::Some(Pubkey::from(TREASURY_ADDRESS)),
+        supply: TREASURY_SUPPLY,
+        decimals: TOKEN_DECIMALS,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    };
+    set_packed(&mut svm, Pubkey::from(MINT_ADDRESS), spl_token::ID, mint);
+    set_token_account(
+        &mut svm,
+        Pubkey::from(TREASURY_ATA),
+        Pubkey::from(TREASURY_ADDRESS),
+        TREASURY_SUPPLY,
+    );
+
+    (svm, payer, program_id)
+}
+
+fn set_packed<T: Pack>(svm: &mut LiteSVM, address: Pubkey, owner: Pubkey, state: T) {
+    let mut data = vec![0u8; T::LEN];
+    T::pack(state, &mut data).unwrap();
+
+    let account = Account {
+        lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+        data,
+        owner,
+        executable: false,
+        rent_epoch: 0,
+    };
+    svm.set_account(address, account.into()).unwrap();
+}
+
+fn set_token_account(svm: &mut LiteSVM, address: Pubkey, owner: Pubkey, amount: u64) {
+    let token_account = spl_token::state::Account {
+        mint: Pubkey::from(MINT_ADDRESS),
+        owner,
+        amount,
+        state: spl_token::state::AccountState::Initialized,
+        ..Default::default()
+    };
+    set_packed(svm, address, spl_token::ID, token_account);
+}
+
+fn token_balance(svm: &LiteSVM, address: &Pubkey) -> u64 {
+    let account = svm.get_account(address).unwrap();
+    spl_token::state::Account::unpack(&account.data)
+        .unwrap()
+        .amount
+}
+
+/// Creates a miner account for `authority` with rewards waiting to be claimed
+fn set_miner(svm: &mut LiteSVM, owner: Pubkey, authority: Pubkey, unclaimed: u64) -> Pubkey {
+    let miner_address = Pubkey::new_unique();
+    let mut data = vec![0u8; core::mem::size_of::<Miner>()];
+    let miner = Miner::unpack_mut(&mut data).unwrap();
+    miner.authority = authority.to_bytes();
+    miner.unclaimed_rewards = unclaimed;
+
+    let account = Account {
+        lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+        data,
+        owner,
+        executable: false,
+        rent_epoch: 0,
+    };
+    svm.set_account(miner_address, account.into()).unwrap();
+    miner_address
+}
+
+fn unclaimed_rewards(svm: &LiteSVM, miner_address: &Pubkey) -> u64 {
+    let account = svm.get_account(miner_address).unwrap();
+    Miner::unpack(&account.data).unwrap().unclaimed_rewards
+}
+
+/// Helper to send a claim instruction signed by `signer`
+fn send_claim(
+    svm: &mut LiteSVM,
+    signer: &Keypair,
+    program_id: Pubkey,
+    beneficiary: Pubkey,
+    miner_address: Pubkey,
+    amount: u64,
+) -> Result<u64, TransactionError> {
+    let mut data = vec![0x23]; // Claim discriminator
+    data.extend_from_slice(&amount.to_le_bytes());
+
+    let ix = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(signer.pubkey(), true),
+            AccountMeta::new(beneficiary, false),
+            AccountMeta::new(miner_address, false),
+            AccountMeta::new_readonly(Pubkey::from(TREASURY_ADDRESS), false),
+            AccountMeta::new(Pubkey::from(TREASURY_ATA), false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+        ],
+        data,
+    };
+
+    let blockhash = svm.latest_blockhash();
+    let tx =
+        Transaction::new_signed_with_payer(&[ix], Some(&signer.pubkey()), &[signer], blockhash);
+    svm.send_transaction(tx)
+        .map(|meta| meta.compute_units_consumed)
+        .map_err(|e| e.err)
+}
+
+#[test]
+fn test_pinocchio_miner_claim_cu_measurement() {
+    println!("\nPINOCCHIO MINER CLAIM - CU MEASUREMENT TEST");
+
+    let (mut svm, payer, program_id) = setup();
+    let miner_address = set_miner(&mut svm, program_id, payer.pubkey(), 5_000);
+    let beneficiary = Pubkey::new_unique();
+    set_token_account(&mut svm, beneficiary, payer.pubkey(), 0);
+
+    // A partial claim pays exactly the requested amount
+    let cus = send_claim(
+        &mut svm,
+        &payer,
+        program_id,
+        beneficiary,
+        miner_address,
+        2_000,
+    )
+    .unwrap();
+    println!("Compute Units: {}", cus);
+
+    assert_eq!(token_balance(&svm, &beneficiary), 2_000);
+    assert_eq!(
+        token_balance(&svm, &Pubkey::from(TREASURY_ATA)),
+        TREASURY_SUPPLY - 2_000
+    );
+    assert_eq!(unclaimed_rewards(&svm, &miner_address), 3_000);
+
+    // A zero amount claims whatever is left
+    svm.expire_blockhash();
+    send_claim(&mut svm, &payer, program_id, beneficiary, miner_address, 0).unwrap();
+
+    assert_eq!(token_balance(&svm, &beneficiary), 5_000);
+    assert_eq!(unclaimed_rewards(&svm, &miner_address), 0);
+
+    println!("\nTEST PASSED - CUs: {}", cus);
+}
+
+#[test]
+fn test_pinocchio_miner_claim_rejects_overclaim() {
+    let (mut svm, payer, program_id) = setup();
+    let miner_address = set_miner(&mut svm, program_id, payer.pubkey(), 5_000);
+    let beneficiary = Pubkey::new_unique();
+    set_token_account(&mut svm, beneficiary, payer.pubkey(), 0);
+
+    let result = send_claim(
+        &mut svm,
+        &payer,
+        program_id,
+        beneficiary,
+        miner_address,
+        5_001,
+    );
+    assert_eq!(
+        result,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TapeError::ClaimTooLarge as u32)
+        ))
+    );
+    assert_eq!(token_balance(&svm, &beneficiary), 0);
+    assert_eq!(unclaimed_rewards(&svm, &miner_address), 5_000);
+}
+
+#[test]
+fn test_pinocchio_miner_claim_rejects_other_authority() {
+    let (mut svm, payer, program_id) = setup();
+    let miner_address = set_miner(&mut svm, program_id, payer.pubkey(), 5_000);
+
+    let thief = Keypair::new();
+    svm.airdrop(&thief.pubkey(), 1_000_000_000).unwrap();
+    let beneficiary = Pubkey::new_unique();
+    set_token_account(&mut svm, beneficiary, thief.pubkey(), 0);
+
+    let result = send_claim(
+        &mut svm,
+        &thief,
+        program_id,
+        beneficiary,
+        miner_address,
+        5_000,
+    );
+    assert_eq!(
+        result,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::MissingRequiredSignature
+        ))
+    );
+
+    // A miner-shaped account the program does not own has earned nothing
+    let forged = set_miner(&mut svm, Pubkey::new_unique(), thief.pubkey(), 5_000);
+    let result = send_claim(&mut svm, &thief, program_id, beneficiary, forged, 5_000);
+    assert_eq!(
+        result,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::IncorrectProgramId
+        ))
+    );
+    assert_eq!(token_balance(&svm, &beneficiary), 0);
+}
@@ -0,0 +1,731 @@
+#![cfg(test)]
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use litesvm::LiteSVM;
+use pinnochio_tape_program::{
+    state::{Archive, Block, DataLen, Epoch, Mine, Miner, PoA, PoW, Spool, Tape, TapeState},
+    utils::AccountDiscriminator,
+};
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program, sysvar,
+    transaction::{Transaction, TransactionError},
+};
+use tape_api::{
+    consts::{
+        ARCHIVE_ADDRESS, MINER, MINER_DEPOSIT, MINER_SLASH_AMOUNT, NAME_LEN, SEGMENT_PROOF_LEN,
+        TAPE, TAPE_PROOF_LEN, TREASURY_ADDRESS, WRITER,
+    },
+    error::TapeError,
+    event::SlashEvent,
+    instruction::tape::build_subsidize_ix_data,
+    state::Writer,
+    types::{SegmentTree, TapeTree},
+    utils::{compute_commitment, to_name},
+};
+use tape_utils::leaf::Leaf;
+
+/// Accounts used by the mine instruction
+struct MineAccounts {
+    epoch: Pubkey,
+    block: Pubkey,
+    miner: Pubkey,
+    tape: Pubkey,
+    archive: Pubkey,
+    spool: Pubkey,
+    treasury: Pubkey,
+}
+
+fn setup_svm() -> (LiteSVM, Keypair, Pubkey) {
+    let mut svm = LiteSVM::new();
+
+    let program_id: Pubkey = "7wApqqrfJo2dAGAKVgheccaVEgeDoqVKogtJSTbFRWn2"
+        .parse()
+        .expect("Invalid program ID");
+
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .expect("Failed to load Pinocchio tape program");
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000)
+        .expect("Failed to airdrop to payer");
+
+    (svm, payer, program_id)
+}
+
+/// Creates a rent-exempt account owned by the program with the given data
+fn set_program_account(svm: &mut LiteSVM, program_id: Pubkey, data: Vec<u8>) -> Pubkey {
+    set_program_account_at(svm, Pubkey::new_unique(), program_id, data)
+}
+
+/// Same as `set_program_account`, at a fixed address
+fn set_program_account_at(
+    svm: &mut LiteSVM,
+    address: Pubkey,
+    program_id: Pubkey,
+    data: Vec<u8>,
+) -> Pubkey {
+    let lamports = svm.minimum_balance_for_rent_exemption(data.len());
+    svm.set_account(
+        address,
+        Account {
+            lamports,
+            data,
+            owner: program_id,
+            executable: false,
+            rent_epoch: 0,
+        }
+        .into(),
+    )
+    .unwrap();
+    address
+}
+
+/// Mutates a program account in place as `T`
+fn update_account<T: bytemuck::Pod>(svm: &mut LiteSVM, address: &Pubkey, f: impl FnOnce(&mut T)) {
+    let mut account = svm.get_account(address).unwrap();
+    let mut value: T = bytemuck::pod_read_unaligned(&account.data);
+    f(&mut value);
+    account.data.copy_from_slice(bytemuck::bytes_of(&value));
+    svm.set_account(*address, account.into()).unwrap();
+}
+
+/// Creates a zeroed `T` laid out as initialize does, behind its type byte
+fn set_discriminated_account<T: AccountDiscriminator>(
+    svm: &mut LiteSVM,
+    program_id: Pubkey,
+) -> Pubkey {
+    let mut data = vec![0; 8 + core::mem::size_of::<T>()];
+    data[0] = T::discriminator();
+    set_program_account(svm, program_id, data)
+}
+
+/// Same as `update_account`, for accounts created by `set_discriminated_account`
+fn update_discriminated_account<T: bytemuck::Pod>(
+    svm: &mut LiteSVM,
+    address: &Pubkey,
+    f: impl FnOnce(&mut T),
+) {
+    let mut account = svm.get_account(address).unwrap();
+    let mut value: T = bytemuck::pod_read_unaligned(&account.data[8..]);
+    f(&mut value);
+    account.data[8..].copy_from_slice(bytemuck::bytes_of(&value));
+    svm.set_account(*address, account.into()).unwrap();
+}
+
+/// Address of the unnamed miner of `payer`
+fn miner_address(payer: &Keypair, program_id: Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[MINER, payer.pubkey().as_ref(), &[0; NAME_LEN]],
+        &program_id,
+    )
+    .0
+}
+
+/// Creates the epoch, block, archive, miner, tape and spool accounts needed
+/// to reach the checks in `process_mine`
+fn setup_mine_accounts(svm: &mut LiteSVM, payer: &Keypair, program_id: Pubkey) -> MineAccounts {
+    let epoch = set_discriminated_account::<Epoch>(svm, program_id);
+    let block = set_discriminated_account::<Block>(svm, program_id);
+    let archive = set_discriminated_account::<Archive>(svm, program_id);
+    // The miner has to sit at its PDA for the unnamed miner of `payer`
+    let (miner_address, miner_bump) = Pubkey::find_program_address(
+        &[MINER, payer.pubkey().as_ref(), &[0; NAME_LEN]],
+        &program_id,
+    );
+    let miner = set_program_account_at(svm, miner_address, program_id, vec![0; Miner::LEN]);
+    // Likewise the tape sits at its PDA, for a tape `payer` created unnamed
+    let (tape_address, tape_bump) = Pubkey::find_program_address(
+        &[TAPE, payer.pubkey().as_ref(), &[0; NAME_LEN]],
+        &program_id,
+    );
+    let tape = set_program_account_at(svm, tape_address, program_id, vec![0; Tape::LEN]);
+    let spool = set_program_account(svm, program_id, vec![0; Spool::LEN]);
+    let treasury =
+        set_program_account_at(svm, Pubkey::from(TREASURY_ADDRESS), program_id, vec![0; 8]);
+
+    let authority = payer.pubkey().to_bytes();
+    update_account::<Miner>(svm, &miner, |miner| {
+        miner.authority = authority;
+        miner.bump = miner_bump as u64;
+    });
+    update_account::<Spool>(svm, &spool, |spool| spool.authority = authority);
+    update_account::<Tape>(svm, &tape, |tape| {
+        tape.creator = authority;
+        tape.bump = tape_bump as u64;
+        tape.number = 1;
+        tape.state = TapeState::Finalized as u64;
+        tape.total_segments = 1;
+    });
+
+    MineAccounts {
+        epoch,
+        block,
+        miner,
+        tape,
+        archive,
+        spool,
+        treasury,
+    }
+}
+
+/// Builds mine instruction data with zeroed solutions and the given spool membership proof
+fn mine_data(tape_value: [u8; 32], tape_proof: &[[u8; 32]; TAPE_PROOF_LEN]) -> Vec<u8> {
+    let mut data = vec![0x22]; // Mine discriminator
+    data.extend_from_slice(&vec![0u8; PoW::LEN + PoA::LEN + 32]);
+    data.extend_from_slice(&tape_value);
+    data.extend_from_slice(tape_proof.as_flattened());
+    assert_eq!(data.len(), 1 + Mine::LEN);
+    data
+}
+
+fn build_mine_ix(
+    program_id: Pubkey,
+    signer: Pubkey,
+    accounts: &MineAccounts,
+    data: Vec<u8>,
+) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(accounts.epoch, false),
+            AccountMeta::new(accounts.block, false),
+            AccountMeta::new(accounts.miner, false),
+            AccountMeta::new(accounts.tape, false),
+            AccountMeta::new(accounts.archive, false),
+            AccountMeta::new_readonly(sysvar::slot_hashes::ID, false),
+            AccountMeta::new_readonly(accounts.spool, false),
+            AccountMeta::new(accounts.treasury, false),
+        ],
+        data,
+    }
+}
+
+fn send_mine(
+    svm: &mut LiteSVM,
+    payer: &Keypair,
+    program_id: Pubkey,
+    accounts: &MineAccounts,
+    data: Vec<u8>,
+) -> Result<u64, TransactionError> {
+    let ix = build_mine_ix(program_id, payer.pubkey(), accounts, data);
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[payer], blockhash);
+    svm.send_transaction(tx)
+        .map(|meta| meta.compute_units_consumed)
+        .map_err(|e| e.err)
+}
+
+/// Commits `value` for the miner through the spool_commit instruction
+fn commit_value(
+    svm: &mut LiteSVM,
+    payer: &Keypair,
+    program_id: Pubkey,
+    accounts: &MineAccounts,
+    value: [u8; 32],
+    proof: &[[u8; 32]; SEGMENT_PROOF_LEN],
+) {
+    let mut data = vec![0x44]; // SpoolCommit discriminator
+    data.extend_from_slice(&value);
+    data.extend_from_slice(proof.as_flattened());
+
+    let ix = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer.pubkey(), true),
+            AccountMeta::new(accounts.miner, false),
+            AccountMeta::new_readonly(accounts.spool, false),
+        ],
+        data,
+    };
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[payer], blockhash);
+    svm.send_transaction(tx).expect("Commit failed");
+}
+
+/// Packs tape 1 into the spool, commits a value from it and returns mine
+/// data that passes every check up to the (unsolved) zero solution
+fn commit_mine(
+    svm: &mut LiteSVM,
+    payer: &Keypair,
+    program_id: Pubkey,
+    accounts: &MineAccounts,
+) -> Vec<u8> {
+    // Pack tape 1 into the spool and point it at segments holding the value
+    let tape_value = [7u8; 32];
+    let tape_leaf = Leaf::new(&[1u64.to_le_bytes().as_ref(), &tape_value]);
+    let mut tapes = TapeTree::new(&[accounts.spool.as_ref()]);
+    tapes.try_add_leaf(tape_leaf).unwrap();
+    let tape_proof = tapes
+        .get_proof_no_std::<1>(&[tape_leaf], 0)
+        .unwrap()
+        .map(|h| h.to_bytes());
+
+    let value = [42u8; 32];
+    let segment_leaf = Leaf::from(value);
+    let mut segments = SegmentTree::new(&[b"segments"]);
+    segments.try_add_leaf(segment_leaf).unwrap();
+    let segment_proof = segments
+        .get_proof_no_std::<1>(&[segment_leaf], 0)
+        .unwrap()
+        .map(|h| h.to_bytes());
+
+    update_account::<Spool>(svm, &accounts.spool, |spool| {
+        spool.state = tapes;
+        spool.contains = segments.get_root().to_bytes();
+    });
+    // Avoid the duplicate-submission check for block 0
+    update_account::<Miner>(svm, &accounts.miner, |miner| miner.last_proof_block = 1);
+
+    commit_value(svm, payer, program_id, accounts, value, &segment_proof);
+
+    let mut data = mine_data(tape_value, &tape_proof);
+    let offset = 1 + PoW::LEN + PoA::LEN;
+    data[offset..offset + 32].copy_from_slice(&value);
+    data
+}
+
+/// Decodes the `Program data:` entries emitted via sol_log_data
+fn program_data(logs: &[String]) -> Vec<Vec<u8>> {
+    logs.iter()
+        .filter_map(|log| log.strip_prefix("Program data: "))
+        .map(|data| STANDARD.decode(data).expect("Invalid base64 event data"))
+        .collect()
+}
+
+fn custom_error(error: TapeError) -> TransactionError {
+    TransactionError::InstructionError(0, InstructionError::Custom(error as u32))
+}
+
+#[test]
+fn test_pinocchio_mine_rejects_balance_above_lamports() {
+    println!("\nPINOCCHIO MINE - BALANCE MISMATCH");
+
+    let (mut svm, payer, program_id) = setup_svm();
+    let accounts = setup_mine_accounts(&mut svm, &payer, program_id);
+
+    // The tape only holds its rent-exempt minimum, so any balance is unbacked
+    update_account::<Tape>(&mut svm, &accounts.tape, |tape| tape.balance = 1_000_000);
+
+    let data = mine_data([0; 32], &[[0; 32]; TAPE_PROOF_LEN]);
+    let result = send_mine(&mut svm, &payer, program_id, &accounts, data);
+    assert_eq!(result, Err(custom_error(TapeError::BalanceMismatch)));
+
+    println!("\nPINOCCHIO MINE - BALANCE MISMATCH PASSED");
+}
+
+#[test]
+fn test_pinocchio_mine_rejects_tape_missing_from_spool() {
+    println!("\nPINOCCHIO MINE - TAPE MISSING FROM SPOOL");
+
+    let (mut svm, payer, program_id) = setup_svm();
+    let accounts = setup_mine_accounts(&mut svm, &payer, program_id);
+
+    // The spool only holds tape 2, while the recall lands on tape 1
+    let packed_value = [7u8; 32];
+    let packed_leaf = Leaf::new(&[2u64.to_le_bytes().as_ref(), &packed_value]);
+    let mut tapes = TapeTree::new(&[accounts.spool.as_ref()]);
+    tapes.try_add_leaf(packed_leaf).unwrap();
+    let tape_proof = tapes
+        .get_proof_no_std::<1>(&[packed_leaf], 0)
+        .unwrap()
+        .map(|h| h.to_bytes());
+
+    let spool_root = tapes.get_root().to_bytes();
+    update_account::<Spool>(&mut svm, &accounts.spool, |spool| spool.state = tapes);
+    update_account::<Miner>(&mut svm, &accounts.miner, |miner| {
+        miner.commitment = compute_commitment(&[0; 32], &[0; 32], &spool_root);
+        // Avoid the duplicate-submission check for block 0
+        miner.last_proof_block = 1;
+    });
+
+    // Proving with tape 2's leaf does not place tape 1 in the spool
+    let data = mine_data(packed_value, &tape_proof);
+    let result = send_mine(&mut svm, &payer, program_id, &accounts, data);
+    assert_eq!(result, Err(custom_error(TapeError::SpoolMissingTape)));
+
+    println!("\nPINOCCHIO MINE - TAPE MISSING FROM SPOOL PASSED");
+}
+
+#[test]
+fn test_pinocchio_mine_rejects_empty_tape() {
+    println!("\nPINOCCHIO MINE - EMPTY TAPE");
+
+    let (mut svm, payer, program_id) = setup_svm();
+    let accounts = setup_mine_accounts(&mut svm, &payer, program_id);
+
+    // A finalized tape that (wrongly) holds no segments
+    update_account::<Tape>(&mut svm, &accounts.tape, |tape| tape.total_segments = 0);
+    update_account::<Miner>(&mut svm, &accounts.miner, |miner| {
+        // The untouched spool has a zero root
+        miner.commitment = compute_commitment(&[0; 32], &[0; 32], &[0; 32]);
+        // Avoid the duplicate-submission check for block 0
+        miner.last_proof_block = 1;
+    });
+
+    let data = mine_data([0; 32], &[[0; 32]; TAPE_PROOF_LEN]);
+    let result = send_mine(&mut svm, &payer, program_id, &accounts, data);
+    assert_eq!(result, Err(custom_error(TapeError::EmptyTape)));
+
+    println!("\nPINOCCHIO MINE - EMPTY TAPE PASSED");
+}
+
+#[test]
+fn test_pinocchio_mine_rejects_reused_commitment() {
+    println!("\nPINOCCHIO MINE - REUSED COMMITMENT");
+
+    let (mut svm, payer, program_id) = setup_svm();
+    let accounts = setup_mine_accounts(&mut svm, &payer, program_id);
+
+    let data = commit_mine(&mut svm, &payer, program_id, &accounts);
+
+    // The commitment is accepted; only the (unsolved) zero solution fails,
+    // which slashes the deposit instead of failing the instruction
+    let result = send_mine(&mut svm, &payer, program_id, &accounts, data.clone());
+    assert!(result.is_ok(), "Slash failed: {:?}", result.err());
+
+    // A real PoW can't be solved here, so apply what a successful proof in
+    // block 0 leaves behind and move on to the next block
+    update_account::<Miner>(&mut svm, &accounts.miner, |miner| {
+        miner.commitment = [0; 32];
+        miner.last_proof_block = 0;
+    });
+    update_discriminated_account::<Block>(&mut svm, &accounts.block, |block| block.number = 1);
+
+    // Reusing the same value without a new commit is rejected
+    svm.expire_blockhash();
+    let result = send_mine(&mut svm, &payer, program_id, &accounts, data);
+    assert_eq!(result, Err(custom_error(TapeError::CommitmentMismatch)));
+
+    println!("\nPINOCCHIO MINE - REUSED COMMITMENT PASSED");
+}
+
+#[test]
+fn test_pinocchio_mine_rejects_wrong_length_proof() {
+    let (mut svm, payer, program_id) = setup_svm();
+    let accounts = setup_mine_accounts(&mut svm, &payer, program_id);
+
+    let mut miner_account = svm.get_account(&accounts.miner).unwrap();
+    miner_account.lamports += MINER_DEPOSIT;
+    svm.set_account(accounts.miner, miner_account.into())
+        .unwrap();
+    update_account::<Miner>(&mut svm, &accounts.miner, |miner| {
+        miner.deposit = MINER_DEPOSIT
+    });
+
+    let data = commit_mine(&mut svm, &payer, program_id, &accounts);
+
+    // One proof hash short, and one too many, both fail cleanly
+    let short = data[..data.len() - 32].to_vec();
+    let result = send_mine(&mut svm, &payer, program_id, &accounts, short);
+    assert_eq!(
+        result,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::InvalidInstructionData
+        ))
+    );
+
+    let mut long = data;
+    long.extend_from_slice(&[0; 32]);
+    let result = send_mine(&mut svm, &payer, program_id, &accounts, long);
+    assert_eq!(
+        result,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::InvalidInstructionData
+        ))
+    );
+
+    // A malformed proof is not a wrong answer, so nothing is slashed
+    let miner_account = svm.get_account(&accounts.miner).unwrap();
+    let miner: Miner = bytemuck::pod_read_unaligned(&miner_account.data);
+    assert_eq!(miner.deposit, MINER_DEPOSIT);
+    assert_eq!(miner.forfeited, 0);
+}
+
+#[test]
+fn test_pinocchio_mine_invalid_solution_slashes_deposit() {
+    let (mut svm, payer, program_id) = setup_svm();
+    let accounts = setup_mine_accounts(&mut svm, &payer, program_id);
+
+    // Hold the deposit a registered miner would
+    let mut miner_account = svm.get_account(&accounts.miner).unwrap();
+    miner_account.lamports += MINER_DEPOSIT;
+    svm.set_account(accounts.miner, miner_account.into())
+        .unwrap();
+    update_account::<Miner>(&mut svm, &accounts.miner, |miner| {
+        miner.deposit = MINER_DEPOSIT
+    });
+
+    let data = commit_mine(&mut svm, &payer, program_id, &accounts);
+    let miner_before: Miner =
+        bytemuck::pod_read_unaligned(&svm.get_account(&accounts.miner).unwrap().data);
+    let miner_lamports = svm.get_account(&accounts.miner).unwrap().lamports;
+    let treasury_before = svm.get_account(&accounts.treasury).unwrap().lamports;
+
+    let ix = build_mine_ix(program_id, payer.pubkey(), &accounts, data);
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[&payer], blockhash);
+    let meta = svm.send_transaction(tx).expect("Slash failed");
+
+    // The slash is reported as its own event, never as a mine event
+    let event = SlashEvent {
+        error: TapeError::SolutionSlashed as u64,
+        amount: MINER_SLASH_AMOUNT,
+        deposit: MINER_DEPOSIT - MINER_SLASH_AMOUNT,
+        miner: accounts.miner.to_bytes(),
+    };
+    let events = program_data(&meta.logs);
+    assert_eq!(events, vec![event.to_bytes().to_vec()]);
+
+    // The treasury is paid right away, out of the miner account
+    let treasury_after = svm.get_account(&accounts.treasury).unwrap().lamports;
+    assert_eq!(treasury_after - treasury_before, MINER_SLASH_AMOUNT);
+    let miner_account = svm.get_account(&accounts.miner).unwrap();
+    assert_eq!(miner_account.lamports, miner_lamports - MINER_SLASH_AMOUNT);
+
+    let miner: Miner = bytemuck::pod_read_unaligned(&miner_account.data);
+    assert_eq!(miner.deposit, MINER_DEPOSIT - MINER_SLASH_AMOUNT);
+    assert_eq!(miner.forfeited, MINER_SLASH_AMOUNT);
+    assert_eq!(miner.commitment, [0; 32]);
+
+    // Nothing was earned or counted for the invalid proof
+    assert_eq!(miner.total_proofs, miner_before.total_proofs);
+    assert_eq!(miner.total_rewards, miner_before.total_rewards);
+    assert_eq!(miner.unclaimed_rewards, miner_before.unclaimed_rewards);
+    assert_eq!(miner.multiplier, miner_before.multiplier);
+    assert_eq!(miner.last_proof_block, miner_before.last_proof_block);
+    assert_eq!(miner.last_proof_at, miner_before.last_proof_at);
+    assert_eq!(miner.challenge, miner_before.challenge);
+
+    // Unregister refunds everything left, the treasury was already paid
+    let payer_before = svm.get_account(&payer.pubkey()).unwrap().lamports;
+    let unregister_ix = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer.pubkey(), true),
+            AccountMeta::new(miner_address(&payer, program_id), false),
+            AccountMeta::new_readonly(solana_sdk::system_program::ID, false),
+        ],
+        data: vec![0x21], // Unregister discriminator
+    };
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[unregister_ix],
+        Some(&payer.pubkey()),
+        &[&payer],
+        blockhash,
+    );
+    svm.send_transaction(tx).expect("Unregister failed");
+
+    let payer_after = svm.get_account(&payer.pubkey()).unwrap().lamports;
+    assert_eq!(
+        svm.get_account(&accounts.treasury).unwrap().lamports,
+        treasury_after
+    );
+    assert_eq!(
+        payer_after + 5_000 - payer_before,
+        miner_lamports - MINER_SLASH_AMOUNT
+    );
+}
+
+#[test]
+fn test_pinocchio_mine_rejects_other_treasury() {
+    let (mut svm, payer, program_id) = setup_svm();
+    let mut accounts = setup_mine_accounts(&mut svm, &payer, program_id);
+    let data = commit_mine(&mut svm, &payer, program_id, &accounts);
+
+    // Slashed lamports can't be redirected to an account of the miner's choosing
+    accounts.treasury = set_program_account(&mut svm, program_id, vec![0; 8]);
+    let result = send_mine(&mut svm, &payer, program_id, &accounts, data);
+    assert_eq!(
+        result,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::InvalidAccountData
+        ))
+    );
+}
+
+#[test]
+fn test_pinocchio_mine_rejects_truncated_data() {
+    let (mut svm, payer, program_id) = setup_svm();
+    let accounts = setup_mine_accounts(&mut svm, &payer, program_id);
+
+    // Cut the instruction off in the middle of the PoA
+    let mut data = mine_data([0; 32], &[[0; 32]; TAPE_PROOF_LEN]);
+    data.truncate(1 + PoW::LEN + PoA::LEN / 2);
+
+    let result = send_mine(&mut svm, &payer, program_id, &accounts, data);
+    assert_eq!(
+        result,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::InvalidInstructionData
+        ))
+    );
+}
+
+#[test]
+fn test_pinocchio_mine_rejects_wrong_account_type() {
+    let (mut svm, payer, program_id) = setup_svm();
+    let mut accounts = setup_mine_accounts(&mut svm, &payer, program_id);
+    let data = commit_mine(&mut svm, &payer, program_id, &accounts);
+
+    // Block and Epoch have the same size, so only the type byte tells them apart
+    assert_eq!(core::mem::size_of::<Block>(), core::mem::size_of::<Epoch>());
+    let epoch = accounts.epoch;
+    accounts.epoch = accounts.block;
+    accounts.block = epoch;
+
+    let result = send_mine(&mut svm, &payer, program_id, &accounts, data.clone());
+    assert_eq!(
+        result,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::InvalidAccountData
+        ))
+    );
+
+    // A bare epoch without the type byte is rejected too
+    let (epoch, block) = (accounts.block, accounts.epoch);
+    accounts.epoch =
+        set_program_account(&mut svm, program_id, vec![0; core::mem::size_of::<Epoch>()]);
+    accounts.block = block;
+    let result = send_mine(&mut svm, &payer, program_id, &accounts, data.clone());
+    assert_eq!(
+        result,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::InvalidAccountData
+        ))
+    );
+
+    // Restoring the right accounts gets past the loaders
+    accounts.epoch = epoch;
+    let result = send_mine(&mut svm, &payer, program_id, &accounts, data);
+    assert!(result.is_ok(), "Mine failed: {:?}", result.err());
+}
+
+#[test]
+fn test_pinocchio_mine_rejects_tape_copy() {
+    let (mut svm, payer, program_id) = setup_svm();
+    let mut accounts = setup_mine_accounts(&mut svm, &payer, program_id);
+    let data = commit_mine(&mut svm, &payer, program_id, &accounts);
+
+    // Same bytes as the recalled tape, number included, at another address
+    let tape_data = svm.get_account(&accounts.tape).unwrap().data;
+    accounts.tape = set_program_account(&mut svm, program_id, tape_data);
+
+    let result = send_mine(&mut svm, &payer, program_id, &accounts, data);
+    assert_eq!(
+        result,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::InvalidSeeds
+        ))
+    );
+}
+
+/// Sends a single instruction signed by `payer`
+fn send_ix(svm: &mut LiteSVM, payer: &Keypair, ix: Instruction) -> Result<(), TransactionError> {
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[payer], blockhash);
+    svm.send_transaction(tx).map(|_| ()).map_err(|e| e.err)
+}
+
+#[test]
+fn test_pinocchio_mine_subsidized_tape() {
+    let (mut svm, payer, program_id) = setup_svm();
+    let mut accounts = setup_mine_accounts(&mut svm, &payer, program_id);
+
+    // A tape created through the program, with one segment written
+    let name = to_name("subsidized");
+    let (tape, _) =
+        Pubkey::find_program_address(&[TAPE, payer.pubkey().as_ref(), &name], &program_id);
+    let (writer, _) = Pubkey::find_program_address(&[WRITER, tape.as_ref()], &program_id);
+
+    let mut data = vec![0x10]; // Create discriminator
+    data.extend_from_slice(&name);
+    let create_ix = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer.pubkey(), true),
+            AccountMeta::new(tape, false),
+            AccountMeta::new(writer, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(sysvar::rent::ID, false),
+        ],
+        data,
+    };
+    send_ix(&mut svm, &payer, create_ix).expect("Create failed");
+
+    let writer_root = Writer::unpack(&svm.get_account(&writer).unwrap().data)
+        .unwrap()
+        .state
+        .get_root();
+    update_account::<Tape>(&mut svm, &tape, |tape| {
+        tape.state = TapeState::Writing as u64;
+        tape.total_segments = 1;
+        tape.merkle_root = writer_root.to_bytes();
+    });
+
+    // Subsidize a year of rent, the only funding the tape gets
+    let tape_data = svm.get_account(&tape).unwrap().data;
+    let required = tape_api::state::Tape::unpack(&tape_data)
+        .unwrap()
+        .required_finalize_balance();
+    let lamports_before = svm.get_account(&tape).unwrap().lamports;
+
+    let mut data = [0u8; 9];
+    let data_len = build_subsidize_ix_data(required, &mut data);
+    let subsidize_ix = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer.pubkey(), true),
+            AccountMeta::new(tape, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: data[..data_len].to_vec(),
+    };
+    send_ix(&mut svm, &payer, subsidize_ix).expect("Subsidize failed");
+
+    // The subsidy lands in the tape as lamports, backing the new balance
+    let tape_account = svm.get_account(&tape).unwrap();
+    let balance = bytemuck::pod_read_unaligned::<Tape>(&tape_account.data).balance;
+    assert_eq!(tape_account.lamports, lamports_before + required);
+    assert_eq!(balance, required);
+
+    set_program_account_at(
+        &mut svm,
+        Pubkey::from(ARCHIVE_ADDRESS),
+        program_id,
+        vec![0; core::mem::size_of::<tape_api::state::Archive>()],
+    );
+    let finalize_ix = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer.pubkey(), true),
+            AccountMeta::new(tape, false),
+            AccountMeta::new(writer, false),
+            AccountMeta::new(Pubkey::from(ARCHIVE_ADDRESS), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(sysvar::rent::ID, false),
+        ],
+        data: vec![0x13], // Finalize discriminator
+    };
+    send_ix(&mut svm, &payer, finalize_ix).expect("Finalize failed");
+
+    // The finalized tape is tape 1, which commit_mine recalls
+    accounts.tape = tape;
+    let data = commit_mine(&mut svm, &payer, program_id, &accounts);
+    let result = send_mine(&mut svm, &payer, program_id, &accounts, data);
+    assert!(result.is_ok(), "Mine failed: {:?}", result.err());
+}
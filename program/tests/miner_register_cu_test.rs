@@ -2,11 +2,16 @@
 
 use litesvm::LiteSVM;
 use solana_sdk::{
-    pubkey::Pubkey, signature::Keypair, signer::Signer, system_program, sysvar,
-    transaction::Transaction,
+    instruction::InstructionError,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program, sysvar,
+    transaction::{Transaction, TransactionError},
 };
 use tape_api::{
-    consts::{MINER, NAME_LEN},
+    consts::{MINER, MINER_DEPOSIT, NAME_LEN},
+    error::TapeError,
     state::Miner,
 };
 
@@ -188,3 +193,72 @@ fn test_pinocchio_miner_register_multiple_runs() {
 
     println!("\nPINOCCHIO MINER REGISTER - MULTIPLE RUNS PASSED");
 }
+
+#[test]
+fn test_pinocchio_miner_register_requires_deposit() {
+    const TX_FEE: u64 = 5_000;
+
+    let mut svm = LiteSVM::new();
+
+    let program_id: Pubkey = "7wApqqrfJo2dAGAKVgheccaVEgeDoqVKogtJSTbFRWn2"
+        .parse()
+        .expect("Invalid program ID");
+
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .expect("Failed to load Pinocchio tape program");
+
+    // Enough for rent, one lamport short of the deposit
+    let rent = svm.minimum_balance_for_rent_exemption(core::mem::size_of::<Miner>());
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), TX_FEE + rent + MINER_DEPOSIT - 1)
+        .expect("Failed to airdrop to payer");
+    let payer_pk = payer.pubkey();
+
+    let name_bytes = to_name("deposit-miner");
+    let (miner_address, _miner_bump) =
+        Pubkey::find_program_address(&[MINER, payer_pk.as_ref(), &name_bytes], &program_id);
+
+    let register_ix = || {
+        let mut data = vec![0x20]; // Register discriminator
+        data.extend_from_slice(&name_bytes);
+
+        solana_sdk::instruction::Instruction {
+            program_id,
+            accounts: vec![
+                solana_sdk::instruction::AccountMeta::new(payer_pk, true),
+                solana_sdk::instruction::AccountMeta::new(miner_address, false),
+                solana_sdk::instruction::AccountMeta::new_readonly(system_program::ID, false),
+                solana_sdk::instruction::AccountMeta::new_readonly(sysvar::rent::ID, false),
+                solana_sdk::instruction::AccountMeta::new_readonly(sysvar::slot_hashes::ID, false),
+            ],
+            data,
+        }
+    };
+
+    let blockhash = svm.latest_blockhash();
+    let tx =
+        Transaction::new_signed_with_payer(&[register_ix()], Some(&payer_pk), &[&payer], blockhash);
+    assert_eq!(
+        svm.send_transaction(tx).map_err(|e| e.err).err(),
+        Some(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TapeError::InsufficientFunds as u32)
+        ))
+    );
+    assert!(svm.get_account(&miner_address).is_none());
+
+    // With the deposit covered, it is held in the miner account on top of rent
+    svm.airdrop(&payer_pk, TX_FEE + 1)
+        .expect("Failed to airdrop to payer");
+
+    let blockhash = svm.latest_blockhash();
+    let tx =
+        Transaction::new_signed_with_payer(&[register_ix()], Some(&payer_pk), &[&payer], blockhash);
+    svm.send_transaction(tx).expect("Register failed");
+
+    let miner_account = svm.get_account(&miner_address).unwrap();
+    let miner = Miner::unpack(&miner_account.data).unwrap();
+    assert_eq!(miner.deposit, MINER_DEPOSIT);
+    assert_eq!(miner.forfeited, 0);
+    assert_eq!(miner_account.lamports, rent + MINER_DEPOSIT);
+}
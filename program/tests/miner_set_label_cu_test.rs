@@ -0,0 +1,150 @@
+#![cfg(test)]
+
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program, sysvar,
+    transaction::{Transaction, TransactionError},
+};
+use tape_api::{
+    consts::{MINER, NAME_LEN},
+    state::Miner,
+};
+
+/// Helper to convert string to fixed-size name array
+fn to_name(s: &str) -> [u8; NAME_LEN] {
+    let mut name = [0u8; NAME_LEN];
+    let bytes = s.as_bytes();
+    let len = bytes.len().min(NAME_LEN);
+    name[..len].copy_from_slice(&bytes[..len]);
+    name
+}
+
+fn setup() -> (LiteSVM, Keypair, Pubkey) {
+    let mut svm = LiteSVM::new();
+
+    let program_id: Pubkey = "7wApqqrfJo2dAGAKVgheccaVEgeDoqVKogtJSTbFRWn2"
+        .parse()
+        .expect("Invalid program ID");
+
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .expect("Failed to load program");
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+    (svm, payer, program_id)
+}
+
+/// Helper to sign and send a single instruction
+fn send(svm: &mut LiteSVM, signer: &Keypair, ix: Instruction) -> Result<u64, TransactionError> {
+    let blockhash = svm.latest_blockhash();
+    let tx =
+        Transaction::new_signed_with_payer(&[ix], Some(&signer.pubkey()), &[signer], blockhash);
+    svm.send_transaction(tx)
+        .map(|meta| meta.compute_units_consumed)
+        .map_err(|e| e.err)
+}
+
+fn register_miner(svm: &mut LiteSVM, payer: &Keypair, program_id: Pubkey, name: &str) -> Pubkey {
+    let payer_pk = payer.pubkey();
+    let name_bytes = to_name(name);
+
+    let (miner_address, _) =
+        Pubkey::find_program_address(&[MINER, payer_pk.as_ref(), &name_bytes], &program_id);
+
+    let mut data = vec![0x20]; // Register discriminator
+    data.extend_from_slice(&name_bytes);
+
+    let ix = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer_pk, true),
+            AccountMeta::new(miner_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(sysvar::rent::ID, false),
+            AccountMeta::new_readonly(sysvar::slot_hashes::ID, false),
+        ],
+        data,
+    };
+    send(svm, payer, ix).unwrap();
+
+    miner_address
+}
+
+fn set_label_ix(
+    program_id: Pubkey,
+    signer: Pubkey,
+    miner_address: Pubkey,
+    label: &str,
+) -> Instruction {
+    let mut data = vec![0x24]; // SetLabel discriminator
+    data.extend_from_slice(&to_name(label));
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(miner_address, false),
+        ],
+        data,
+    }
+}
+
+fn miner_state(svm: &LiteSVM, miner_address: &Pubkey) -> Miner {
+    *Miner::unpack(&svm.get_account(miner_address).unwrap().data).unwrap()
+}
+
+#[test]
+fn test_pinocchio_miner_set_label_cu() {
+    println!("\nPINOCCHIO MINER SET LABEL - CU MEASUREMENT TEST");
+
+    let (mut svm, payer, program_id) = setup();
+    let miner_address = register_miner(&mut svm, &payer, program_id, "seed-name");
+
+    // The label starts out as the registered name
+    assert_eq!(
+        miner_state(&svm, &miner_address).display_name,
+        to_name("seed-name")
+    );
+
+    let ix = set_label_ix(program_id, payer.pubkey(), miner_address, "Friendly Miner");
+    let cus = send(&mut svm, &payer, ix).unwrap();
+    println!("Compute Units: {}", cus);
+
+    // Only the label changes, the seed name and address stay put
+    let miner = miner_state(&svm, &miner_address);
+    assert_eq!(miner.display_name, to_name("Friendly Miner"));
+    assert_eq!(miner.name, to_name("seed-name"));
+
+    let (derived, _) =
+        Pubkey::find_program_address(&[MINER, payer.pubkey().as_ref(), &miner.name], &program_id);
+    assert_eq!(derived, miner_address);
+
+    println!("\nTEST PASSED - CUs: {}", cus);
+}
+
+#[test]
+fn test_pinocchio_miner_set_label_rejects_other_signer() {
+    let (mut svm, payer, program_id) = setup();
+    let miner_address = register_miner(&mut svm, &payer, program_id, "labelled");
+
+    let intruder = Keypair::new();
+    svm.airdrop(&intruder.pubkey(), 1_000_000_000).unwrap();
+
+    let ix = set_label_ix(program_id, intruder.pubkey(), miner_address, "stolen");
+    assert_eq!(
+        send(&mut svm, &intruder, ix),
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::MissingRequiredSignature
+        ))
+    );
+    assert_eq!(
+        miner_state(&svm, &miner_address).display_name,
+        to_name("labelled")
+    );
+}
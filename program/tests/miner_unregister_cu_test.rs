@@ -10,7 +10,7 @@ use solana_sdk::{
     transaction::Transaction,
 };
 use tape_api::{
-    consts::{MINER, NAME_LEN},
+    consts::{MINER, MINER_DEPOSIT, NAME_LEN},
     state::Miner,
 };
 
@@ -85,6 +85,8 @@ fn test_pinocchio_miner_unregister_single() {
     let miner = Miner::unpack(&miner_account.data).unwrap();
     assert_eq!(miner.authority.as_ref(), payer_pk.as_ref());
     assert_eq!(miner.unclaimed_rewards, 0);
+    assert_eq!(miner.deposit, MINER_DEPOSIT);
+    let miner_lamports = miner_account.lamports;
     println!("Authority: {:?}", &miner.authority[..8]);
     println!("Unclaimed rewards: {}", miner.unclaimed_rewards);
 
@@ -155,6 +157,14 @@ fn test_pinocchio_miner_unregister_single() {
             "Payer should receive rent back"
         );
 
+        // Nothing was slashed, so rent and the whole deposit come back
+        const TX_FEE: u64 = 5_000;
+        assert_eq!(
+            payer_balance_after + TX_FEE - payer_balance_before,
+            miner_lamports
+        );
+        assert!(miner_lamports > MINER_DEPOSIT);
+
         println!(
             "\nTEST PASSED - CUs: {}",
             metadata.compute_units_consumed
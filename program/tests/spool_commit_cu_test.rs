@@ -1,18 +1,35 @@
 #![cfg(test)]
 
+use base64::{engine::general_purpose::STANDARD, Engine};
 use litesvm::LiteSVM;
+use pinnochio_tape_program::state::{
+    Archive as ProgramArchive, Block as ProgramBlock, DataLen, Epoch as ProgramEpoch, Mine, PoA,
+    PoW,
+};
 use solana_sdk::{
-    pubkey::Pubkey, signature::Keypair, signer::Signer, system_program, sysvar,
-    transaction::Transaction,
+    account::Account,
+    instruction::{AccountMeta, InstructionError},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program, sysvar,
+    transaction::{Transaction, TransactionError},
 };
 use tape_api::{
-    consts::{MINER, NAME_LEN, SEGMENT_PROOF_LEN, SPOOL, TAPE_TREE_HEIGHT},
-    state::{Miner, Spool},
+    consts::{
+        MINER, NAME_LEN, SEGMENT_PROOF_LEN, SEGMENT_TREE_HEIGHT, SPOOL, TAPE_TREE_HEIGHT,
+        TREASURY_ADDRESS,
+    },
+    error::TapeError,
+    event::SpoolCommitEvent,
+    state::{Miner, Spool, Tape, TapeState},
     types::ProofPath,
+    utils::compute_commitment,
 };
 use tape_utils::{leaf::Leaf, tree::MerkleTree};
 
 type TapeTree = MerkleTree<TAPE_TREE_HEIGHT>;
+type SegmentTree = MerkleTree<SEGMENT_TREE_HEIGHT>;
 
 /// Helper to convert string to fixed-size name array
 fn to_name(s: &str) -> [u8; NAME_LEN] {
@@ -133,6 +150,99 @@ fn pack_value(
     svm.send_transaction(tx).unwrap();
 }
 
+/// Decodes the `Program data:` entries emitted via sol_log_data
+fn program_data(logs: &[String]) -> Vec<Vec<u8>> {
+    logs.iter()
+        .filter_map(|log| log.strip_prefix("Program data: "))
+        .map(|data| STANDARD.decode(data).expect("Invalid base64 event data"))
+        .collect()
+}
+
+/// Creates a zeroed account of `len` bytes owned by the program
+fn set_program_account(svm: &mut LiteSVM, program_id: Pubkey, len: usize) -> Pubkey {
+    let address = Pubkey::new_unique();
+    svm.set_account(
+        address,
+        Account {
+            lamports: 1_000_000_000,
+            data: vec![0u8; len],
+            owner: program_id,
+            executable: false,
+            rent_epoch: 0,
+        }
+        .into(),
+    )
+    .unwrap();
+    address
+}
+
+/// Creates a finalized tape account with the given number
+fn set_finalized_tape(svm: &mut LiteSVM, program_id: Pubkey, number: u64) -> Pubkey {
+    let tape_address = set_program_account(svm, program_id, core::mem::size_of::<Tape>());
+    let mut tape_account = svm.get_account(&tape_address).unwrap();
+    let tape = Tape::unpack_mut(&mut tape_account.data).unwrap();
+    tape.number = number;
+    tape.state = TapeState::Finalized as u64;
+    svm.set_account(tape_address, tape_account.into()).unwrap();
+    tape_address
+}
+
+fn commit_value(
+    svm: &mut LiteSVM,
+    payer: &Keypair,
+    program_id: Pubkey,
+    miner_address: Pubkey,
+    spool_address: Pubkey,
+    value: [u8; 32],
+    proof: &[[u8; 32]; SEGMENT_PROOF_LEN],
+) {
+    send_commit(
+        svm,
+        payer,
+        program_id,
+        miner_address,
+        spool_address,
+        value,
+        proof,
+    )
+    .unwrap();
+}
+
+/// Sends a commit, returning the transaction error instead of panicking
+fn send_commit(
+    svm: &mut LiteSVM,
+    payer: &Keypair,
+    program_id: Pubkey,
+    miner_address: Pubkey,
+    spool_address: Pubkey,
+    value: [u8; 32],
+    proof: &[[u8; 32]; SEGMENT_PROOF_LEN],
+) -> Result<(), TransactionError> {
+    let payer_pk = payer.pubkey();
+
+    let mut data = vec![0x44];
+    data.extend_from_slice(&value);
+    for proof_hash in proof {
+        data.extend_from_slice(proof_hash);
+    }
+
+    let accounts = vec![
+        AccountMeta::new(payer_pk, true),
+        AccountMeta::new(miner_address, false),
+        AccountMeta::new_readonly(spool_address, false),
+    ];
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id,
+        accounts,
+        data,
+    };
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[payer], blockhash);
+    svm.send_transaction(tx).map(|_| ()).map_err(|e| e.err)
+}
+
 #[test]
 fn test_pinocchio_spool_commit_cu_measurement() {
     println!("\nPINOCCHIO SPOOL COMMIT - CU MEASUREMENT TEST");
@@ -192,7 +302,7 @@ fn test_pinocchio_spool_commit_cu_measurement() {
     assert_eq!(tree.get_root().to_bytes(), spool.contains);
     println!("Merkle proof verified locally");
 
-    let proof_hashes = tree.get_proof_no_std(&[leaf], 0);
+    let proof_hashes = tree.get_proof_no_std::<1>(&[leaf], 0).unwrap();
     let proof_array: [[u8; 32]; SEGMENT_PROOF_LEN] = proof_hashes.map(|h| h.to_bytes());
 
     // Step 5: Commit
@@ -231,7 +341,12 @@ fn test_pinocchio_spool_commit_cu_measurement() {
         println!("\nCommitment Set:");
         println!("Miner commitment: {:?}", &miner.commitment[..8]);
 
-        assert_eq!(miner.commitment, test_value);
+        let expected = compute_commitment(
+            &test_value,
+            &spool.contains,
+            &spool.state.get_root().to_bytes(),
+        );
+        assert_eq!(miner.commitment, expected);
 
         println!(
             "\nTEST PASSED - CUs: {}",
@@ -291,7 +406,7 @@ fn test_pinocchio_spool_commit_multiple_runs() {
         let mut tree = TapeTree::new(&[spool_address.as_ref()]);
         tree.try_add_leaf(leaf).unwrap();
 
-        let proof_hashes = tree.get_proof_no_std(&[leaf], 0);
+        let proof_hashes = tree.get_proof_no_std::<1>(&[leaf], 0).unwrap();
         let proof_array: [[u8; 32]; SEGMENT_PROOF_LEN] = proof_hashes.map(|h| h.to_bytes());
 
         // Commit
@@ -339,3 +454,365 @@ fn test_pinocchio_spool_commit_multiple_runs() {
 
     println!("\nPINOCCHIO SPOOL COMMIT - MULTIPLE RUNS PASSED");
 }
+
+#[test]
+fn test_pinocchio_spool_commit_stale_after_pack() {
+    println!("\nPINOCCHIO SPOOL COMMIT - STALE COMMITMENT");
+
+    let mut svm = LiteSVM::new();
+
+    let program_id: Pubkey = "7wApqqrfJo2dAGAKVgheccaVEgeDoqVKogtJSTbFRWn2"
+        .parse()
+        .expect("Invalid program ID");
+
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .expect("Failed to load Pinocchio tape program");
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000)
+        .expect("Failed to airdrop to payer");
+
+    let payer_pk = payer.pubkey();
+
+    let miner_address = register_miner(&mut svm, &payer, program_id, "stale-miner");
+    let spool_address = create_spool(&mut svm, &payer, program_id, miner_address, 0);
+
+    // Pack the first tape
+    let tape_1 = set_finalized_tape(&mut svm, program_id, 1);
+    pack_value(&mut svm, &payer, program_id, spool_address, tape_1, [1u8; 32]);
+
+    // Point the spool at a segment tree holding the committed value
+    let test_value = [42u8; 32];
+    let leaf = Leaf::from(test_value);
+    let mut segments = SegmentTree::new(&[b"segments"]);
+    segments.try_add_leaf(leaf).unwrap();
+    {
+        let mut spool_account = svm.get_account(&spool_address).unwrap();
+        let spool = Spool::unpack_mut(&mut spool_account.data).unwrap();
+        spool.contains = segments.get_root().to_bytes();
+        svm.set_account(spool_address, spool_account.into()).unwrap();
+    }
+
+    let proof: [[u8; 32]; SEGMENT_PROOF_LEN] = segments
+        .get_proof_no_std::<1>(&[leaf], 0)
+        .unwrap()
+        .map(|h| h.to_bytes());
+
+    commit_value(
+        &mut svm,
+        &payer,
+        program_id,
+        miner_address,
+        spool_address,
+        test_value,
+        &proof,
+    );
+
+    // Modify the spool after committing
+    let tape_2 = set_finalized_tape(&mut svm, program_id, 2);
+    pack_value(&mut svm, &payer, program_id, spool_address, tape_2, [2u8; 32]);
+
+    let epoch_address = set_program_account(&mut svm, program_id, ProgramEpoch::LEN);
+    let block_address = set_program_account(&mut svm, program_id, ProgramBlock::LEN);
+    let archive_address = set_program_account(&mut svm, program_id, ProgramArchive::LEN);
+
+    let build_mine_ix = |value: [u8; 32]| {
+        let mut data = vec![0u8; 1 + Mine::LEN];
+        data[0] = 0x22;
+        let offset = 1 + PoW::LEN + PoA::LEN;
+        data[offset..offset + 32].copy_from_slice(&value);
+
+        solana_sdk::instruction::Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(payer_pk, true),
+                AccountMeta::new(epoch_address, false),
+                AccountMeta::new(block_address, false),
+                AccountMeta::new(miner_address, false),
+                AccountMeta::new(tape_1, false),
+                AccountMeta::new(archive_address, false),
+                AccountMeta::new_readonly(sysvar::slot_hashes::ID, false),
+                AccountMeta::new_readonly(spool_address, false),
+                AccountMeta::new(TREASURY_ADDRESS.into(), false),
+            ],
+            data,
+        }
+    };
+
+    let mismatch = TransactionError::InstructionError(
+        0,
+        InstructionError::Custom(TapeError::CommitmentMismatch as u32),
+    );
+
+    // The stale commitment is rejected at mine time
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[build_mine_ix(test_value)],
+        Some(&payer_pk),
+        &[&payer],
+        blockhash,
+    );
+    let result = svm.send_transaction(tx);
+    assert_eq!(result.err().map(|e| e.err), Some(mismatch.clone()));
+
+    // Re-committing against the current spool clears the mismatch
+    svm.expire_blockhash();
+    commit_value(
+        &mut svm,
+        &payer,
+        program_id,
+        miner_address,
+        spool_address,
+        test_value,
+        &proof,
+    );
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[build_mine_ix(test_value)],
+        Some(&payer_pk),
+        &[&payer],
+        blockhash,
+    );
+    let result = svm.send_transaction(tx);
+    assert_ne!(result.err().map(|e| e.err), Some(mismatch));
+
+    println!("\nPINOCCHIO SPOOL COMMIT - STALE COMMITMENT PASSED");
+}
+
+#[test]
+fn test_pinocchio_spool_commit_emits_event() {
+    let mut svm = LiteSVM::new();
+
+    let program_id: Pubkey = "7wApqqrfJo2dAGAKVgheccaVEgeDoqVKogtJSTbFRWn2"
+        .parse()
+        .expect("Invalid program ID");
+
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .expect("Failed to load Pinocchio tape program");
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000)
+        .expect("Failed to airdrop to payer");
+
+    let payer_pk = payer.pubkey();
+
+    let miner_address = register_miner(&mut svm, &payer, program_id, "event-miner");
+    let spool_address = create_spool(&mut svm, &payer, program_id, miner_address, 0);
+
+    // Point the spool at a segment tree holding the committed value
+    let test_value = [42u8; 32];
+    let leaf = Leaf::from(test_value);
+    let mut segments = SegmentTree::new(&[b"segments"]);
+    segments.try_add_leaf(leaf).unwrap();
+    {
+        let mut spool_account = svm.get_account(&spool_address).unwrap();
+        let spool = Spool::unpack_mut(&mut spool_account.data).unwrap();
+        spool.contains = segments.get_root().to_bytes();
+        svm.set_account(spool_address, spool_account.into()).unwrap();
+    }
+
+    let proof: [[u8; 32]; SEGMENT_PROOF_LEN] = segments
+        .get_proof_no_std::<1>(&[leaf], 0)
+        .unwrap()
+        .map(|h| h.to_bytes());
+
+    let mut data = vec![0x44];
+    data.extend_from_slice(&test_value);
+    for proof_hash in &proof {
+        data.extend_from_slice(proof_hash);
+    }
+
+    let accounts = vec![
+        AccountMeta::new(payer_pk, true),
+        AccountMeta::new(miner_address, false),
+        AccountMeta::new_readonly(spool_address, false),
+    ];
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id,
+        accounts,
+        data,
+    };
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let metadata = svm.send_transaction(tx).expect("Commit failed");
+
+    let events = program_data(&metadata.logs);
+    assert_eq!(events.len(), 1, "Expected one event");
+
+    let event = SpoolCommitEvent::try_from_bytes(&events[0]).unwrap();
+    assert_eq!(event.value, test_value);
+    assert_eq!(event.address, spool_address.to_bytes());
+
+    println!("\nSpoolCommitEvent verified");
+}
+
+#[test]
+fn test_pinocchio_spool_commit_rejects_invalid_proof() {
+    let mut svm = LiteSVM::new();
+
+    let program_id: Pubkey = "7wApqqrfJo2dAGAKVgheccaVEgeDoqVKogtJSTbFRWn2"
+        .parse()
+        .expect("Invalid program ID");
+
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .expect("Failed to load Pinocchio tape program");
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000)
+        .expect("Failed to airdrop to payer");
+
+    let payer_pk = payer.pubkey();
+
+    let miner_address = register_miner(&mut svm, &payer, program_id, "invalid-miner");
+    let spool_address = create_spool(&mut svm, &payer, program_id, miner_address, 0);
+
+    // Point the spool at a segment tree holding the committed value
+    let test_value = [42u8; 32];
+    let leaf = Leaf::from(test_value);
+    let mut segments = SegmentTree::new(&[b"segments"]);
+    segments.try_add_leaf(leaf).unwrap();
+    {
+        let mut spool_account = svm.get_account(&spool_address).unwrap();
+        let spool = Spool::unpack_mut(&mut spool_account.data).unwrap();
+        spool.contains = segments.get_root().to_bytes();
+        svm.set_account(spool_address, spool_account.into()).unwrap();
+    }
+
+    // Tamper with an otherwise valid proof
+    let mut proof: [[u8; 32]; SEGMENT_PROOF_LEN] = segments
+        .get_proof_no_std::<1>(&[leaf], 0)
+        .unwrap()
+        .map(|h| h.to_bytes());
+    proof[0][0] ^= 0xff;
+
+    let mut data = vec![0x44];
+    data.extend_from_slice(&test_value);
+    for proof_hash in &proof {
+        data.extend_from_slice(proof_hash);
+    }
+
+    let accounts = vec![
+        AccountMeta::new(payer_pk, true),
+        AccountMeta::new(miner_address, false),
+        AccountMeta::new_readonly(spool_address, false),
+    ];
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id,
+        accounts,
+        data,
+    };
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let result = svm.send_transaction(tx);
+
+    let invalid = TransactionError::InstructionError(
+        0,
+        InstructionError::Custom(TapeError::SolutionInvalid as u32),
+    );
+    assert_eq!(result.err().map(|e| e.err), Some(invalid));
+
+    // The miner must not hold a commitment for the rejected value
+    let miner_account = svm.get_account(&miner_address).unwrap();
+    let miner = Miner::unpack(&miner_account.data).unwrap();
+    assert_eq!(miner.commitment, [0u8; 32]);
+}
+
+#[test]
+fn test_pinocchio_spool_commit_rejects_proof_for_replaced_contents() {
+    let mut svm = LiteSVM::new();
+
+    let program_id: Pubkey = "7wApqqrfJo2dAGAKVgheccaVEgeDoqVKogtJSTbFRWn2"
+        .parse()
+        .expect("Invalid program ID");
+
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .expect("Failed to load Pinocchio tape program");
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000)
+        .expect("Failed to airdrop to payer");
+
+    let miner_address = register_miner(&mut svm, &payer, program_id, "replaced-miner");
+    let spool_address = create_spool(&mut svm, &payer, program_id, miner_address, 0);
+
+    let tape_1 = set_finalized_tape(&mut svm, program_id, 1);
+    pack_value(&mut svm, &payer, program_id, spool_address, tape_1, [1u8; 32]);
+
+    // Points the spool at a segment tree holding `value`, as an unpack does,
+    // and returns the proof a client would build for it
+    let mut unpack = |value: [u8; 32]| {
+        let leaf = Leaf::from(value);
+        let mut segments = SegmentTree::new(&[b"segments"]);
+        segments.try_add_leaf(leaf).unwrap();
+
+        let mut spool_account = svm.get_account(&spool_address).unwrap();
+        let spool = Spool::unpack_mut(&mut spool_account.data).unwrap();
+        spool.contains = segments.get_root().to_bytes();
+        svm.set_account(spool_address, spool_account.into()).unwrap();
+
+        let proof: [[u8; 32]; SEGMENT_PROOF_LEN] = segments
+            .get_proof_no_std::<1>(&[leaf], 0)
+            .unwrap()
+            .map(|h| h.to_bytes());
+        proof
+    };
+
+    // The client builds its proof, then the spool moves on before the commit
+    let stale_value = [42u8; 32];
+    let stale_proof = unpack(stale_value);
+    let live_value = [43u8; 32];
+    let live_proof = unpack(live_value);
+
+    let tape_2 = set_finalized_tape(&mut svm, program_id, 2);
+    pack_value(&mut svm, &payer, program_id, spool_address, tape_2, [2u8; 32]);
+
+    let result = send_commit(
+        &mut svm,
+        &payer,
+        program_id,
+        miner_address,
+        spool_address,
+        stale_value,
+        &stale_proof,
+    );
+    assert_eq!(
+        result,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TapeError::SolutionInvalid as u32)
+        ))
+    );
+
+    let miner_account = svm.get_account(&miner_address).unwrap();
+    let miner = Miner::unpack(&miner_account.data).unwrap();
+    assert_eq!(miner.commitment, [0u8; 32]);
+
+    // A proof against the live contents commits to the spool as it is now
+    commit_value(
+        &mut svm,
+        &payer,
+        program_id,
+        miner_address,
+        spool_address,
+        live_value,
+        &live_proof,
+    );
+
+    let spool_account = svm.get_account(&spool_address).unwrap();
+    let spool = Spool::unpack(&spool_account.data).unwrap();
+    let miner_account = svm.get_account(&miner_address).unwrap();
+    let miner = Miner::unpack(&miner_account.data).unwrap();
+    assert_eq!(
+        miner.commitment,
+        compute_commitment(
+            &live_value,
+            &spool.contains,
+            &spool.state.get_root().to_bytes()
+        )
+    );
+}
@@ -1,12 +1,20 @@
 #![cfg(test)]
 
+use base64::{engine::general_purpose::STANDARD, Engine};
 use litesvm::LiteSVM;
 use solana_sdk::{
-    pubkey::Pubkey, signature::Keypair, signer::Signer, system_program, sysvar,
-    transaction::Transaction,
+    clock::Clock,
+    instruction::{Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program, sysvar,
+    transaction::{Transaction, TransactionError},
 };
 use tape_api::{
     consts::{MINER, NAME_LEN, SPOOL},
+    error::TapeError,
+    event::SpoolCreateEvent,
     state::{Miner, Spool},
 };
 
@@ -57,15 +65,12 @@ fn register_miner(
     miner_address
 }
 
-fn create_spool(
-    svm: &mut LiteSVM,
-    payer: &Keypair,
+fn build_create_spool_ix(
+    payer_pk: Pubkey,
     program_id: Pubkey,
     miner_address: Pubkey,
     spool_number: u64,
-) -> Pubkey {
-    let payer_pk = payer.pubkey();
-
+) -> (Instruction, Pubkey) {
     // Derive spool PDA
     let spool_number_bytes = spool_number.to_le_bytes();
     let (spool_address, _spool_bump) = Pubkey::find_program_address(
@@ -85,12 +90,26 @@ fn create_spool(
         solana_sdk::instruction::AccountMeta::new_readonly(sysvar::rent::ID, false),
     ];
 
-    let ix = solana_sdk::instruction::Instruction {
+    let ix = Instruction {
         program_id,
         accounts,
         data,
     };
 
+    (ix, spool_address)
+}
+
+fn create_spool(
+    svm: &mut LiteSVM,
+    payer: &Keypair,
+    program_id: Pubkey,
+    miner_address: Pubkey,
+    spool_number: u64,
+) -> Pubkey {
+    let payer_pk = payer.pubkey();
+    let (ix, spool_address) =
+        build_create_spool_ix(payer_pk, program_id, miner_address, spool_number);
+
     let blockhash = svm.latest_blockhash();
     let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[payer], blockhash);
     svm.send_transaction(tx).unwrap();
@@ -98,6 +117,14 @@ fn create_spool(
     spool_address
 }
 
+/// Decodes the `Program data:` entries emitted via sol_log_data
+fn program_data(logs: &[String]) -> Vec<Vec<u8>> {
+    logs.iter()
+        .filter_map(|log| log.strip_prefix("Program data: "))
+        .map(|data| STANDARD.decode(data).expect("Invalid base64 event data"))
+        .collect()
+}
+
 #[test]
 fn test_pinocchio_spool_create_cu_measurement() {
     println!("\nPINOCCHIO SPOOL CREATE - CU MEASUREMENT TEST");
@@ -271,3 +298,143 @@ fn test_pinocchio_spool_create_multiple_runs() {
 
     println!("\nPINOCCHIO SPOOL CREATE - MULTIPLE RUNS PASSED");
 }
+
+#[test]
+fn test_pinocchio_spool_create_emits_event() {
+    let mut svm = LiteSVM::new();
+
+    let program_id: Pubkey = "7wApqqrfJo2dAGAKVgheccaVEgeDoqVKogtJSTbFRWn2"
+        .parse()
+        .expect("Invalid program ID");
+
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .expect("Failed to load Pinocchio tape program");
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000)
+        .expect("Failed to airdrop to payer");
+
+    let payer_pk = payer.pubkey();
+    let miner_address = register_miner(&mut svm, &payer, program_id, "event-miner");
+
+    let spool_number: u64 = 7;
+    let spool_number_bytes = spool_number.to_le_bytes();
+    let (spool_address, _) = Pubkey::find_program_address(
+        &[SPOOL, miner_address.as_ref(), &spool_number_bytes],
+        &program_id,
+    );
+
+    let mut data = vec![0x40]; // Create spool discriminator
+    data.extend_from_slice(&spool_number_bytes);
+
+    let accounts = vec![
+        solana_sdk::instruction::AccountMeta::new(payer_pk, true),
+        solana_sdk::instruction::AccountMeta::new(miner_address, false),
+        solana_sdk::instruction::AccountMeta::new(spool_address, false),
+        solana_sdk::instruction::AccountMeta::new_readonly(system_program::ID, false),
+        solana_sdk::instruction::AccountMeta::new_readonly(sysvar::rent::ID, false),
+    ];
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id,
+        accounts,
+        data,
+    };
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let metadata = svm.send_transaction(tx).expect("Spool create failed");
+
+    let events = program_data(&metadata.logs);
+    assert_eq!(events.len(), 1, "Expected one event");
+
+    let event = SpoolCreateEvent::try_from_bytes(&events[0]).unwrap();
+    assert_eq!(event.number, spool_number);
+    assert_eq!(event.authority, payer_pk.to_bytes());
+    assert_eq!(event.address, spool_address.to_bytes());
+
+    println!("\nSpoolCreateEvent verified");
+}
+
+#[test]
+fn test_pinocchio_spool_create_genesis_proof_fields() {
+    let mut svm = LiteSVM::new();
+
+    let program_id: Pubkey = "7wApqqrfJo2dAGAKVgheccaVEgeDoqVKogtJSTbFRWn2"
+        .parse()
+        .expect("Invalid program ID");
+
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .expect("Failed to load Pinocchio tape program");
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000)
+        .expect("Failed to airdrop to payer");
+
+    let miner_address = register_miner(&mut svm, &payer, program_id, "genesis-miner");
+
+    // With a real clock, last_proof_at is the creation time
+    let mut clock = svm.get_sysvar::<Clock>();
+    clock.unix_timestamp = 1_700_000_000;
+    svm.set_sysvar::<Clock>(&clock);
+
+    let spool_address = create_spool(&mut svm, &payer, program_id, miner_address, 0);
+    let spool_account = svm.get_account(&spool_address).unwrap();
+    let spool = Spool::unpack(&spool_account.data).unwrap();
+    assert_eq!(spool.last_proof_block, 0, "No proofs yet");
+    assert_eq!(spool.last_proof_at, 1_700_000_000);
+
+    // A zero clock falls back to the non-zero sentinel
+    clock.unix_timestamp = 0;
+    svm.set_sysvar::<Clock>(&clock);
+
+    let spool_address = create_spool(&mut svm, &payer, program_id, miner_address, 1);
+    let spool_account = svm.get_account(&spool_address).unwrap();
+    let spool = Spool::unpack(&spool_account.data).unwrap();
+    assert_eq!(spool.last_proof_block, 0, "No proofs yet");
+    assert_eq!(spool.last_proof_at, 1);
+}
+
+#[test]
+fn test_pinocchio_spool_create_rejects_foreign_miner() {
+    let mut svm = LiteSVM::new();
+
+    let program_id: Pubkey = "7wApqqrfJo2dAGAKVgheccaVEgeDoqVKogtJSTbFRWn2"
+        .parse()
+        .expect("Invalid program ID");
+
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .expect("Failed to load Pinocchio tape program");
+
+    let owner = Keypair::new();
+    svm.airdrop(&owner.pubkey(), 10_000_000_000)
+        .expect("Failed to airdrop to owner");
+    let attacker = Keypair::new();
+    svm.airdrop(&attacker.pubkey(), 10_000_000_000)
+        .expect("Failed to airdrop to attacker");
+
+    let miner_address = register_miner(&mut svm, &owner, program_id, "owned-miner");
+
+    // The attacker signs a spool creation seeded by the owner's miner
+    let attacker_pk = attacker.pubkey();
+    let (ix, spool_address) = build_create_spool_ix(attacker_pk, program_id, miner_address, 0);
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&attacker_pk), &[&attacker], blockhash);
+    let result = svm.send_transaction(tx);
+
+    assert_eq!(
+        result.err().map(|e| e.err),
+        Some(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TapeError::Unauthorized as u32)
+        ))
+    );
+    assert!(svm.get_account(&spool_address).is_none());
+
+    // The owner can still create it
+    create_spool(&mut svm, &owner, program_id, miner_address, 0);
+    let spool_account = svm.get_account(&spool_address).unwrap();
+    let spool = Spool::unpack(&spool_account.data).unwrap();
+    assert_eq!(spool.authority, owner.pubkey().to_bytes());
+}
@@ -1,14 +1,19 @@
 #![cfg(test)]
 
+use base64::{engine::general_purpose::STANDARD, Engine};
 use litesvm::LiteSVM;
 use solana_sdk::{
-    instruction::AccountMeta, pubkey::Pubkey, signature::Keypair, signer::Signer, system_program,
-    sysvar, transaction::Transaction,
+    account::Account, instruction::AccountMeta, pubkey::Pubkey, signature::Keypair,
+    signer::Signer, system_program, sysvar, transaction::Transaction,
 };
 use tape_api::{
-    consts::{MINER, NAME_LEN, SPOOL, TAPE, WRITER},
+    consts::{MAX_TAPES_PER_PACK, MINER, NAME_LEN, SPOOL, TAPE, TAPE_TREE_HEIGHT, WRITER},
+    event::SpoolPackEvent,
     state::{Spool, Tape, TapeState},
 };
+use tape_utils::{leaf::Leaf, tree::MerkleTree};
+
+type TapeTree = MerkleTree<TAPE_TREE_HEIGHT>;
 
 /// Helper to convert string to fixed-size name array
 fn to_name(s: &str) -> [u8; NAME_LEN] {
@@ -201,6 +206,63 @@ fn add_rent_to_tape(svm: &mut LiteSVM, tape_address: &Pubkey, amount: u64) {
     svm.set_account(*tape_address, tape_account.into()).unwrap();
 }
 
+/// Creates a finalized tape account with the given number
+fn set_finalized_tape(svm: &mut LiteSVM, program_id: Pubkey, number: u64) -> Pubkey {
+    let tape_address = Pubkey::new_unique();
+    let mut data = vec![0u8; core::mem::size_of::<Tape>()];
+    let tape = Tape::unpack_mut(&mut data).unwrap();
+    tape.number = number;
+    tape.state = TapeState::Finalized as u64;
+
+    let account = Account {
+        lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+        data,
+        owner: program_id,
+        executable: false,
+        rent_epoch: 0,
+    };
+    svm.set_account(tape_address, account.into()).unwrap();
+    tape_address
+}
+
+/// Builds a batched pack instruction for the given tapes and values
+fn pack_batch_ix(
+    program_id: Pubkey,
+    signer: Pubkey,
+    spool_address: Pubkey,
+    tapes: &[Pubkey],
+    values: &[[u8; 32]],
+) -> solana_sdk::instruction::Instruction {
+    let mut data = vec![0x47, values.len() as u8];
+    for value in values {
+        data.extend_from_slice(value);
+    }
+
+    let mut accounts = vec![
+        AccountMeta::new(signer, true),
+        AccountMeta::new(spool_address, false),
+    ];
+    accounts.extend(
+        tapes
+            .iter()
+            .map(|tape| AccountMeta::new_readonly(*tape, false)),
+    );
+
+    solana_sdk::instruction::Instruction {
+        program_id,
+        accounts,
+        data,
+    }
+}
+
+/// Decodes the `Program data:` entries emitted via sol_log_data
+fn program_data(logs: &[String]) -> Vec<Vec<u8>> {
+    logs.iter()
+        .filter_map(|log| log.strip_prefix("Program data: "))
+        .map(|data| STANDARD.decode(data).expect("Invalid base64 event data"))
+        .collect()
+}
+
 #[test]
 fn test_pinocchio_spool_pack_cu_measurement() {
     println!("\nPINOCCHIO SPOOL PACK - CU MEASUREMENT TEST");
@@ -426,3 +488,227 @@ fn test_pinocchio_spool_pack_multiple_runs() {
 
     println!("\nPINOCCHIO SPOOL PACK - MULTIPLE RUNS PASSED");
 }
+
+#[test]
+fn test_pinocchio_spool_pack_emits_event() {
+    let mut svm = LiteSVM::new();
+
+    let program_id: Pubkey = "7wApqqrfJo2dAGAKVgheccaVEgeDoqVKogtJSTbFRWn2"
+        .parse()
+        .expect("Invalid program ID");
+
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .expect("Failed to load Pinocchio tape program");
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000)
+        .expect("Failed to airdrop to payer");
+
+    let payer_pk = payer.pubkey();
+
+    let miner_address = register_miner(&mut svm, &payer, program_id, "event-miner");
+    let spool_address = create_spool(&mut svm, &payer, program_id, miner_address, 0);
+    let tape_address = set_finalized_tape(&mut svm, program_id, 3);
+
+    let test_value = [42u8; 32];
+    let mut data = vec![0x42];
+    data.extend_from_slice(&test_value);
+
+    let accounts = vec![
+        AccountMeta::new(payer_pk, true),
+        AccountMeta::new(spool_address, false),
+        AccountMeta::new_readonly(tape_address, false),
+    ];
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id,
+        accounts,
+        data,
+    };
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let metadata = svm.send_transaction(tx).expect("Pack failed");
+
+    // Expected root after packing the single leaf
+    let mut tree = TapeTree::new(&[spool_address.as_ref()]);
+    let leaf = Leaf::new(&[3u64.to_le_bytes().as_ref(), test_value.as_ref()]);
+    tree.try_add_leaf(leaf).unwrap();
+
+    let events = program_data(&metadata.logs);
+    assert_eq!(events.len(), 1, "Expected one event");
+
+    let event = SpoolPackEvent::try_from_bytes(&events[0]).unwrap();
+    assert_eq!(event.tape, 3);
+    assert_eq!(event.total_tapes, 1);
+    assert_eq!(event.root, tree.get_root().to_bytes());
+    assert_eq!(event.address, spool_address.to_bytes());
+
+    let spool_account = svm.get_account(&spool_address).unwrap();
+    let spool = Spool::unpack(&spool_account.data).unwrap();
+    assert_eq!(event.root, spool.state.get_root().to_bytes());
+
+    println!("\nSpoolPackEvent verified");
+}
+
+#[test]
+fn test_pinocchio_spool_pack_batch_cu_vs_single() {
+    let mut svm = LiteSVM::new();
+
+    let program_id: Pubkey = "7wApqqrfJo2dAGAKVgheccaVEgeDoqVKogtJSTbFRWn2"
+        .parse()
+        .expect("Invalid program ID");
+
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .expect("Failed to load Pinocchio tape program");
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000)
+        .expect("Failed to airdrop to payer");
+
+    let payer_pk = payer.pubkey();
+
+    let miner_address = register_miner(&mut svm, &payer, program_id, "batch-miner");
+    let single_spool = create_spool(&mut svm, &payer, program_id, miner_address, 0);
+    let batch_spool = create_spool(&mut svm, &payer, program_id, miner_address, 1);
+
+    let tapes: Vec<Pubkey> = (1..=3)
+        .map(|number| set_finalized_tape(&mut svm, program_id, number))
+        .collect();
+    let values: Vec<[u8; 32]> = (1..=3).map(|i| [i as u8; 32]).collect();
+
+    // Three separate packs
+    let mut single_cus = 0;
+    for (tape_address, value) in tapes.iter().zip(&values) {
+        let mut data = vec![0x42];
+        data.extend_from_slice(value);
+
+        let ix = solana_sdk::instruction::Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(payer_pk, true),
+                AccountMeta::new(single_spool, false),
+                AccountMeta::new_readonly(*tape_address, false),
+            ],
+            data,
+        };
+
+        let blockhash = svm.latest_blockhash();
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+        let metadata = svm.send_transaction(tx).expect("Single pack failed");
+        single_cus += metadata.compute_units_consumed;
+    }
+
+    // One batched pack of the same tapes
+    let ix = pack_batch_ix(program_id, payer_pk, batch_spool, &tapes, &values);
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let metadata = svm.send_transaction(tx).expect("Batched pack failed");
+    let batch_cus = metadata.compute_units_consumed;
+
+    let events = program_data(&metadata.logs);
+    assert_eq!(events.len(), 3, "Expected one event per tape");
+    for (i, data) in events.iter().enumerate() {
+        let event = SpoolPackEvent::try_from_bytes(data).unwrap();
+        assert_eq!(event.tape, i as u64 + 1);
+        assert_eq!(event.total_tapes, i as u64 + 1);
+        assert_eq!(event.address, batch_spool.to_bytes());
+    }
+
+    // Both spools hold the same leaves, only the seeds differ
+    let mut tree = TapeTree::new(&[batch_spool.as_ref()]);
+    for (number, value) in (1u64..=3).zip(&values) {
+        let leaf = Leaf::new(&[number.to_le_bytes().as_ref(), value.as_ref()]);
+        tree.try_add_leaf(leaf).unwrap();
+    }
+
+    let batch_account = svm.get_account(&batch_spool).unwrap();
+    let batch = Spool::unpack(&batch_account.data).unwrap();
+    assert_eq!(batch.total_tapes, 3);
+    assert_eq!(
+        batch.state.get_root().to_bytes(),
+        tree.get_root().to_bytes()
+    );
+
+    let single_account = svm.get_account(&single_spool).unwrap();
+    let single = Spool::unpack(&single_account.data).unwrap();
+    assert_eq!(single.total_tapes, batch.total_tapes);
+    assert_eq!(single.state.get_leaf_count(), batch.state.get_leaf_count());
+
+    println!("\n=== PINOCCHIO SPOOL PACK BATCH ===");
+    println!("3 single packs: {} CUs", single_cus);
+    println!("1 batched pack: {} CUs", batch_cus);
+
+    assert!(
+        batch_cus < single_cus,
+        "Batching should be cheaper than separate packs"
+    );
+}
+
+#[test]
+fn test_pinocchio_spool_pack_batch_rejects_invalid() {
+    let mut svm = LiteSVM::new();
+
+    let program_id: Pubkey = "7wApqqrfJo2dAGAKVgheccaVEgeDoqVKogtJSTbFRWn2"
+        .parse()
+        .expect("Invalid program ID");
+
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .expect("Failed to load Pinocchio tape program");
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000)
+        .expect("Failed to airdrop to payer");
+
+    let payer_pk = payer.pubkey();
+
+    let miner_address = register_miner(&mut svm, &payer, program_id, "batch-reject");
+    let spool_address = create_spool(&mut svm, &payer, program_id, miner_address, 0);
+
+    let finalized = set_finalized_tape(&mut svm, program_id, 1);
+    let writing = set_finalized_tape(&mut svm, program_id, 2);
+    let mut tape_account = svm.get_account(&writing).unwrap();
+    Tape::unpack_mut(&mut tape_account.data).unwrap().state = TapeState::Writing as u64;
+    svm.set_account(writing, tape_account).unwrap();
+
+    let cases = [
+        // A tape that isn't finalized fails the whole batch
+        pack_batch_ix(
+            program_id,
+            payer_pk,
+            spool_address,
+            &[finalized, writing],
+            &[[1; 32], [2; 32]],
+        ),
+        // More values than tapes
+        pack_batch_ix(
+            program_id,
+            payer_pk,
+            spool_address,
+            &[finalized],
+            &[[1; 32], [2; 32]],
+        ),
+        // Over the batch limit
+        pack_batch_ix(
+            program_id,
+            payer_pk,
+            spool_address,
+            &[finalized; MAX_TAPES_PER_PACK + 1],
+            &[[1; 32]; MAX_TAPES_PER_PACK + 1],
+        ),
+        // Empty batch
+        pack_batch_ix(program_id, payer_pk, spool_address, &[], &[]),
+    ];
+
+    for (i, ix) in cases.into_iter().enumerate() {
+        let blockhash = svm.latest_blockhash();
+        let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+        assert!(svm.send_transaction(tx).is_err(), "Case {} should fail", i);
+        svm.expire_blockhash();
+    }
+
+    let spool_account = svm.get_account(&spool_address).unwrap();
+    let spool = Spool::unpack(&spool_account.data).unwrap();
+    assert_eq!(spool.total_tapes, 0);
+    assert_eq!(spool.state.get_leaf_count(), 0);
+}
@@ -0,0 +1,339 @@
+#![cfg(test)]
+
+use litesvm::LiteSVM;
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program, sysvar,
+    transaction::{Transaction, TransactionError},
+};
+use tape_api::{
+    consts::{MINER, NAME_LEN, SPOOL, TAPE_TREE_HEIGHT},
+    error::TapeError,
+    state::{Spool, Tape, TapeState},
+};
+use tape_utils::{
+    leaf::{hashv, Hash, Leaf},
+    tree::MerkleTree,
+};
+
+type TapeTree = MerkleTree<TAPE_TREE_HEIGHT>;
+
+/// Helper to convert string to fixed-size name array
+fn to_name(s: &str) -> [u8; NAME_LEN] {
+    let mut name = [0u8; NAME_LEN];
+    let bytes = s.as_bytes();
+    let len = bytes.len().min(NAME_LEN);
+    name[..len].copy_from_slice(&bytes[..len]);
+    name
+}
+
+fn setup() -> (LiteSVM, Keypair, Pubkey) {
+    let mut svm = LiteSVM::new();
+
+    let program_id: Pubkey = "7wApqqrfJo2dAGAKVgheccaVEgeDoqVKogtJSTbFRWn2"
+        .parse()
+        .expect("Invalid program ID");
+
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .expect("Failed to load program");
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+    (svm, payer, program_id)
+}
+
+/// Helper to sign and send a single instruction
+fn send(svm: &mut LiteSVM, payer: &Keypair, ix: Instruction) -> Result<u64, TransactionError> {
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[payer], blockhash);
+    svm.send_transaction(tx)
+        .map(|meta| meta.compute_units_consumed)
+        .map_err(|e| e.err)
+}
+
+/// Registers a miner and creates its first spool, returning the spool address
+fn create_spool(svm: &mut LiteSVM, payer: &Keypair, program_id: Pubkey) -> Pubkey {
+    let payer_pk = payer.pubkey();
+    let name_bytes = to_name("remove-miner");
+
+    let (miner_address, _) =
+        Pubkey::find_program_address(&[MINER, payer_pk.as_ref(), &name_bytes], &program_id);
+
+    let mut data = vec![0x20]; // Register discriminator
+    data.extend_from_slice(&name_bytes);
+
+    let ix = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer_pk, true),
+            AccountMeta::new(miner_address, false),
+            AccountMeta::new_readonly(sysvar::rent::ID, false),
+            AccountMeta::new_readonly(sysvar::slot_hashes::ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data,
+    };
+    send(svm, payer, ix).unwrap();
+
+    let spool_number = 0u64.to_le_bytes();
+    let (spool_address, _) =
+        Pubkey::find_program_address(&[SPOOL, miner_address.as_ref(), &spool_number], &program_id);
+
+    let mut data = vec![0x40]; // Spool create discriminator
+    data.extend_from_slice(&spool_number);
+
+    let ix = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer_pk, true),
+            AccountMeta::new(miner_address, false),
+            AccountMeta::new(spool_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(sysvar::rent::ID, false),
+        ],
+        data,
+    };
+    send(svm, payer, ix).unwrap();
+
+    spool_address
+}
+
+/// Creates a finalized tape account with the given number
+fn set_finalized_tape(svm: &mut LiteSVM, program_id: Pubkey, number: u64) -> Pubkey {
+    let tape_address = Pubkey::new_unique();
+    let mut data = vec![0u8; core::mem::size_of::<Tape>()];
+    let tape = Tape::unpack_mut(&mut data).unwrap();
+    tape.number = number;
+    tape.state = TapeState::Finalized as u64;
+
+    let account = Account {
+        lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+        data,
+        owner: program_id,
+        executable: false,
+        rent_epoch: 0,
+    };
+    svm.set_account(tape_address, account).unwrap();
+    tape_address
+}
+
+fn pack_ix(
+    program_id: Pubkey,
+    payer: Pubkey,
+    spool_address: Pubkey,
+    tape_address: Pubkey,
+    value: [u8; 32],
+) -> Instruction {
+    let mut data = vec![0x42]; // Pack discriminator
+    data.extend_from_slice(&value);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(spool_address, false),
+            AccountMeta::new_readonly(tape_address, false),
+        ],
+        data,
+    }
+}
+
+fn remove_ix(
+    program_id: Pubkey,
+    payer: Pubkey,
+    spool_address: Pubkey,
+    tape_number: u64,
+    proof: &[Hash; TAPE_TREE_HEIGHT],
+    value: [u8; 32],
+) -> Instruction {
+    let mut data = vec![0x45]; // Remove discriminator
+    data.extend_from_slice(&tape_number.to_le_bytes());
+    for node in proof {
+        data.extend_from_slice(&node.to_bytes());
+    }
+    data.extend_from_slice(&value);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(spool_address, false),
+        ],
+        data,
+    }
+}
+
+fn rollover_ix(program_id: Pubkey, signer: Pubkey, spool_address: Pubkey) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(spool_address, false),
+        ],
+        data: vec![0x46], // Rollover discriminator
+    }
+}
+
+fn spool_state(svm: &LiteSVM, spool_address: &Pubkey) -> Spool {
+    *Spool::unpack(&svm.get_account(spool_address).unwrap().data).unwrap()
+}
+
+#[test]
+fn test_pinocchio_spool_remove_cu() {
+    println!("\nPINOCCHIO SPOOL REMOVE - CU MEASUREMENT TEST");
+
+    let (mut svm, payer, program_id) = setup();
+    let spool_address = create_spool(&mut svm, &payer, program_id);
+
+    // Pack two tapes, mirroring the spool tree off-chain
+    let values = [[1u8; 32], [2u8; 32]];
+    let mut tree = TapeTree::new(&[spool_address.as_ref()]);
+    let mut leaves = Vec::new();
+    for (i, value) in values.iter().enumerate() {
+        let number = i as u64 + 1;
+        let tape_address = set_finalized_tape(&mut svm, program_id, number);
+        let ix = pack_ix(
+            program_id,
+            payer.pubkey(),
+            spool_address,
+            tape_address,
+            *value,
+        );
+        send(&mut svm, &payer, ix).unwrap();
+
+        let leaf = Leaf::new(&[number.to_le_bytes().as_ref(), value.as_ref()]);
+        tree.try_add_leaf(leaf).unwrap();
+        leaves.push(leaf);
+    }
+    let spool = spool_state(&svm, &spool_address);
+    assert_eq!((spool.total_tapes, spool.live_tapes), (2, 2));
+
+    // Remove the first tape
+    let proof = tree.get_proof_no_std::<2>(&leaves, 0).unwrap();
+    let ix = remove_ix(
+        program_id,
+        payer.pubkey(),
+        spool_address,
+        1,
+        &proof,
+        values[0],
+    );
+    let cus = send(&mut svm, &payer, ix).unwrap();
+    println!("Compute Units: {}", cus);
+
+    tree.try_remove_leaf_no_std(&proof, leaves[0]).unwrap();
+    leaves[0] = tree.get_empty_leaf();
+
+    // The cumulative count stays, only the live count drops
+    let spool = spool_state(&svm, &spool_address);
+    assert_eq!(spool.total_tapes, 2);
+    assert_eq!(spool.live_tapes, 1);
+    assert_eq!(spool.state.get_root(), tree.get_root());
+
+    // The remaining tape still proves against the new root, the removed one doesn't
+    let proof = tree.get_proof_no_std::<2>(&leaves, 1).unwrap();
+    assert!(spool.state.contains_leaf_no_std(&proof, leaves[1]));
+
+    let stale = Leaf::new(&[1u64.to_le_bytes().as_ref(), values[0].as_ref()]);
+    let proof = tree.get_proof_no_std::<2>(&leaves, 0).unwrap();
+    assert!(!spool.state.contains_leaf_no_std(&proof, stale));
+
+    println!("\nTEST PASSED - CUs: {}", cus);
+}
+
+#[test]
+fn test_pinocchio_spool_remove_rejects_bad_proof() {
+    let (mut svm, payer, program_id) = setup();
+    let spool_address = create_spool(&mut svm, &payer, program_id);
+
+    let value = [7u8; 32];
+    let tape_address = set_finalized_tape(&mut svm, program_id, 1);
+    let ix = pack_ix(
+        program_id,
+        payer.pubkey(),
+        spool_address,
+        tape_address,
+        value,
+    );
+    send(&mut svm, &payer, ix).unwrap();
+
+    // A tape that was never packed has no valid proof
+    let proof = [Hash::default(); TAPE_TREE_HEIGHT];
+    let ix = remove_ix(program_id, payer.pubkey(), spool_address, 2, &proof, value);
+    assert_eq!(
+        send(&mut svm, &payer, ix),
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TapeError::SpoolUnpackFailed as u32)
+        ))
+    );
+    let spool = spool_state(&svm, &spool_address);
+    assert_eq!((spool.total_tapes, spool.live_tapes), (1, 1));
+}
+
+#[test]
+fn test_pinocchio_spool_remove_then_rollover() {
+    let (mut svm, payer, program_id) = setup();
+    let spool_address = create_spool(&mut svm, &payer, program_id);
+
+    let values = [[1u8; 32], [2u8; 32]];
+    let mut tree = TapeTree::new(&[spool_address.as_ref()]);
+    let mut leaves = Vec::new();
+    for (i, value) in values.iter().enumerate() {
+        let number = i as u64 + 1;
+        let tape_address = set_finalized_tape(&mut svm, program_id, number);
+        let ix = pack_ix(
+            program_id,
+            payer.pubkey(),
+            spool_address,
+            tape_address,
+            *value,
+        );
+        send(&mut svm, &payer, ix).unwrap();
+
+        let leaf = Leaf::new(&[number.to_le_bytes().as_ref(), value.as_ref()]);
+        tree.try_add_leaf(leaf).unwrap();
+        leaves.push(leaf);
+    }
+
+    let proof = tree.get_proof_no_std::<2>(&leaves, 1).unwrap();
+    let ix = remove_ix(
+        program_id,
+        payer.pubkey(),
+        spool_address,
+        2,
+        &proof,
+        values[1],
+    );
+    send(&mut svm, &payer, ix).unwrap();
+    tree.try_remove_leaf_no_std(&proof, leaves[1]).unwrap();
+
+    // Rollover retires the root left by the removal
+    let ix = rollover_ix(program_id, payer.pubkey(), spool_address);
+    send(&mut svm, &payer, ix).unwrap();
+
+    let spool = spool_state(&svm, &spool_address);
+    let retired = tree.get_root().to_bytes();
+    assert_eq!(spool.history, hashv(&[&[0u8; 32], &retired]).to_bytes());
+    assert_eq!(spool.state, TapeTree::new(&[spool_address.as_ref()]));
+    assert_eq!((spool.total_tapes, spool.live_tapes), (2, 0));
+
+    // Packing after the rollover counts on from the cumulative total
+    let tape_address = set_finalized_tape(&mut svm, program_id, 3);
+    let ix = pack_ix(
+        program_id,
+        payer.pubkey(),
+        spool_address,
+        tape_address,
+        [3u8; 32],
+    );
+    send(&mut svm, &payer, ix).unwrap();
+
+    let spool = spool_state(&svm, &spool_address);
+    assert_eq!((spool.total_tapes, spool.live_tapes), (3, 1));
+}
@@ -0,0 +1,259 @@
+#![cfg(test)]
+
+use litesvm::LiteSVM;
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program, sysvar,
+    transaction::{Transaction, TransactionError},
+};
+use tape_api::{
+    consts::{MAX_TAPES_PER_SPOOL, MINER, NAME_LEN, SPOOL, TAPE_TREE_HEIGHT},
+    error::TapeError,
+    state::{Spool, Tape, TapeState},
+};
+use tape_utils::{
+    leaf::{hashv, Leaf},
+    tree::MerkleTree,
+};
+
+type TapeTree = MerkleTree<TAPE_TREE_HEIGHT>;
+
+/// Helper to convert string to fixed-size name array
+fn to_name(s: &str) -> [u8; NAME_LEN] {
+    let mut name = [0u8; NAME_LEN];
+    let bytes = s.as_bytes();
+    let len = bytes.len().min(NAME_LEN);
+    name[..len].copy_from_slice(&bytes[..len]);
+    name
+}
+
+fn setup() -> (LiteSVM, Keypair, Pubkey) {
+    let mut svm = LiteSVM::new();
+
+    let program_id: Pubkey = "7wApqqrfJo2dAGAKVgheccaVEgeDoqVKogtJSTbFRWn2"
+        .parse()
+        .expect("Invalid program ID");
+
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .expect("Failed to load program");
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+    (svm, payer, program_id)
+}
+
+/// Helper to sign and send a single instruction
+fn send(svm: &mut LiteSVM, payer: &Keypair, ix: Instruction) -> Result<u64, TransactionError> {
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[payer], blockhash);
+    svm.send_transaction(tx)
+        .map(|meta| meta.compute_units_consumed)
+        .map_err(|e| e.err)
+}
+
+/// Registers a miner and creates its first spool, returning the spool address
+fn create_spool(svm: &mut LiteSVM, payer: &Keypair, program_id: Pubkey) -> Pubkey {
+    let payer_pk = payer.pubkey();
+    let name_bytes = to_name("rollover-miner");
+
+    let (miner_address, _) =
+        Pubkey::find_program_address(&[MINER, payer_pk.as_ref(), &name_bytes], &program_id);
+
+    let mut data = vec![0x20]; // Register discriminator
+    data.extend_from_slice(&name_bytes);
+
+    let ix = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer_pk, true),
+            AccountMeta::new(miner_address, false),
+            AccountMeta::new_readonly(sysvar::rent::ID, false),
+            AccountMeta::new_readonly(sysvar::slot_hashes::ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data,
+    };
+    send(svm, payer, ix).unwrap();
+
+    let spool_number = 0u64.to_le_bytes();
+    let (spool_address, _) =
+        Pubkey::find_program_address(&[SPOOL, miner_address.as_ref(), &spool_number], &program_id);
+
+    let mut data = vec![0x40]; // Spool create discriminator
+    data.extend_from_slice(&spool_number);
+
+    let ix = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer_pk, true),
+            AccountMeta::new(miner_address, false),
+            AccountMeta::new(spool_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(sysvar::rent::ID, false),
+        ],
+        data,
+    };
+    send(svm, payer, ix).unwrap();
+
+    spool_address
+}
+
+/// Creates a finalized tape account with the given number
+fn set_finalized_tape(svm: &mut LiteSVM, program_id: Pubkey, number: u64) -> Pubkey {
+    let tape_address = Pubkey::new_unique();
+    let mut data = vec![0u8; core::mem::size_of::<Tape>()];
+    let tape = Tape::unpack_mut(&mut data).unwrap();
+    tape.number = number;
+    tape.state = TapeState::Finalized as u64;
+
+    let account = Account {
+        lamports: svm.minimum_balance_for_rent_exemption(data.len()),
+        data,
+        owner: program_id,
+        executable: false,
+        rent_epoch: 0,
+    };
+    svm.set_account(tape_address, account).unwrap();
+    tape_address
+}
+
+fn pack_ix(
+    program_id: Pubkey,
+    payer: Pubkey,
+    spool_address: Pubkey,
+    tape_address: Pubkey,
+    value: [u8; 32],
+) -> Instruction {
+    let mut data = vec![0x42]; // Pack discriminator
+    data.extend_from_slice(&value);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(spool_address, false),
+            AccountMeta::new_readonly(tape_address, false),
+        ],
+        data,
+    }
+}
+
+fn rollover_ix(program_id: Pubkey, signer: Pubkey, spool_address: Pubkey) -> Instruction {
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(spool_address, false),
+        ],
+        data: vec![0x46], // Rollover discriminator
+    }
+}
+
+fn spool_state(svm: &LiteSVM, spool_address: &Pubkey) -> Spool {
+    *Spool::unpack(&svm.get_account(spool_address).unwrap().data).unwrap()
+}
+
+/// Fills the spool past MAX_TAPES_PER_SPOOL directly, as packing that many
+/// tapes one transaction at a time would be slow
+fn fill_spool(svm: &mut LiteSVM, spool_address: &Pubkey) -> Spool {
+    let mut tree = TapeTree::new(&[spool_address.as_ref()]);
+    let count = MAX_TAPES_PER_SPOOL as u64 + 1;
+    for number in 1..=count {
+        let leaf = Leaf::new(&[number.to_le_bytes().as_ref(), [1u8; 32].as_ref()]);
+        tree.try_add_leaf(leaf).unwrap();
+    }
+
+    let mut account = svm.get_account(spool_address).unwrap();
+    let spool = Spool::unpack_mut(&mut account.data).unwrap();
+    spool.state = tree;
+    spool.total_tapes = count;
+    let filled = *spool;
+    svm.set_account(*spool_address, account).unwrap();
+    filled
+}
+
+#[test]
+fn test_pinocchio_spool_rollover_cu() {
+    println!("\nPINOCCHIO SPOOL ROLLOVER - CU MEASUREMENT TEST");
+
+    let (mut svm, payer, program_id) = setup();
+    let spool_address = create_spool(&mut svm, &payer, program_id);
+    let filled = fill_spool(&mut svm, &spool_address);
+
+    // The full spool turns away new tapes
+    let next = filled.total_tapes + 1;
+    let tape_address = set_finalized_tape(&mut svm, program_id, next);
+    let ix = pack_ix(
+        program_id,
+        payer.pubkey(),
+        spool_address,
+        tape_address,
+        [2u8; 32],
+    );
+    assert_eq!(
+        send(&mut svm, &payer, ix),
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TapeError::SpoolTooManyTapes as u32)
+        ))
+    );
+
+    let ix = rollover_ix(program_id, payer.pubkey(), spool_address);
+    let cus = send(&mut svm, &payer, ix).unwrap();
+    println!("Compute Units: {}", cus);
+
+    // The old root is kept in the chain and the tree starts over empty
+    let spool = spool_state(&svm, &spool_address);
+    let old_root = filled.state.get_root().to_bytes();
+    assert_eq!(spool.history, hashv(&[&[0u8; 32], &old_root]).to_bytes());
+    assert_eq!(spool.state, TapeTree::new(&[spool_address.as_ref()]));
+    assert_eq!(spool.total_tapes, filled.total_tapes);
+
+    // New packs succeed and keep counting from the cumulative total
+    let ix = pack_ix(
+        program_id,
+        payer.pubkey(),
+        spool_address,
+        tape_address,
+        [2u8; 32],
+    );
+    send(&mut svm, &payer, ix).unwrap();
+
+    let spool = spool_state(&svm, &spool_address);
+    assert_eq!(spool.total_tapes, next);
+    assert_eq!(spool.state.get_leaf_count(), 1);
+
+    // A second rollover extends the chain
+    let root = spool.state.get_root().to_bytes();
+    let expected = hashv(&[&spool.history, &root]).to_bytes();
+    let ix = rollover_ix(program_id, payer.pubkey(), spool_address);
+    send(&mut svm, &payer, ix).unwrap();
+    assert_eq!(spool_state(&svm, &spool_address).history, expected);
+
+    println!("\nTEST PASSED - CUs: {}", cus);
+}
+
+#[test]
+fn test_pinocchio_spool_rollover_rejects_other_signer() {
+    let (mut svm, payer, program_id) = setup();
+    let spool_address = create_spool(&mut svm, &payer, program_id);
+    let before = spool_state(&svm, &spool_address);
+
+    let intruder = Keypair::new();
+    svm.airdrop(&intruder.pubkey(), 10_000_000_000).unwrap();
+
+    let ix = rollover_ix(program_id, intruder.pubkey(), spool_address);
+    assert_eq!(
+        send(&mut svm, &intruder, ix),
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::MissingRequiredSignature
+        ))
+    );
+    assert_eq!(spool_state(&svm, &spool_address), before);
+}
@@ -0,0 +1,186 @@
+#![cfg(test)]
+
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program, sysvar,
+    transaction::{Transaction, TransactionError},
+};
+use tape_api::{
+    consts::{NAME_LEN, TAPE, WRITER},
+    error::TapeError,
+    instruction::tape::build_close_ix_data,
+    state::{Tape, TapeState},
+};
+
+/// Helper to convert string to fixed-size name array
+fn to_name(s: &str) -> [u8; NAME_LEN] {
+    let mut name = [0u8; NAME_LEN];
+    let bytes = s.as_bytes();
+    let len = bytes.len().min(NAME_LEN);
+    name[..len].copy_from_slice(&bytes[..len]);
+    name
+}
+
+fn setup() -> (LiteSVM, Keypair, Pubkey) {
+    let mut svm = LiteSVM::new();
+
+    let program_id: Pubkey = "7wApqqrfJo2dAGAKVgheccaVEgeDoqVKogtJSTbFRWn2"
+        .parse()
+        .expect("Invalid program ID");
+
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .expect("Failed to load program");
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+    (svm, payer, program_id)
+}
+
+/// Helper to create a tape through the program, returning the tape and writer addresses
+fn create_tape(
+    svm: &mut LiteSVM,
+    payer: &Keypair,
+    program_id: Pubkey,
+    tape_name: &str,
+) -> (Pubkey, Pubkey) {
+    let payer_pk = payer.pubkey();
+    let name_bytes = to_name(tape_name);
+
+    let (tape_address, _) =
+        Pubkey::find_program_address(&[TAPE, payer_pk.as_ref(), &name_bytes], &program_id);
+    let (writer_address, _) =
+        Pubkey::find_program_address(&[WRITER, tape_address.as_ref()], &program_id);
+
+    let mut data = vec![0x10]; // Create discriminator
+    data.extend_from_slice(&name_bytes);
+
+    let ix = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer_pk, true),
+            AccountMeta::new(tape_address, false),
+            AccountMeta::new(writer_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(sysvar::rent::ID, false),
+        ],
+        data,
+    };
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[payer], blockhash);
+    svm.send_transaction(tx).unwrap();
+
+    (tape_address, writer_address)
+}
+
+/// Helper to send a close instruction signed by `signer`
+fn send_close(
+    svm: &mut LiteSVM,
+    signer: &Keypair,
+    program_id: Pubkey,
+    tape_address: Pubkey,
+    writer_address: Pubkey,
+) -> Result<u64, TransactionError> {
+    let mut data = [0u8; 1];
+    let data_len = build_close_ix_data(&mut data);
+
+    let ix = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(signer.pubkey(), true),
+            AccountMeta::new(tape_address, false),
+            AccountMeta::new(writer_address, false),
+        ],
+        data: data[..data_len].to_vec(),
+    };
+
+    let blockhash = svm.latest_blockhash();
+    let tx =
+        Transaction::new_signed_with_payer(&[ix], Some(&signer.pubkey()), &[signer], blockhash);
+    svm.send_transaction(tx)
+        .map(|meta| meta.compute_units_consumed)
+        .map_err(|e| e.err)
+}
+
+fn lamports(svm: &LiteSVM, address: &Pubkey) -> u64 {
+    svm.get_account(address)
+        .map_or(0, |account| account.lamports)
+}
+
+#[test]
+fn test_pinocchio_tape_close_cu() {
+    println!("\nPINOCCHIO TAPE CLOSE - CU MEASUREMENT TEST");
+
+    let (mut svm, payer, program_id) = setup();
+    let (tape_address, writer_address) = create_tape(&mut svm, &payer, program_id, "close-test");
+
+    let tape_rent = lamports(&svm, &tape_address);
+    let writer_rent = lamports(&svm, &writer_address);
+    let payer_before = lamports(&svm, &payer.pubkey());
+
+    let cus = send_close(&mut svm, &payer, program_id, tape_address, writer_address).unwrap();
+    println!("Compute Units: {}", cus);
+
+    // Both accounts are gone
+    assert_eq!(lamports(&svm, &tape_address), 0);
+    assert_eq!(lamports(&svm, &writer_address), 0);
+
+    // The author got all of the rent back, minus the transaction fee
+    let payer_after = lamports(&svm, &payer.pubkey());
+    assert_eq!(payer_after, payer_before + tape_rent + writer_rent - 5000);
+
+    println!("\nTEST PASSED - CUs: {}", cus);
+}
+
+#[test]
+fn test_pinocchio_tape_close_rejects_finalized_tape() {
+    let (mut svm, payer, program_id) = setup();
+    let (tape_address, writer_address) = create_tape(&mut svm, &payer, program_id, "finalized");
+
+    // Mark the tape finalized in place
+    let mut account = svm.get_account(&tape_address).unwrap();
+    Tape::unpack_mut(&mut account.data).unwrap().state = TapeState::Finalized as u64;
+    svm.set_account(tape_address, account).unwrap();
+
+    let result = send_close(&mut svm, &payer, program_id, tape_address, writer_address);
+    assert_eq!(
+        result,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TapeError::UnexpectedState as u32)
+        ))
+    );
+    assert!(lamports(&svm, &tape_address) > 0);
+    assert!(lamports(&svm, &writer_address) > 0);
+}
+
+#[test]
+fn test_pinocchio_tape_close_rejects_other_signer() {
+    let (mut svm, payer, program_id) = setup();
+    let (tape_address, writer_address) = create_tape(&mut svm, &payer, program_id, "not-yours");
+
+    let intruder = Keypair::new();
+    svm.airdrop(&intruder.pubkey(), 1_000_000_000).unwrap();
+
+    let result = send_close(
+        &mut svm,
+        &intruder,
+        program_id,
+        tape_address,
+        writer_address,
+    );
+    assert_eq!(
+        result,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TapeError::Unauthorized as u32)
+        ))
+    );
+    assert!(lamports(&svm, &tape_address) > 0);
+    assert!(lamports(&svm, &writer_address) > 0);
+}
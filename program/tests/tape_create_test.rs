@@ -2,15 +2,16 @@
 
 use litesvm::LiteSVM;
 use solana_sdk::{
-    instruction::{AccountMeta, Instruction},
+    instruction::{AccountMeta, Instruction, InstructionError},
     pubkey::Pubkey,
     signature::Keypair,
     signer::Signer,
     system_program, sysvar,
-    transaction::Transaction,
+    transaction::{Transaction, TransactionError},
 };
 use tape_api::{
     consts::{HEADER_SIZE, NAME_LEN},
+    error::TapeError,
     pda::{tape_pda, writer_pda},
     state::{Tape, TapeState, Writer},
     utils::to_name,
@@ -344,3 +345,108 @@ fn test_pinocchio_cu_measurements() {
         println!("Savings: {} CUs ({:.1}%)", savings, percent);
     }
 }
+
+#[test]
+fn test_pinocchio_tape_create_rejects_empty_name() {
+    let (mut svm, program_id) = setup_svm_with_program();
+    let payer = create_payer(&mut svm);
+    let payer_pk = payer.pubkey();
+    let payer_arr: [u8; 32] = payer_pk.to_bytes();
+
+    // An all-zero name is rejected
+    let name_bytes = [0u8; NAME_LEN];
+    let (tape_arr, _) = tape_pda(payer_arr, &name_bytes);
+    let (writer_arr, _) = writer_pda(tape_arr);
+
+    let ix = build_pinocchio_create_ix(
+        payer_pk,
+        Pubkey::from(tape_arr),
+        Pubkey::from(writer_arr),
+        name_bytes,
+        program_id,
+    );
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let result = svm.send_transaction(tx);
+
+    assert_eq!(
+        result.map_err(|e| e.err).err(),
+        Some(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TapeError::InvalidName as u32)
+        )),
+        "Empty name should be rejected"
+    );
+    assert!(svm.get_account(&Pubkey::from(tape_arr)).is_none());
+
+    // A regular name still succeeds
+    let name_bytes = to_name("named-tape");
+    let (tape_arr, _) = tape_pda(payer_arr, &name_bytes);
+    let (writer_arr, _) = writer_pda(tape_arr);
+
+    let ix = build_pinocchio_create_ix(
+        payer_pk,
+        Pubkey::from(tape_arr),
+        Pubkey::from(writer_arr),
+        name_bytes,
+        program_id,
+    );
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let result = svm.send_transaction(tx);
+
+    assert!(result.is_ok(), "Transaction failed: {:?}", result.err());
+}
+
+#[test]
+fn test_pinocchio_tape_create_rejects_underfunded_signer() {
+    const TX_FEE: u64 = 5_000;
+
+    let (mut svm, program_id) = setup_svm_with_program();
+
+    // Fund the payer one lamport short of the tape + writer rent
+    let required = svm.minimum_balance_for_rent_exemption(core::mem::size_of::<Tape>())
+        + svm.minimum_balance_for_rent_exemption(core::mem::size_of::<Writer>());
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), TX_FEE + required - 1)
+        .expect("Failed to airdrop to payer");
+    let payer_pk = payer.pubkey();
+    let payer_arr: [u8; 32] = payer_pk.to_bytes();
+
+    let name_bytes = to_name("underfunded");
+    let (tape_arr, _) = tape_pda(payer_arr, &name_bytes);
+    let (writer_arr, _) = writer_pda(tape_arr);
+
+    let ix = build_pinocchio_create_ix(
+        payer_pk,
+        Pubkey::from(tape_arr),
+        Pubkey::from(writer_arr),
+        name_bytes,
+        program_id,
+    );
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let failure = svm.send_transaction(tx).unwrap_err();
+
+    assert_eq!(
+        failure.err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TapeError::InsufficientFunds as u32)
+        ),
+        "Underfunded signer should get a program error, not a system program one"
+    );
+    assert!(
+        failure
+            .meta
+            .logs
+            .iter()
+            .any(|log| log.contains("short 1 lamports")),
+        "Shortfall should be logged"
+    );
+    assert!(svm.get_account(&Pubkey::from(tape_arr)).is_none());
+    assert!(svm.get_account(&Pubkey::from(writer_arr)).is_none());
+}
@@ -0,0 +1,160 @@
+#![cfg(test)]
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use litesvm::LiteSVM;
+use solana_sdk::{
+    account::Account,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program, sysvar,
+    transaction::Transaction,
+};
+use tape_api::{
+    consts::{ARCHIVE_ADDRESS, NAME_LEN, TAPE, WRITER},
+    event::{CreateEvent, FinalizeEvent},
+    state::{Archive, Tape, TapeState, Writer},
+};
+
+/// Helper to convert string to fixed-size name array
+fn to_name(s: &str) -> [u8; NAME_LEN] {
+    let mut name = [0u8; NAME_LEN];
+    let bytes = s.as_bytes();
+    let len = bytes.len().min(NAME_LEN);
+    name[..len].copy_from_slice(&bytes[..len]);
+    name
+}
+
+fn setup() -> (LiteSVM, Keypair, Pubkey) {
+    let mut svm = LiteSVM::new();
+
+    let program_id: Pubkey = "7wApqqrfJo2dAGAKVgheccaVEgeDoqVKogtJSTbFRWn2"
+        .parse()
+        .expect("Invalid program ID");
+
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .expect("Failed to load program");
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+    (svm, payer, program_id)
+}
+
+/// Decodes the `Program data:` entries emitted via sol_log_data
+fn program_data(logs: &[String]) -> Vec<Vec<u8>> {
+    logs.iter()
+        .filter_map(|log| log.strip_prefix("Program data: "))
+        .map(|data| STANDARD.decode(data).expect("Invalid base64 event data"))
+        .collect()
+}
+
+/// Helper to sign and send a single instruction, returning the emitted events
+fn send(svm: &mut LiteSVM, payer: &Keypair, ix: Instruction) -> Vec<Vec<u8>> {
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[payer], blockhash);
+    let meta = svm.send_transaction(tx).unwrap();
+    program_data(&meta.logs)
+}
+
+/// Gives the tape enough rent to be finalized and moves it to Writing,
+/// returning the writer's root that finalize will archive
+fn prepare_for_finalize(
+    svm: &mut LiteSVM,
+    tape_address: &Pubkey,
+    writer_address: &Pubkey,
+) -> [u8; 32] {
+    const BLOCKS_PER_YEAR: u64 = 525_600;
+
+    let writer_account = svm.get_account(writer_address).unwrap();
+    let root = Writer::unpack(&writer_account.data)
+        .unwrap()
+        .state
+        .get_root()
+        .to_bytes();
+
+    let mut tape_account = svm.get_account(tape_address).unwrap();
+    let tape = Tape::unpack_mut(&mut tape_account.data).unwrap();
+    tape.state = TapeState::Writing as u64;
+    tape.total_segments = 1;
+    tape.merkle_root = root;
+
+    let rent_needed = tape.rent_per_block() * BLOCKS_PER_YEAR;
+    tape.balance = rent_needed;
+    tape_account.lamports += rent_needed;
+    svm.set_account(*tape_address, tape_account).unwrap();
+    root
+}
+
+#[test]
+fn test_tape_create_and_finalize_emit_events() {
+    let (mut svm, payer, program_id) = setup();
+    let payer_pk = payer.pubkey();
+    let name_bytes = to_name("events");
+
+    let (tape_address, _) =
+        Pubkey::find_program_address(&[TAPE, payer_pk.as_ref(), &name_bytes], &program_id);
+    let (writer_address, _) =
+        Pubkey::find_program_address(&[WRITER, tape_address.as_ref()], &program_id);
+
+    // Create emits the new tape address and its authority
+    let mut data = vec![0x10]; // Create discriminator
+    data.extend_from_slice(&name_bytes);
+
+    let ix = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer_pk, true),
+            AccountMeta::new(tape_address, false),
+            AccountMeta::new(writer_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(sysvar::rent::ID, false),
+        ],
+        data,
+    };
+
+    let events = send(&mut svm, &payer, ix);
+    assert_eq!(events.len(), 1, "Expected a single event");
+
+    let event = CreateEvent::try_from_bytes(&events[0]).unwrap();
+    let tape_account = svm.get_account(&tape_address).unwrap();
+    let tape = Tape::unpack(&tape_account.data).unwrap();
+    assert_eq!(event.address, tape_address.to_bytes());
+    assert_eq!(event.authority, payer_pk.to_bytes());
+    assert_eq!(event.first_slot, tape.first_slot);
+
+    // Finalize emits the assigned tape number and the final root
+    let root = prepare_for_finalize(&mut svm, &tape_address, &writer_address);
+
+    let archive = Account {
+        lamports: 10_000_000,
+        data: vec![0; core::mem::size_of::<Archive>()],
+        owner: program_id,
+        executable: false,
+        rent_epoch: 0,
+    };
+    svm.set_account(Pubkey::from(ARCHIVE_ADDRESS), archive)
+        .unwrap();
+
+    let ix = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer_pk, true),
+            AccountMeta::new(tape_address, false),
+            AccountMeta::new(writer_address, false),
+            AccountMeta::new(Pubkey::from(ARCHIVE_ADDRESS), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(sysvar::rent::ID, false),
+        ],
+        data: vec![0x13], // Finalize discriminator
+    };
+
+    let events = send(&mut svm, &payer, ix);
+    assert_eq!(events.len(), 1, "Expected a single event");
+
+    let event = FinalizeEvent::try_from_bytes(&events[0]).unwrap();
+    assert_eq!(event.tape, 1);
+    assert_eq!(event.merkle_root, root);
+    assert_eq!(event.address, tape_address.to_bytes());
+}
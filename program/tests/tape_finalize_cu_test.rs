@@ -2,16 +2,26 @@
 
 use litesvm::LiteSVM;
 use solana_sdk::{
-    instruction::{AccountMeta, Instruction},
+    instruction::{AccountMeta, Instruction, InstructionError},
     pubkey::Pubkey,
     signature::Keypair,
     signer::Signer,
     system_program, sysvar,
-    transaction::Transaction,
+    transaction::{Transaction, TransactionError},
 };
 use tape_api::{
-    consts::{ARCHIVE_ADDRESS, HEADER_SIZE, NAME_LEN, TAPE, WRITER},
+    consts::{
+        ARCHIVE_ADDRESS, HEADER_SIZE, NAME_LEN, SEGMENT_SIZE, TAPE, WRITER, WRITER_SHARD_SEGMENTS,
+    },
+    error::TapeError,
+    instruction::tape::{build_create_shard_ix_data, build_finalize_with_header_ix_data},
+    rent::min_finalization_rent,
     state::{Archive, Tape, TapeState, Writer},
+    utils::{compute_leaf, padded_array, to_segment, verify_tape},
+};
+use tape_utils::{
+    leaf::{Hash, Leaf},
+    tree::{hash_left_right, verify_at, SEGMENT_TREE_ZEROS_18},
 };
 
 /// Helper to convert string to fixed-size name array
@@ -60,13 +70,134 @@ fn create_tape(
     (tape_address, writer_address)
 }
 
-/// Helper to manually set tape to Writing state
+/// Helper to manually set tape to Writing state, keeping its root in sync
+/// with the writer
 fn set_tape_writing_state(svm: &mut LiteSVM, tape_address: &Pubkey) {
     let mut tape_account = svm.get_account(tape_address).unwrap();
+    let (writer_address, _) =
+        Pubkey::find_program_address(&[WRITER, tape_address.as_ref()], &tape_account.owner);
+    let writer_account = svm.get_account(&writer_address).unwrap();
+    let writer = Writer::unpack(&writer_account.data).unwrap();
+
     let tape_mut = Tape::unpack_mut(&mut tape_account.data).unwrap();
     tape_mut.state = TapeState::Writing as u64;
     tape_mut.total_segments = 1; // Add at least one segment
+    tape_mut.merkle_root = writer.state.get_root().to_bytes();
+    svm.set_account(*tape_address, tape_account.into()).unwrap();
+}
+
+/// Helper to fund a tape for finalization and create the archive
+fn prepare_finalize(svm: &mut LiteSVM, program_id: Pubkey, tape_address: &Pubkey) {
+    const BLOCKS_PER_YEAR: u64 = 525_600;
+    let mut tape_account = svm.get_account(tape_address).unwrap();
+    let tape_mut = Tape::unpack_mut(&mut tape_account.data).unwrap();
+    let rent_needed = tape_mut.rent_per_block() * BLOCKS_PER_YEAR;
+    tape_mut.balance = rent_needed;
+    tape_account.lamports += rent_needed;
     svm.set_account(*tape_address, tape_account.into()).unwrap();
+
+    let archive_account = solana_sdk::account::Account {
+        lamports: 10_000_000,
+        data: vec![0; core::mem::size_of::<Archive>()],
+        owner: program_id,
+        executable: false,
+        rent_epoch: 0,
+    };
+    svm.set_account(Pubkey::from(ARCHIVE_ADDRESS), archive_account.into())
+        .unwrap();
+}
+
+/// Helper to send a finalize instruction signed by `signer`
+fn send_finalize(
+    svm: &mut LiteSVM,
+    signer: &Keypair,
+    program_id: Pubkey,
+    tape_address: Pubkey,
+    writer_address: Pubkey,
+) -> Result<(), TransactionError> {
+    send_finalize_with_data(
+        svm,
+        signer,
+        program_id,
+        tape_address,
+        writer_address,
+        vec![0x13], // Finalize discriminator
+    )
+}
+
+/// Helper to send a finalize instruction with raw instruction data
+fn send_finalize_with_data(
+    svm: &mut LiteSVM,
+    signer: &Keypair,
+    program_id: Pubkey,
+    tape_address: Pubkey,
+    writer_address: Pubkey,
+    data: Vec<u8>,
+) -> Result<(), TransactionError> {
+    let ix = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(signer.pubkey(), true),
+            AccountMeta::new(tape_address, false),
+            AccountMeta::new(writer_address, false),
+            AccountMeta::new(Pubkey::from(ARCHIVE_ADDRESS), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(sysvar::rent::ID, false),
+        ],
+        data,
+    };
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&signer.pubkey()), &[signer], blockhash);
+    svm.send_transaction(tx).map(|_| ()).map_err(|e| e.err)
+}
+
+/// Helper to send a finalize instruction passing writer shards 1.. after the sysvars
+fn send_finalize_with_shards(
+    svm: &mut LiteSVM,
+    signer: &Keypair,
+    program_id: Pubkey,
+    tape_address: Pubkey,
+    writer_address: Pubkey,
+    shards: &[Pubkey],
+) -> Result<(), TransactionError> {
+    let mut accounts = vec![
+        AccountMeta::new(signer.pubkey(), true),
+        AccountMeta::new(tape_address, false),
+        AccountMeta::new(writer_address, false),
+        AccountMeta::new(Pubkey::from(ARCHIVE_ADDRESS), false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(sysvar::rent::ID, false),
+    ];
+    accounts.extend(shards.iter().map(|shard| AccountMeta::new(*shard, false)));
+
+    let ix = Instruction {
+        program_id,
+        accounts,
+        data: vec![0x13], // Finalize discriminator
+    };
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&signer.pubkey()), &[signer], blockhash);
+    svm.send_transaction(tx).map(|_| ()).map_err(|e| e.err)
+}
+
+/// Proof for `leaves[index]` in a segment tree holding `leaves`
+fn segment_proof(leaves: &[Leaf], index: usize) -> Vec<[u8; 32]> {
+    let mut layer: Vec<Hash> = leaves.iter().map(|leaf| Hash::from(*leaf)).collect();
+    let mut index = index;
+    let mut proof = Vec::new();
+
+    for zero in SEGMENT_TREE_ZEROS_18 {
+        proof.push(layer.get(index ^ 1).copied().unwrap_or(zero).to_bytes());
+        layer = layer
+            .chunks(2)
+            .map(|pair| hash_left_right(pair[0], pair.get(1).copied().unwrap_or(zero)))
+            .collect();
+        index /= 2;
+    }
+
+    proof
 }
 
 #[test]
@@ -297,3 +428,567 @@ fn test_pinocchio_tape_finalize_multiple_runs() {
     println!("Avg CUs: {}", avg);
     println!();
 }
+
+#[test]
+fn test_pinocchio_tape_finalize_downloaded_segments_match_root() {
+    let mut svm = LiteSVM::new();
+
+    let program_id: Pubkey = "7wApqqrfJo2dAGAKVgheccaVEgeDoqVKogtJSTbFRWn2"
+        .parse()
+        .expect("Invalid program ID");
+
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .expect("Failed to load program");
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+    let payer_pk = payer.pubkey();
+
+    let (tape_address, writer_address) = create_tape(&mut svm, &payer, program_id, "verify");
+
+    // Write a tape spanning several segments, the last one partial
+    let contents: Vec<u8> = (0..SEGMENT_SIZE * 3 + 17).map(|i| i as u8).collect();
+
+    let mut write_data = vec![0x11]; // Write discriminator
+    write_data.extend_from_slice(&contents);
+
+    let ix = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer_pk, true),
+            AccountMeta::new(tape_address, false),
+            AccountMeta::new(writer_address, false),
+        ],
+        data: write_data,
+    };
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    svm.send_transaction(tx).expect("Write failed");
+
+    // Fund the tape so it can be finalized
+    let mut tape_account = svm.get_account(&tape_address).unwrap();
+    let rent_needed = {
+        let tape_mut = Tape::unpack_mut(&mut tape_account.data).unwrap();
+        assert_eq!(tape_mut.total_segments, 4);
+        let rent_needed = tape_mut.rent_per_block() * 525_600;
+        tape_mut.balance = rent_needed;
+        rent_needed
+    };
+    tape_account.lamports += rent_needed;
+    svm.set_account(tape_address, tape_account.into()).unwrap();
+
+    let archive_address = Pubkey::from(ARCHIVE_ADDRESS);
+    svm.set_account(
+        archive_address,
+        solana_sdk::account::Account {
+            lamports: 10_000_000,
+            data: vec![0; core::mem::size_of::<Archive>()],
+            owner: program_id,
+            executable: false,
+            rent_epoch: 0,
+        }
+        .into(),
+    )
+    .unwrap();
+
+    let ix = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer_pk, true),
+            AccountMeta::new(tape_address, false),
+            AccountMeta::new(writer_address, false),
+            AccountMeta::new(archive_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(sysvar::rent::ID, false),
+        ],
+        data: vec![0x13], // Finalize discriminator
+    };
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    svm.send_transaction(tx).expect("Finalize failed");
+
+    let tape_account = svm.get_account(&tape_address).unwrap();
+    let tape = Tape::unpack(&tape_account.data).unwrap();
+    assert_eq!(tape.state, TapeState::Finalized as u64);
+
+    // Reassemble the tape the way a downloader would
+    let mut segments: Vec<[u8; SEGMENT_SIZE]> = contents
+        .chunks(SEGMENT_SIZE)
+        .map(|chunk| to_segment(chunk).unwrap())
+        .collect();
+
+    assert!(
+        verify_tape(&tape.merkle_root, &segments),
+        "Downloaded segments should reproduce the finalized root"
+    );
+
+    // A single flipped bit is detected
+    segments[2][5] ^= 1;
+    assert!(
+        !verify_tape(&tape.merkle_root, &segments),
+        "A corrupted segment should not reproduce the root"
+    );
+}
+
+#[test]
+fn test_pinocchio_tape_finalize_rejects_non_authority() {
+    let mut svm = LiteSVM::new();
+    let program_id: Pubkey = "7wApqqrfJo2dAGAKVgheccaVEgeDoqVKogtJSTbFRWn2"
+        .parse()
+        .unwrap();
+
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .unwrap();
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+    let (tape_address, writer_address) = create_tape(&mut svm, &payer, program_id, "owned");
+    set_tape_writing_state(&mut svm, &tape_address);
+
+    let intruder = Keypair::new();
+    svm.airdrop(&intruder.pubkey(), 10_000_000_000).unwrap();
+
+    let result = send_finalize(
+        &mut svm,
+        &intruder,
+        program_id,
+        tape_address,
+        writer_address,
+    );
+    assert_eq!(
+        result,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TapeError::Unauthorized as u32)
+        ))
+    );
+}
+
+#[test]
+fn test_pinocchio_tape_finalize_rejects_mismatched_writer() {
+    let mut svm = LiteSVM::new();
+    let program_id: Pubkey = "7wApqqrfJo2dAGAKVgheccaVEgeDoqVKogtJSTbFRWn2"
+        .parse()
+        .unwrap();
+
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .unwrap();
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+    let (tape_address, _) = create_tape(&mut svm, &payer, program_id, "tape-a");
+    let (_, other_writer) = create_tape(&mut svm, &payer, program_id, "tape-b");
+    set_tape_writing_state(&mut svm, &tape_address);
+
+    let result = send_finalize(&mut svm, &payer, program_id, tape_address, other_writer);
+    assert_eq!(
+        result,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::InvalidSeeds
+        ))
+    );
+}
+
+#[test]
+fn test_pinocchio_tape_finalize_checks_root_after_write() {
+    let mut svm = LiteSVM::new();
+    let program_id: Pubkey = "7wApqqrfJo2dAGAKVgheccaVEgeDoqVKogtJSTbFRWn2"
+        .parse()
+        .unwrap();
+
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .unwrap();
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+    let payer_pk = payer.pubkey();
+
+    let (tape_address, writer_address) = create_tape(&mut svm, &payer, program_id, "desynced");
+
+    let mut write_data = vec![0x11]; // Write discriminator
+    write_data.extend_from_slice(&[42; SEGMENT_SIZE * 2]);
+
+    let ix = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer_pk, true),
+            AccountMeta::new(tape_address, false),
+            AccountMeta::new(writer_address, false),
+        ],
+        data: write_data,
+    };
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    svm.send_transaction(tx).expect("Write failed");
+
+    prepare_finalize(&mut svm, program_id, &tape_address);
+
+    // Corrupt the tape's root so it no longer matches the writer
+    let original = svm.get_account(&tape_address).unwrap();
+    let mut corrupted = original.clone();
+    Tape::unpack_mut(&mut corrupted.data).unwrap().merkle_root[0] ^= 1;
+    svm.set_account(tape_address, corrupted).unwrap();
+
+    let result = send_finalize(&mut svm, &payer, program_id, tape_address, writer_address);
+    assert_eq!(
+        result,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TapeError::RootMismatch as u32)
+        ))
+    );
+
+    let tape_account = svm.get_account(&tape_address).unwrap();
+    let tape = Tape::unpack(&tape_account.data).unwrap();
+    assert_eq!(tape.state, TapeState::Writing as u64);
+    assert!(svm.get_account(&writer_address).is_some());
+
+    // With the root restored, the same tape finalizes
+    svm.set_account(tape_address, original).unwrap();
+    svm.expire_blockhash();
+    send_finalize(&mut svm, &payer, program_id, tape_address, writer_address).unwrap();
+
+    let tape_account = svm.get_account(&tape_address).unwrap();
+    let tape = Tape::unpack(&tape_account.data).unwrap();
+    assert_eq!(tape.state, TapeState::Finalized as u64);
+    assert_eq!(tape.number, 1);
+}
+
+#[test]
+fn test_pinocchio_tape_finalize_with_header() {
+    let mut svm = LiteSVM::new();
+    let program_id: Pubkey = "7wApqqrfJo2dAGAKVgheccaVEgeDoqVKogtJSTbFRWn2"
+        .parse()
+        .unwrap();
+
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .unwrap();
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+    let (tape_address, writer_address) = create_tape(&mut svm, &payer, program_id, "labelled");
+    set_tape_writing_state(&mut svm, &tape_address);
+    prepare_finalize(&mut svm, program_id, &tape_address);
+
+    let header = padded_array::<HEADER_SIZE>(b"indexer label");
+    let mut data = [0u8; 1 + HEADER_SIZE];
+    let data_len = build_finalize_with_header_ix_data(&header, &mut data);
+
+    send_finalize_with_data(
+        &mut svm,
+        &payer,
+        program_id,
+        tape_address,
+        writer_address,
+        data[..data_len].to_vec(),
+    )
+    .unwrap();
+
+    // Both the number and the header land in the one transaction
+    let tape_account = svm.get_account(&tape_address).unwrap();
+    let tape = Tape::unpack(&tape_account.data).unwrap();
+    assert_eq!(tape.state, TapeState::Finalized as u64);
+    assert_eq!(tape.number, 1);
+    assert_eq!(tape.header, header);
+}
+
+#[test]
+fn test_pinocchio_tape_finalize_rejects_short_header() {
+    let mut svm = LiteSVM::new();
+    let program_id: Pubkey = "7wApqqrfJo2dAGAKVgheccaVEgeDoqVKogtJSTbFRWn2"
+        .parse()
+        .unwrap();
+
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .unwrap();
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+    let (tape_address, writer_address) = create_tape(&mut svm, &payer, program_id, "short");
+    set_tape_writing_state(&mut svm, &tape_address);
+    prepare_finalize(&mut svm, program_id, &tape_address);
+
+    let mut data = vec![0x13]; // Finalize discriminator
+    data.extend_from_slice(&[1; HEADER_SIZE - 1]);
+
+    let result = send_finalize_with_data(
+        &mut svm,
+        &payer,
+        program_id,
+        tape_address,
+        writer_address,
+        data,
+    );
+    assert_eq!(
+        result,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::InvalidInstructionData
+        ))
+    );
+
+    let tape_account = svm.get_account(&tape_address).unwrap();
+    let tape = Tape::unpack(&tape_account.data).unwrap();
+    assert_eq!(tape.state, TapeState::Writing as u64);
+}
+
+#[test]
+fn test_pinocchio_tape_finalize_requires_a_year_of_rent() {
+    let mut svm = LiteSVM::new();
+    let program_id: Pubkey = "7wApqqrfJo2dAGAKVgheccaVEgeDoqVKogtJSTbFRWn2"
+        .parse()
+        .unwrap();
+
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .unwrap();
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+    // Offset from the required balance, and whether finalize should pass
+    let cases = [
+        ("below", -1, false),
+        ("exact", 0, true),
+        ("above", 1_000_000, true),
+    ];
+
+    for (name, offset, accepted) in cases {
+        let (tape_address, writer_address) = create_tape(&mut svm, &payer, program_id, name);
+        set_tape_writing_state(&mut svm, &tape_address);
+        prepare_finalize(&mut svm, program_id, &tape_address);
+
+        // prepare_finalize funds exactly the required balance, move it by `offset`
+        let mut tape_account = svm.get_account(&tape_address).unwrap();
+        let tape = Tape::unpack_mut(&mut tape_account.data).unwrap();
+        assert_eq!(tape.balance, tape.required_finalize_balance());
+        tape.balance = tape.balance.checked_add_signed(offset).unwrap();
+        tape_account.lamports = tape_account.lamports.checked_add_signed(offset).unwrap();
+        svm.set_account(tape_address, tape_account.into()).unwrap();
+
+        let result = send_finalize(&mut svm, &payer, program_id, tape_address, writer_address);
+        if accepted {
+            assert!(result.is_ok(), "{}: {:?}", name, result);
+        } else {
+            assert_eq!(
+                result,
+                Err(TransactionError::InstructionError(
+                    0,
+                    InstructionError::Custom(TapeError::InsufficientRent as u32)
+                )),
+                "{}",
+                name
+            );
+        }
+    }
+}
+
+#[test]
+fn test_pinocchio_tape_finalize_combines_writer_shards() {
+    let mut svm = LiteSVM::new();
+    let program_id: Pubkey = "7wApqqrfJo2dAGAKVgheccaVEgeDoqVKogtJSTbFRWn2"
+        .parse()
+        .unwrap();
+
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .unwrap();
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+    let payer_pk = payer.pubkey();
+
+    let (tape_address, writer_address) = create_tape(&mut svm, &payer, program_id, "sharded");
+
+    // Open shard 1 next to the tape's own writer
+    let (shard_address, _) = Pubkey::find_program_address(
+        &[WRITER, tape_address.as_ref(), &1u64.to_le_bytes()],
+        &program_id,
+    );
+
+    let mut data = [0u8; 16];
+    let (data_len, built_address) =
+        build_create_shard_ix_data(&tape_address.to_bytes(), 1, &mut data);
+    assert_eq!(Pubkey::from(built_address), shard_address);
+
+    let ix = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer_pk, true),
+            AccountMeta::new_readonly(tape_address, false),
+            AccountMeta::new(shard_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(sysvar::rent::ID, false),
+        ],
+        data: data[..data_len].to_vec(),
+    };
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    svm.send_transaction(tx).expect("Create shard failed");
+
+    let shard_account = svm.get_account(&shard_address).unwrap();
+    let shard = Writer::unpack(&shard_account.data).unwrap();
+    assert_eq!(shard.tape, tape_address.to_bytes());
+    assert_eq!(shard.shard, 1);
+
+    let segment = |i: u64| {
+        let mut segment = [0xAB; SEGMENT_SIZE];
+        segment[..8].copy_from_slice(&i.to_le_bytes());
+        segment
+    };
+
+    let shard_segments = WRITER_SHARD_SEGMENTS as u64;
+    let total_segments = shard_segments + 3;
+    let leaves: Vec<Leaf> = (0..total_segments)
+        .map(|i| compute_leaf(i, &segment(i)))
+        .collect();
+
+    // Fill shard 0 up to its last two segments directly, writing them would
+    // take thousands of transactions
+    let prefilled = shard_segments - 2;
+    let mut writer_account = svm.get_account(&writer_address).unwrap();
+    let root = {
+        let writer = Writer::unpack_mut(&mut writer_account.data).unwrap();
+        writer
+            .state
+            .try_add_leaves(&leaves[..prefilled as usize])
+            .unwrap();
+        writer.state.get_root().to_bytes()
+    };
+    svm.set_account(writer_address, writer_account.into())
+        .unwrap();
+
+    let mut tape_account = svm.get_account(&tape_address).unwrap();
+    {
+        let tape = Tape::unpack_mut(&mut tape_account.data).unwrap();
+        tape.state = TapeState::Writing as u64;
+        tape.total_segments = prefilled;
+        tape.merkle_root = root;
+    }
+    svm.set_account(tape_address, tape_account.into()).unwrap();
+
+    // Shard 0 must be full before a later shard can be combined
+    let result = send_finalize_with_shards(
+        &mut svm,
+        &payer,
+        program_id,
+        tape_address,
+        writer_address,
+        &[shard_address],
+    );
+    assert_eq!(
+        result,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TapeError::ShardNotFull as u32)
+        ))
+    );
+
+    let write_ix = |writer: Pubkey, tape_is_writable: bool, first: u64, count: u64| {
+        let mut data = vec![0x11]; // Write discriminator
+        for i in first..first + count {
+            data.extend_from_slice(&segment(i));
+        }
+
+        let tape_meta = if tape_is_writable {
+            AccountMeta::new(tape_address, false)
+        } else {
+            AccountMeta::new_readonly(tape_address, false)
+        };
+
+        Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new(payer_pk, true),
+                tape_meta,
+                AccountMeta::new(writer, false),
+            ],
+            data,
+        }
+    };
+
+    // Both shards write in the same transaction, shard 1 only reading the tape
+    let ixs = [
+        write_ix(writer_address, true, prefilled, 2),
+        write_ix(shard_address, false, shard_segments, 3),
+    ];
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&ixs, Some(&payer_pk), &[&payer], blockhash);
+    let meta = svm.send_transaction(tx).expect("Sharded writes failed");
+
+    // Shard 1 appends from the start of its own slice
+    assert_eq!(meta.return_data.data, shard_segments.to_le_bytes());
+
+    // Only shard 0 moved the tape
+    let tape_account = svm.get_account(&tape_address).unwrap();
+    let tape = Tape::unpack(&tape_account.data).unwrap();
+    assert_eq!(tape.total_segments, shard_segments);
+
+    // Fund the tape for every segment it holds once combined
+    let rent_needed = min_finalization_rent(total_segments);
+    let mut tape_account = svm.get_account(&tape_address).unwrap();
+    Tape::unpack_mut(&mut tape_account.data).unwrap().balance = rent_needed;
+    tape_account.lamports += rent_needed;
+    svm.set_account(tape_address, tape_account.into()).unwrap();
+
+    svm.set_account(
+        Pubkey::from(ARCHIVE_ADDRESS),
+        solana_sdk::account::Account {
+            lamports: 10_000_000,
+            data: vec![0; core::mem::size_of::<Archive>()],
+            owner: program_id,
+            executable: false,
+            rent_epoch: 0,
+        }
+        .into(),
+    )
+    .unwrap();
+
+    send_finalize_with_shards(
+        &mut svm,
+        &payer,
+        program_id,
+        tape_address,
+        writer_address,
+        &[shard_address],
+    )
+    .expect("Finalize failed");
+
+    let tape_account = svm.get_account(&tape_address).unwrap();
+    let tape = Tape::unpack(&tape_account.data).unwrap();
+    assert_eq!(tape.state, TapeState::Finalized as u64);
+    assert_eq!(tape.total_segments, total_segments);
+
+    let archive_account = svm.get_account(&Pubkey::from(ARCHIVE_ADDRESS)).unwrap();
+    let archive = Archive::unpack(&archive_account.data).unwrap();
+    assert_eq!(archive.segments_stored, total_segments);
+
+    for closed in [writer_address, shard_address] {
+        let account = svm.get_account(&closed);
+        assert!(
+            account.is_none() || account.as_ref().unwrap().data.len() <= 1,
+            "Writer shards should be closed"
+        );
+    }
+
+    // The combined root proves segments from both shards
+    for index in [0, prefilled, shard_segments - 1, shard_segments, total_segments - 1] {
+        let proof = segment_proof(&leaves, index as usize);
+        assert!(
+            verify_at(tape.merkle_root, &proof, &segment(index), index),
+            "segment {index}"
+        );
+    }
+
+    // Same root as writing every segment through a single writer
+    let segments: Vec<[u8; SEGMENT_SIZE]> = (0..total_segments).map(segment).collect();
+    assert!(verify_tape(&tape.merkle_root, &segments));
+}
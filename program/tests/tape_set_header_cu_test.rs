@@ -1,16 +1,18 @@
 #![cfg(test)]
 
+use base64::{engine::general_purpose::STANDARD, Engine};
 use litesvm::LiteSVM;
 use solana_sdk::{
-    instruction::{AccountMeta, Instruction},
+    instruction::{AccountMeta, Instruction, InstructionError},
     pubkey::Pubkey,
     signature::Keypair,
     signer::Signer,
     system_program, sysvar,
-    transaction::Transaction,
+    transaction::{Transaction, TransactionError},
 };
 use tape_api::{
     consts::{HEADER_SIZE, NAME_LEN, TAPE, WRITER},
+    event::HeaderUpdateEvent,
     state::{Tape, TapeState},
 };
 
@@ -64,6 +66,34 @@ fn set_tape_writing_state(svm: &mut LiteSVM, tape_address: &Pubkey) {
     svm.set_account(*tape_address, tape_account.into()).unwrap();
 }
 
+/// Helper to build a set_header instruction
+fn build_set_header_ix(
+    program_id: Pubkey,
+    signer: Pubkey,
+    tape_address: Pubkey,
+    header: [u8; HEADER_SIZE],
+) -> Instruction {
+    let mut data = vec![0x14]; // SetHeader discriminator
+    data.extend_from_slice(&header);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(tape_address, false),
+        ],
+        data,
+    }
+}
+
+/// Decodes the `Program data:` entries emitted via sol_log_data
+fn program_data(logs: &[String]) -> Vec<Vec<u8>> {
+    logs.iter()
+        .filter_map(|log| log.strip_prefix("Program data: "))
+        .map(|data| STANDARD.decode(data).expect("Invalid base64 event data"))
+        .collect()
+}
+
 #[test]
 fn test_pinocchio_tape_set_header_cu_measurement() {
     println!("\nPINOCCHIO TAPE SET_HEADER - CU MEASUREMENT TEST\n");
@@ -232,3 +262,82 @@ fn test_pinocchio_tape_set_header_multiple_runs() {
     println!("  Avg CUs: {}", avg);
     println!();
 }
+
+#[test]
+fn test_pinocchio_tape_set_header_rejects_non_authority() {
+    let mut svm = LiteSVM::new();
+    let program_id: Pubkey = "7wApqqrfJo2dAGAKVgheccaVEgeDoqVKogtJSTbFRWn2"
+        .parse()
+        .unwrap();
+
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .unwrap();
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+    let tape_address = create_tape(&mut svm, &payer, program_id, "header-auth");
+    set_tape_writing_state(&mut svm, &tape_address);
+
+    let intruder = Keypair::new();
+    svm.airdrop(&intruder.pubkey(), 10_000_000_000).unwrap();
+
+    let ix = build_set_header_ix(
+        program_id,
+        intruder.pubkey(),
+        tape_address,
+        [0xAB; HEADER_SIZE],
+    );
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&intruder.pubkey()),
+        &[&intruder],
+        blockhash,
+    );
+    let result = svm.send_transaction(tx);
+
+    assert_eq!(
+        result.err().map(|e| e.err),
+        Some(TransactionError::InstructionError(
+            0,
+            InstructionError::MissingRequiredSignature
+        ))
+    );
+
+    let tape_account = svm.get_account(&tape_address).unwrap();
+    let tape = Tape::unpack(&tape_account.data).unwrap();
+    assert_eq!(
+        tape.header, [0u8; HEADER_SIZE],
+        "Header should be unchanged"
+    );
+}
+
+#[test]
+fn test_pinocchio_tape_set_header_emits_event() {
+    let mut svm = LiteSVM::new();
+    let program_id: Pubkey = "7wApqqrfJo2dAGAKVgheccaVEgeDoqVKogtJSTbFRWn2"
+        .parse()
+        .unwrap();
+
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .unwrap();
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+    let payer_pk = payer.pubkey();
+
+    let tape_address = create_tape(&mut svm, &payer, program_id, "header-event");
+    set_tape_writing_state(&mut svm, &tape_address);
+
+    let ix = build_set_header_ix(program_id, payer_pk, tape_address, [0xCD; HEADER_SIZE]);
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let metadata = svm.send_transaction(tx).expect("Set header failed");
+
+    let events = program_data(&metadata.logs);
+    assert_eq!(events.len(), 1, "Expected one event");
+
+    let event = HeaderUpdateEvent::try_from_bytes(&events[0]).unwrap();
+    assert_eq!(event.address, tape_address.to_bytes());
+}
@@ -0,0 +1,215 @@
+#![cfg(test)]
+
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program, sysvar,
+    transaction::{Transaction, TransactionError},
+};
+use tape_api::{
+    consts::{HEADER_SIZE, NAME_LEN, TAPE, WRITER},
+    error::TapeError,
+    instruction::tape::build_transfer_authority_ix_data,
+    state::{Tape, TapeState},
+};
+
+/// Helper to convert string to fixed-size name array
+fn to_name(s: &str) -> [u8; NAME_LEN] {
+    let mut name = [0u8; NAME_LEN];
+    let bytes = s.as_bytes();
+    let len = bytes.len().min(NAME_LEN);
+    name[..len].copy_from_slice(&bytes[..len]);
+    name
+}
+
+fn setup() -> (LiteSVM, Keypair, Pubkey) {
+    let mut svm = LiteSVM::new();
+
+    let program_id: Pubkey = "7wApqqrfJo2dAGAKVgheccaVEgeDoqVKogtJSTbFRWn2"
+        .parse()
+        .expect("Invalid program ID");
+
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .expect("Failed to load program");
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+    (svm, payer, program_id)
+}
+
+/// Helper to create tape
+fn create_tape(svm: &mut LiteSVM, payer: &Keypair, program_id: Pubkey, tape_name: &str) -> Pubkey {
+    let payer_pk = payer.pubkey();
+    let name_bytes = to_name(tape_name);
+
+    let (tape_address, _) =
+        Pubkey::find_program_address(&[TAPE, payer_pk.as_ref(), &name_bytes], &program_id);
+    let (writer_address, _) =
+        Pubkey::find_program_address(&[WRITER, tape_address.as_ref()], &program_id);
+
+    let mut data = vec![0x10]; // Create discriminator
+    data.extend_from_slice(&name_bytes);
+
+    let ix = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer_pk, true),
+            AccountMeta::new(tape_address, false),
+            AccountMeta::new(writer_address, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(sysvar::rent::ID, false),
+        ],
+        data,
+    };
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[payer], blockhash);
+    svm.send_transaction(tx).unwrap();
+
+    tape_address
+}
+
+/// Helper to manually set the tape state
+fn set_tape_state(svm: &mut LiteSVM, tape_address: &Pubkey, state: TapeState) {
+    let mut tape_account = svm.get_account(tape_address).unwrap();
+    let tape_mut = Tape::unpack_mut(&mut tape_account.data).unwrap();
+    tape_mut.state = state as u64;
+    tape_mut.total_segments = 1;
+    svm.set_account(*tape_address, tape_account).unwrap();
+}
+
+/// Helper to sign and send a single instruction
+fn send(svm: &mut LiteSVM, signer: &Keypair, ix: Instruction) -> Result<u64, TransactionError> {
+    let blockhash = svm.latest_blockhash();
+    let tx =
+        Transaction::new_signed_with_payer(&[ix], Some(&signer.pubkey()), &[signer], blockhash);
+    svm.send_transaction(tx)
+        .map(|meta| meta.compute_units_consumed)
+        .map_err(|e| e.err)
+}
+
+fn transfer_authority_ix(
+    program_id: Pubkey,
+    signer: Pubkey,
+    tape_address: Pubkey,
+    new_authority: Pubkey,
+) -> Instruction {
+    let mut data = [0u8; 33];
+    let data_len = build_transfer_authority_ix_data(&new_authority.to_bytes(), &mut data);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(tape_address, false),
+        ],
+        data: data[..data_len].to_vec(),
+    }
+}
+
+fn set_header_ix(program_id: Pubkey, signer: Pubkey, tape_address: Pubkey) -> Instruction {
+    let mut data = vec![0x14]; // SetHeader discriminator
+    data.extend_from_slice(&[0xAB; HEADER_SIZE]);
+
+    Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(signer, true),
+            AccountMeta::new(tape_address, false),
+        ],
+        data,
+    }
+}
+
+#[test]
+fn test_pinocchio_tape_transfer_authority_cu() {
+    println!("\nPINOCCHIO TAPE TRANSFER AUTHORITY - CU MEASUREMENT TEST");
+
+    let (mut svm, payer, program_id) = setup();
+    let tape_address = create_tape(&mut svm, &payer, program_id, "handover");
+    set_tape_state(&mut svm, &tape_address, TapeState::Writing);
+
+    let multisig = Keypair::new();
+    svm.airdrop(&multisig.pubkey(), 1_000_000_000).unwrap();
+
+    let ix = transfer_authority_ix(program_id, payer.pubkey(), tape_address, multisig.pubkey());
+    let cus = send(&mut svm, &payer, ix).unwrap();
+    println!("Compute Units: {}", cus);
+
+    let account = svm.get_account(&tape_address).unwrap();
+    let tape = Tape::unpack(&account.data).unwrap();
+    assert_eq!(Pubkey::from(tape.authority), multisig.pubkey());
+    assert_eq!(Pubkey::from(tape.creator), payer.pubkey());
+
+    // The old signer can no longer manage the tape
+    let ix = set_header_ix(program_id, payer.pubkey(), tape_address);
+    assert_eq!(
+        send(&mut svm, &payer, ix),
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::MissingRequiredSignature
+        ))
+    );
+
+    // The new authority can, at the same tape address
+    let ix = set_header_ix(program_id, multisig.pubkey(), tape_address);
+    send(&mut svm, &multisig, ix).unwrap();
+
+    let account = svm.get_account(&tape_address).unwrap();
+    let tape = Tape::unpack(&account.data).unwrap();
+    assert_eq!(tape.header, [0xAB; HEADER_SIZE]);
+
+    println!("\nTEST PASSED - CUs: {}", cus);
+}
+
+#[test]
+fn test_pinocchio_tape_transfer_authority_rejects_non_authority() {
+    let (mut svm, payer, program_id) = setup();
+    let tape_address = create_tape(&mut svm, &payer, program_id, "not-yours");
+
+    let intruder = Keypair::new();
+    svm.airdrop(&intruder.pubkey(), 1_000_000_000).unwrap();
+
+    let ix = transfer_authority_ix(
+        program_id,
+        intruder.pubkey(),
+        tape_address,
+        intruder.pubkey(),
+    );
+    assert_eq!(
+        send(&mut svm, &intruder, ix),
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TapeError::Unauthorized as u32)
+        ))
+    );
+}
+
+#[test]
+fn test_pinocchio_tape_transfer_authority_rejects_finalized_tape() {
+    let (mut svm, payer, program_id) = setup();
+    let tape_address = create_tape(&mut svm, &payer, program_id, "finalized");
+    set_tape_state(&mut svm, &tape_address, TapeState::Finalized);
+
+    let ix = transfer_authority_ix(
+        program_id,
+        payer.pubkey(),
+        tape_address,
+        Pubkey::new_unique(),
+    );
+    assert_eq!(
+        send(&mut svm, &payer, ix),
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TapeError::UnexpectedState as u32)
+        ))
+    );
+
+    let account = svm.get_account(&tape_address).unwrap();
+    let tape = Tape::unpack(&account.data).unwrap();
+    assert_eq!(Pubkey::from(tape.authority), payer.pubkey());
+}
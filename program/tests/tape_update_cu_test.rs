@@ -13,6 +13,7 @@ use tape_api::{
     consts::{NAME_LEN, SEGMENT_SIZE, TAPE, WRITER},
     state::{Tape, TapeState, Writer},
     types::{ProofPath, SegmentTree},
+    utils::to_segment,
 };
 use tape_utils::leaf::Leaf;
 
@@ -24,15 +25,8 @@ fn to_name(s: &str) -> [u8; NAME_LEN] {
     name
 }
 
-fn padded_array<const N: usize>(input: &[u8]) -> [u8; N] {
-    let mut out = [0u8; N];
-    let len = input.len().min(N);
-    out[..len].copy_from_slice(&input[..len]);
-    out
-}
-
 fn compute_leaf(segment_id: u64, segment: &[u8; SEGMENT_SIZE]) -> Leaf {
-    Leaf::new(&[segment_id.to_le_bytes().as_ref(), segment])
+    Leaf::from_segment(segment_id, segment)
 }
 
 fn create_tape(
@@ -135,7 +129,7 @@ fn test_pinocchio_tape_update_cu_measurement() {
         let mut writer_account = svm.get_account(&writer_address).unwrap();
         let writer_mut = Writer::unpack_mut(&mut writer_account.data).unwrap();
         let segment_number: u64 = 0;
-        let old_data = padded_array::<SEGMENT_SIZE>(initial_data);
+        let old_data = to_segment(initial_data).unwrap();
         let old_leaf = compute_leaf(segment_number, &old_data);
         writer_mut.state.try_add_leaf(old_leaf).unwrap();
         tape_mut.merkle_root = writer_mut.state.get_root().to_bytes();
@@ -148,16 +142,18 @@ fn test_pinocchio_tape_update_cu_measurement() {
 
     // Step 3: Prepare update
     let segment_number: u64 = 0;
-    let old_data = padded_array::<SEGMENT_SIZE>(initial_data);
+    let old_data = to_segment(initial_data).unwrap();
     let new_data_raw = b"Hello, UPDATED segment!";
-    let new_data = padded_array::<SEGMENT_SIZE>(new_data_raw);
+    let new_data = to_segment(new_data_raw).unwrap();
 
     // Build merkle proof
     let old_leaf = compute_leaf(segment_number, &old_data);
     let mut writer_tree = SegmentTree::new(&[tape_address.as_ref()]);
     writer_tree.try_add_leaf(old_leaf).unwrap();
 
-    let proof_hashes = writer_tree.get_proof_no_std(&[old_leaf], segment_number as usize);
+    let proof_hashes = writer_tree
+        .get_proof_no_std::<1>(&[old_leaf], segment_number as usize)
+        .unwrap();
     let proof_nodes: Vec<[u8; 32]> = proof_hashes.iter().map(|h| h.to_bytes()).collect();
 
     let proof_path = ProofPath::from_slice(&proof_nodes).unwrap();
@@ -261,7 +257,7 @@ fn test_pinocchio_tape_update_multiple_runs() {
             let mut writer_account = svm.get_account(&writer_address).unwrap();
             let writer_mut = Writer::unpack_mut(&mut writer_account.data).unwrap();
             let segment_number: u64 = 0;
-            let old_data = padded_array::<SEGMENT_SIZE>(initial_data.as_bytes());
+            let old_data = to_segment(initial_data.as_bytes()).unwrap();
             let old_leaf = compute_leaf(segment_number, &old_data);
             writer_mut.state.try_add_leaf(old_leaf).unwrap();
             tape_mut.merkle_root = writer_mut.state.get_root().to_bytes();
@@ -273,15 +269,17 @@ fn test_pinocchio_tape_update_multiple_runs() {
 
         // Prepare update
         let segment_number: u64 = 0;
-        let old_data = padded_array::<SEGMENT_SIZE>(initial_data.as_bytes());
+        let old_data = to_segment(initial_data.as_bytes()).unwrap();
         let new_data_raw = format!("Updated {}", i);
-        let new_data = padded_array::<SEGMENT_SIZE>(new_data_raw.as_bytes());
+        let new_data = to_segment(new_data_raw.as_bytes()).unwrap();
 
         let old_leaf = compute_leaf(segment_number, &old_data);
         let mut writer_tree = SegmentTree::new(&[tape_address.as_ref()]);
         writer_tree.try_add_leaf(old_leaf).unwrap();
 
-        let proof_hashes = writer_tree.get_proof_no_std(&[old_leaf], segment_number as usize);
+        let proof_hashes = writer_tree
+            .get_proof_no_std::<1>(&[old_leaf], segment_number as usize)
+            .unwrap();
         let proof_nodes: Vec<[u8; 32]> = proof_hashes.iter().map(|h| h.to_bytes()).collect();
 
         let proof_path = ProofPath::from_slice(&proof_nodes).unwrap();
@@ -327,3 +325,95 @@ fn test_pinocchio_tape_update_multiple_runs() {
     println!("Avg CUs: {}", avg);
     println!();
 }
+
+#[test]
+fn test_pinocchio_tape_write_and_update_keep_roots_in_sync() {
+    let mut svm = LiteSVM::new();
+
+    let program_id: Pubkey = "7wApqqrfJo2dAGAKVgheccaVEgeDoqVKogtJSTbFRWn2"
+        .parse()
+        .expect("Invalid program ID");
+
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .expect("Failed to load program");
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+    let payer_pk = payer.pubkey();
+
+    let (tape_address, writer_address) = create_tape(&mut svm, &payer, program_id, "sync-test");
+
+    let assert_in_sync = |svm: &LiteSVM| {
+        let tape_account = svm.get_account(&tape_address).unwrap();
+        let tape = Tape::unpack(&tape_account.data).unwrap();
+        let writer_account = svm.get_account(&writer_address).unwrap();
+        let writer = Writer::unpack(&writer_account.data).unwrap();
+        assert_eq!(
+            tape.merkle_root,
+            writer.state.get_root().to_bytes(),
+            "Tape root should track the writer root"
+        );
+        writer.state.get_root()
+    };
+
+    // Two segments written through the program
+    let segments = [
+        to_segment(b"first segment").unwrap(),
+        to_segment(b"second segment").unwrap(),
+    ];
+    write_to_tape(
+        &mut svm,
+        &payer,
+        program_id,
+        tape_address,
+        writer_address,
+        &segments.concat(),
+    );
+    assert_in_sync(&svm);
+
+    // Update the second segment with a proof against the on-chain tree
+    let leaves = [compute_leaf(0, &segments[0]), compute_leaf(1, &segments[1])];
+    let writer_account = svm.get_account(&writer_address).unwrap();
+    let mut expected_tree = Writer::unpack(&writer_account.data).unwrap().state;
+    let proof_nodes: Vec<[u8; 32]> = expected_tree
+        .get_proof_no_std::<2>(&leaves, 1)
+        .unwrap()
+        .iter()
+        .map(|h| h.to_bytes())
+        .collect();
+    let proof_path = ProofPath::from_slice(&proof_nodes).unwrap();
+
+    let segment_number: u64 = 1;
+    let new_data = to_segment(b"second segment, updated").unwrap();
+
+    let mut data = vec![0x12]; // Update discriminator
+    data.extend_from_slice(&segment_number.to_le_bytes());
+    data.extend_from_slice(&segments[1]);
+    data.extend_from_slice(&new_data);
+    data.extend_from_slice(bytemuck::bytes_of(&proof_path));
+
+    let ix = Instruction {
+        program_id,
+        accounts: vec![
+            AccountMeta::new(payer_pk, true),
+            AccountMeta::new(tape_address, false),
+            AccountMeta::new(writer_address, false),
+        ],
+        data,
+    };
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    svm.send_transaction(tx).expect("Update failed");
+
+    let root = assert_in_sync(&svm);
+
+    expected_tree
+        .try_replace_leaf_no_std(
+            &proof_nodes,
+            leaves[1],
+            compute_leaf(segment_number, &new_data),
+        )
+        .unwrap();
+    assert_eq!(root, expected_tree.get_root());
+}
@@ -2,12 +2,21 @@
 
 use litesvm::LiteSVM;
 use solana_sdk::{
-    pubkey::Pubkey, signature::Keypair, signer::Signer, system_program, transaction::Transaction,
+    instruction::InstructionError,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+    transaction::{Transaction, TransactionError},
 };
 use tape_api::{
-    consts::{ARCHIVE_ADDRESS, NAME_LEN, TAPE, WRITER},
+    consts::{ARCHIVE_ADDRESS, MAX_SEGMENTS_PER_WRITE, NAME_LEN, SEGMENT_SIZE, TAPE, WRITER},
+    error::TapeError,
     state::{Tape, TapeState, Writer},
+    types::SegmentTree,
+    utils::{compute_leaf, to_segment},
 };
+use tape_utils::tree::SEGMENT_TREE_ZEROS_18;
 
 /// Helper to convert string to fixed-size name array
 fn to_name(s: &str) -> [u8; NAME_LEN] {
@@ -265,3 +274,389 @@ fn test_pinocchio_tape_write_multiple_runs() {
 
     println!("\nPINOCCHIO TAPE WRITE - MULTIPLE RUNS PASSED");
 }
+
+/// Measure the CU saved by deriving the tape and writer PDAs from their
+/// cached bumps instead of searching with `find_program_address`
+#[test]
+fn test_pinocchio_tape_write_cached_bumps_cu() {
+    // Each rejected bump in find_program_address costs one create_program_address
+    // syscall (1500 CU), so a search ending at `bump` costs (256 - bump) * 1500.
+    const CREATE_PROGRAM_ADDRESS_CU: u64 = 1500;
+    let search_cost = |bump: u8| (256 - bump as u64) * CREATE_PROGRAM_ADDRESS_CU;
+
+    let mut svm = LiteSVM::new();
+    let program_id: Pubkey = "7wApqqrfJo2dAGAKVgheccaVEgeDoqVKogtJSTbFRWn2"
+        .parse()
+        .expect("Invalid program ID");
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .expect("Failed to load Pinocchio tape program");
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000)
+        .expect("Failed to airdrop to payer");
+    let payer_pk = payer.pubkey();
+
+    let (tape_address, writer_address) = create_tape(&mut svm, &payer, program_id, "bump-test");
+
+    // The bumps recorded at creation are the ones a search would find
+    let tape = *Tape::unpack(&svm.get_account(&tape_address).unwrap().data).unwrap();
+    let writer = *Writer::unpack(&svm.get_account(&writer_address).unwrap().data).unwrap();
+    let (_, tape_bump) = Pubkey::find_program_address(
+        &[TAPE, payer_pk.as_ref(), &to_name("bump-test")],
+        &program_id,
+    );
+    let (_, writer_bump) =
+        Pubkey::find_program_address(&[WRITER, tape_address.as_ref()], &program_id);
+    assert_eq!(tape.bump, tape_bump as u64);
+    assert_eq!(writer.bump, writer_bump as u64);
+
+    // Before: write searched for both the tape and the writer PDA
+    let saved = search_cost(tape_bump) + search_cost(writer_bump);
+
+    let mut data = vec![0x11]; // Write discriminator
+    data.extend_from_slice(b"cached bumps");
+
+    let accounts = vec![
+        solana_sdk::instruction::AccountMeta::new(payer_pk, true),
+        solana_sdk::instruction::AccountMeta::new(tape_address, false),
+        solana_sdk::instruction::AccountMeta::new(writer_address, false),
+    ];
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id,
+        accounts,
+        data,
+    };
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer_pk), &[&payer], blockhash);
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "Write failed: {:?}", result.err());
+
+    let cu_used = result.unwrap().compute_units_consumed;
+
+    println!(
+        "\nPINOCCHIO Write Compute Units (cached bumps): {}",
+        cu_used
+    );
+    println!("  Bump search avoided: ~{} CU", saved);
+    println!("  Equivalent with bump search: ~{} CU\n", cu_used + saved);
+
+    // Searching alone would have cost at least one syscall per PDA
+    assert!(saved >= 2 * CREATE_PROGRAM_ADDRESS_CU);
+}
+
+#[test]
+fn test_pinocchio_tape_write_rejects_empty_payload() {
+    let mut svm = LiteSVM::new();
+    let program_id: Pubkey = "7wApqqrfJo2dAGAKVgheccaVEgeDoqVKogtJSTbFRWn2"
+        .parse()
+        .expect("Invalid program ID");
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .expect("Failed to load Pinocchio tape program");
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000)
+        .expect("Failed to airdrop to payer");
+    let payer_pk = payer.pubkey();
+
+    let (tape_address, writer_address) = create_tape(&mut svm, &payer, program_id, "empty-write");
+
+    let write_ix = |payload: &[u8]| {
+        let mut data = vec![0x11]; // Write discriminator
+        data.extend_from_slice(payload);
+
+        solana_sdk::instruction::Instruction {
+            program_id,
+            accounts: vec![
+                solana_sdk::instruction::AccountMeta::new(payer_pk, true),
+                solana_sdk::instruction::AccountMeta::new(tape_address, false),
+                solana_sdk::instruction::AccountMeta::new(writer_address, false),
+            ],
+            data,
+        }
+    };
+
+    // Just the discriminator
+    let blockhash = svm.latest_blockhash();
+    let tx =
+        Transaction::new_signed_with_payer(&[write_ix(&[])], Some(&payer_pk), &[&payer], blockhash);
+    let result = svm.send_transaction(tx);
+    assert_eq!(
+        result.err().map(|e| e.err),
+        Some(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TapeError::EmptyWrite as u32)
+        ))
+    );
+
+    let tape_account = svm.get_account(&tape_address).unwrap();
+    let tape = Tape::unpack(&tape_account.data).unwrap();
+    assert_eq!(tape.state, TapeState::Created as u64);
+    assert_eq!(tape.total_segments, 0);
+
+    // A single byte is padded out to a full segment
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[write_ix(b"x")],
+        Some(&payer_pk),
+        &[&payer],
+        blockhash,
+    );
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "Write failed: {:?}", result.err());
+
+    let mut expected = SegmentTree::from_zeros(SEGMENT_TREE_ZEROS_18);
+    expected
+        .try_add_leaf(compute_leaf(0, &to_segment(b"x").unwrap()))
+        .unwrap();
+
+    let tape_account = svm.get_account(&tape_address).unwrap();
+    let tape = Tape::unpack(&tape_account.data).unwrap();
+    assert_eq!(tape.state, TapeState::Writing as u64);
+    assert_eq!(tape.total_segments, 1);
+    assert_eq!(tape.merkle_root, expected.get_root().to_bytes());
+}
+
+/// Sends one write and returns its compute units and the starting segment it reports
+fn send_write(
+    svm: &mut LiteSVM,
+    payer: &Keypair,
+    program_id: Pubkey,
+    tape_address: Pubkey,
+    writer_address: Pubkey,
+    payload: &[u8],
+) -> Result<(u64, u64), TransactionError> {
+    let mut data = vec![0x11]; // Write discriminator
+    data.extend_from_slice(payload);
+
+    let ix = solana_sdk::instruction::Instruction {
+        program_id,
+        accounts: vec![
+            solana_sdk::instruction::AccountMeta::new(payer.pubkey(), true),
+            solana_sdk::instruction::AccountMeta::new(tape_address, false),
+            solana_sdk::instruction::AccountMeta::new(writer_address, false),
+        ],
+        data,
+    };
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[payer], blockhash);
+    let meta = svm.send_transaction(tx).map_err(|e| e.err)?;
+
+    let start = u64::from_le_bytes(meta.return_data.data[..8].try_into().unwrap());
+    Ok((meta.compute_units_consumed, start))
+}
+
+#[test]
+fn test_pinocchio_tape_write_batched_segments_cu() {
+    let mut svm = LiteSVM::new();
+    let program_id: Pubkey = "7wApqqrfJo2dAGAKVgheccaVEgeDoqVKogtJSTbFRWn2"
+        .parse()
+        .expect("Invalid program ID");
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .expect("Failed to load Pinocchio tape program");
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000)
+        .expect("Failed to airdrop to payer");
+
+    let segments: Vec<[u8; SEGMENT_SIZE]> = (0..4u8).map(|i| [i + 1; SEGMENT_SIZE]).collect();
+
+    // Four segments in one write
+    let (batched_tape, batched_writer) = create_tape(&mut svm, &payer, program_id, "batched");
+    let (batched_cu, start) = send_write(
+        &mut svm,
+        &payer,
+        program_id,
+        batched_tape,
+        batched_writer,
+        &segments.concat(),
+    )
+    .unwrap();
+    assert_eq!(start, 0);
+
+    // The same four segments, one write each
+    let (single_tape, single_writer) = create_tape(&mut svm, &payer, program_id, "single");
+    let mut single_cu = 0;
+    for (i, segment) in segments.iter().enumerate() {
+        let (cu, start) = send_write(
+            &mut svm,
+            &payer,
+            program_id,
+            single_tape,
+            single_writer,
+            segment,
+        )
+        .unwrap();
+        assert_eq!(start, i as u64);
+        single_cu += cu;
+    }
+
+    println!("\nPINOCCHIO Write Compute Units (4 segments)");
+    println!("  One write:   {}", batched_cu);
+    println!("  Four writes: {}\n", single_cu);
+
+    // Both tapes end up with the same tree
+    let batched = *Tape::unpack(&svm.get_account(&batched_tape).unwrap().data).unwrap();
+    let single = *Tape::unpack(&svm.get_account(&single_tape).unwrap().data).unwrap();
+    assert_eq!(batched.total_segments, 4);
+    assert_eq!(batched.merkle_root, single.merkle_root);
+
+    assert!(batched_cu < single_cu);
+
+    // A follow-up write reports where it starts
+    let (_, start) = send_write(
+        &mut svm,
+        &payer,
+        program_id,
+        batched_tape,
+        batched_writer,
+        b"tail",
+    )
+    .unwrap();
+    assert_eq!(start, 4);
+}
+
+#[test]
+fn test_pinocchio_tape_write_rejects_oversized_batch() {
+    let mut svm = LiteSVM::new();
+    let program_id: Pubkey = "7wApqqrfJo2dAGAKVgheccaVEgeDoqVKogtJSTbFRWn2"
+        .parse()
+        .expect("Invalid program ID");
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .expect("Failed to load Pinocchio tape program");
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000)
+        .expect("Failed to airdrop to payer");
+
+    let (tape_address, writer_address) = create_tape(&mut svm, &payer, program_id, "oversized");
+
+    // One byte past the limit needs an extra segment
+    let payload = vec![7u8; MAX_SEGMENTS_PER_WRITE * SEGMENT_SIZE + 1];
+    let result = send_write(
+        &mut svm,
+        &payer,
+        program_id,
+        tape_address,
+        writer_address,
+        &payload,
+    );
+    assert_eq!(
+        result,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TapeError::WriteTooLarge as u32)
+        ))
+    );
+
+    // Exactly the limit is accepted
+    let payload = &payload[..MAX_SEGMENTS_PER_WRITE * SEGMENT_SIZE];
+    send_write(
+        &mut svm,
+        &payer,
+        program_id,
+        tape_address,
+        writer_address,
+        payload,
+    )
+    .unwrap();
+
+    let tape = *Tape::unpack(&svm.get_account(&tape_address).unwrap().data).unwrap();
+    assert_eq!(tape.total_segments, MAX_SEGMENTS_PER_WRITE as u64);
+}
+
+#[test]
+fn test_pinocchio_tape_write_spills_partial_segment() {
+    let mut svm = LiteSVM::new();
+    let program_id: Pubkey = "7wApqqrfJo2dAGAKVgheccaVEgeDoqVKogtJSTbFRWn2"
+        .parse()
+        .expect("Invalid program ID");
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .expect("Failed to load Pinocchio tape program");
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000)
+        .expect("Failed to airdrop to payer");
+
+    // A segment and a few bytes more is never truncated to one segment
+    let mut payload = vec![9u8; SEGMENT_SIZE];
+    payload.extend_from_slice(b"end");
+
+    let (spilled_tape, spilled_writer) = create_tape(&mut svm, &payer, program_id, "spilled");
+    send_write(
+        &mut svm,
+        &payer,
+        program_id,
+        spilled_tape,
+        spilled_writer,
+        &payload,
+    )
+    .unwrap();
+
+    // The same bytes written as a full segment and a short one
+    let (split_tape, split_writer) = create_tape(&mut svm, &payer, program_id, "split");
+    for chunk in payload.chunks(SEGMENT_SIZE) {
+        send_write(
+            &mut svm,
+            &payer,
+            program_id,
+            split_tape,
+            split_writer,
+            chunk,
+        )
+        .unwrap();
+    }
+
+    let spilled = *Tape::unpack(&svm.get_account(&spilled_tape).unwrap().data).unwrap();
+    let split = *Tape::unpack(&svm.get_account(&split_tape).unwrap().data).unwrap();
+    assert_eq!(spilled.total_segments, 2);
+    assert_eq!(spilled.merkle_root, split.merkle_root);
+
+    // The tail is stored zero-padded
+    let mut expected = SegmentTree::from_zeros(SEGMENT_TREE_ZEROS_18);
+    expected
+        .try_add_leaf(compute_leaf(0, &to_segment(&payload[..SEGMENT_SIZE]).unwrap()))
+        .unwrap();
+    expected
+        .try_add_leaf(compute_leaf(1, &to_segment(b"end").unwrap()))
+        .unwrap();
+    assert_eq!(spilled.merkle_root, expected.get_root().to_bytes());
+}
+
+#[test]
+fn test_pinocchio_tape_write_exact_segment() {
+    let mut svm = LiteSVM::new();
+    let program_id: Pubkey = "7wApqqrfJo2dAGAKVgheccaVEgeDoqVKogtJSTbFRWn2"
+        .parse()
+        .expect("Invalid program ID");
+    svm.add_program_from_file(program_id, "../target/deploy/pinnochio_tape_program.so")
+        .expect("Failed to load Pinocchio tape program");
+
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 10_000_000_000)
+        .expect("Failed to airdrop to payer");
+
+    // A payload of exactly SEGMENT_SIZE bytes is one segment, stored unpadded
+    let payload = [5u8; SEGMENT_SIZE];
+    let (tape_address, writer_address) = create_tape(&mut svm, &payer, program_id, "exact");
+    send_write(
+        &mut svm,
+        &payer,
+        program_id,
+        tape_address,
+        writer_address,
+        &payload,
+    )
+    .unwrap();
+
+    let tape = *Tape::unpack(&svm.get_account(&tape_address).unwrap().data).unwrap();
+    assert_eq!(tape.total_segments, 1);
+
+    let mut expected = SegmentTree::from_zeros(SEGMENT_TREE_ZEROS_18);
+    expected
+        .try_add_leaf(compute_leaf(0, &to_segment(&payload).unwrap()))
+        .unwrap();
+    assert_eq!(tape.merkle_root, expected.get_root().to_bytes());
+}
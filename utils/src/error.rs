@@ -3,7 +3,30 @@ pub enum BrineTreeError {
     InvalidArgument,
     TreeFull,
     InvalidProof,
-    ProofLength,
+    /// The proof depth doesn't match the tree; both lengths saturate at `u8::MAX`
+    ProofLength {
+        expected: u8,
+        got: u8,
+    },
+    BufferTooSmall,
+}
+
+impl BrineTreeError {
+    /// Builds a [`BrineTreeError::ProofLength`] from raw lengths.
+    pub const fn proof_length(expected: usize, got: usize) -> Self {
+        Self::ProofLength {
+            expected: saturate_u8(expected),
+            got: saturate_u8(got),
+        }
+    }
+}
+
+const fn saturate_u8(value: usize) -> u8 {
+    if value > u8::MAX as usize {
+        u8::MAX
+    } else {
+        value as u8
+    }
 }
 
 pub type ProgramResult = Result<(), BrineTreeError>;
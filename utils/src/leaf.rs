@@ -1,17 +1,24 @@
+use crate::error::BrineTreeError;
 use bytemuck::{Pod, Zeroable};
+use core::fmt;
 
 pub const HASH_BYTES: usize = 32;
 
 #[repr(C)]
-#[derive(Clone, Copy, PartialEq, Debug, Default, Pod, Zeroable)]
+#[derive(Clone, Copy, PartialEq, Default, Pod, Zeroable)]
 pub struct Hash {
     pub(crate) value: [u8; 32],
 }
 
 #[repr(C)]
-#[derive(Clone, Copy, PartialEq, Debug, Pod, Zeroable)]
+#[derive(Clone, Copy, PartialEq, Pod, Zeroable)]
 pub struct Leaf(Hash);
 
+// Hash and Leaf are embedded in account data; they must stay plain 32-byte arrays.
+const _: () = assert!(core::mem::size_of::<Hash>() == HASH_BYTES);
+const _: () = assert!(core::mem::size_of::<Leaf>() == HASH_BYTES);
+const _: () = assert!(core::mem::align_of::<Hash>() == 1);
+
 impl From<Hash> for [u8; HASH_BYTES] {
     fn from(from: Hash) -> Self {
         from.value
@@ -68,6 +75,68 @@ impl Hash {
     pub fn as_leaf(self) -> Leaf {
         Leaf(self)
     }
+
+    /// Compares without exiting early, so the time taken doesn't depend on
+    /// how many leading bytes match.
+    pub fn ct_eq(&self, other: &Hash) -> bool {
+        let mut diff = 0u8;
+        for (a, b) in self.value.iter().zip(other.value.iter()) {
+            diff |= a ^ b;
+        }
+        core::hint::black_box(diff) == 0
+    }
+
+    /// Lowercase hex encoding, as ASCII bytes (no_std has no String).
+    pub fn to_hex(&self) -> [u8; HASH_BYTES * 2] {
+        const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+        let mut out = [0u8; HASH_BYTES * 2];
+        for (i, byte) in self.value.iter().enumerate() {
+            out[i * 2] = DIGITS[(byte >> 4) as usize];
+            out[i * 2 + 1] = DIGITS[(byte & 0x0f) as usize];
+        }
+        out
+    }
+
+    /// Parses exactly 64 hex digits, either case.
+    pub fn from_hex(hex: &str) -> Result<Self, BrineTreeError> {
+        let hex = hex.as_bytes();
+        if hex.len() != HASH_BYTES * 2 {
+            return Err(BrineTreeError::InvalidArgument);
+        }
+
+        let mut value = [0u8; HASH_BYTES];
+        for (byte, pair) in value.iter_mut().zip(hex.chunks_exact(2)) {
+            *byte = (hex_digit(pair[0])? << 4) | hex_digit(pair[1])?;
+        }
+        Ok(Self { value })
+    }
+}
+
+fn hex_digit(c: u8) -> Result<u8, BrineTreeError> {
+    match c {
+        b'0'..=b'9' => Ok(c - b'0'),
+        b'a'..=b'f' => Ok(c - b'a' + 10),
+        b'A'..=b'F' => Ok(c - b'A' + 10),
+        _ => Err(BrineTreeError::InvalidArgument),
+    }
+}
+
+impl fmt::Display for Hash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hex = self.to_hex();
+        // to_hex only ever emits ASCII
+        f.write_str(core::str::from_utf8(&hex).map_err(|_| fmt::Error)?)
+    }
+}
+
+impl fmt::Debug for Hash {
+    // Just the first 8 bytes, enough to tell hashes apart in test output
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hex = self.to_hex();
+        let prefix = core::str::from_utf8(&hex[..16]).map_err(|_| fmt::Error)?;
+        write!(f, "Hash({}..)", prefix)
+    }
 }
 
 impl Leaf {
@@ -84,11 +153,31 @@ impl Leaf {
         Leaf(Hash::new_from_array(hasher.finalize().into()))
     }
 
+    /// Leaf of a tape segment: its id as little-endian u64, then its bytes.
+    ///
+    /// Writers and miners must agree on this encoding byte for byte, so
+    /// build segment leaves only through here.
+    pub fn from_segment(segment_id: u64, segment: &[u8]) -> Self {
+        Self::new(&[segment_id.to_le_bytes().as_ref(), segment])
+    }
+
     pub fn to_bytes(self) -> [u8; HASH_BYTES] {
         self.0.value
     }
 }
 
+impl fmt::Display for Leaf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Debug for Leaf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Leaf({:?})", self.0)
+    }
+}
+
 #[inline(always)]
 pub fn hashv(data: &[&[u8]]) -> Hash {
     let mut hasher = blake3::Hasher::new();
@@ -104,3 +193,91 @@ pub fn hash(data: &[u8]) -> Hash {
     hasher.update(data);
     Hash::new_from_array(hasher.finalize().into())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::format;
+
+    #[test]
+    fn from_segment_matches_manual_construction() {
+        let segment = [7u8; 128];
+
+        for segment_id in [0, 1, 255, 256, u64::MAX] {
+            let manual = Leaf::new(&[segment_id.to_le_bytes().as_ref(), &segment]);
+            assert_eq!(
+                Leaf::from_segment(segment_id, &segment).to_bytes(),
+                manual.to_bytes()
+            );
+        }
+
+        // The id is part of the leaf, so the same bytes elsewhere differ
+        assert_ne!(
+            Leaf::from_segment(0, &segment).to_bytes(),
+            Leaf::from_segment(1, &segment).to_bytes()
+        );
+    }
+
+    #[test]
+    fn hex_round_trips() {
+        let hash = hash(b"round trip");
+        let hex = hash.to_hex();
+        let hex = core::str::from_utf8(&hex).unwrap();
+
+        assert_eq!(Hash::from_hex(hex), Ok(hash));
+        assert_eq!(format!("{}", hash), hex);
+        assert_eq!(format!("{}", hash.as_leaf()), hex);
+        assert_eq!(format!("{:?}", hash), format!("Hash({}..)", &hex[..16]));
+
+        // Uppercase digits parse to the same hash
+        let upper = hex.to_ascii_uppercase();
+        assert_eq!(Hash::from_hex(&upper), Ok(hash));
+    }
+
+    #[test]
+    fn ct_eq_agrees_with_eq() {
+        let mut hashes = [Hash::default(); 16];
+        for (i, h) in hashes.iter_mut().enumerate() {
+            *h = hash(&[i as u8]);
+        }
+
+        for a in hashes.iter() {
+            for b in hashes.iter() {
+                assert_eq!(a.ct_eq(b), a == b);
+            }
+        }
+
+        // Hashes differing in a single bit, at either end and in between
+        let base = hash(b"adversarial");
+        assert!(base.ct_eq(&base));
+        for byte in 0..HASH_BYTES {
+            for bit in 0..8 {
+                let mut value = base.to_bytes();
+                value[byte] ^= 1 << bit;
+                let flipped = Hash::from(value);
+                assert!(!base.ct_eq(&flipped));
+                assert_eq!(base.ct_eq(&flipped), base == flipped);
+            }
+        }
+    }
+
+    #[test]
+    fn from_hex_rejects_invalid_input() {
+        let hex = hash(b"invalid").to_hex();
+        let hex = core::str::from_utf8(&hex).unwrap();
+
+        // Wrong length
+        assert_eq!(
+            Hash::from_hex(&hex[..62]),
+            Err(BrineTreeError::InvalidArgument)
+        );
+        assert_eq!(
+            Hash::from_hex(&format!("{}00", hex)),
+            Err(BrineTreeError::InvalidArgument)
+        );
+
+        // Not a hex digit
+        let bad = format!("zz{}", &hex[2..]);
+        assert_eq!(Hash::from_hex(&bad), Err(BrineTreeError::InvalidArgument));
+    }
+}
@@ -2,11 +2,11 @@
 
 use super::{
     error::{BrineTreeError, ProgramResult},
-    leaf::{hashv, Hash, Leaf},
+    leaf::{hashv, Hash, Leaf, HASH_BYTES},
     utils::check_condition,
 };
 use bytemuck::{Pod, Zeroable};
-use core::mem::MaybeUninit;
+use core::marker::PhantomData;
 
 // ============================================================================
 // PRE-COMPUTED ZERO VALUES FOR COMMON TREE HEIGHTS
@@ -132,27 +132,41 @@ extern crate std;
 #[cfg(feature = "std")]
 use std::{vec, vec::Vec};
 
+/// An append-only Merkle tree of height `N`.
+///
+/// `H` picks how sibling nodes are combined. The default [`Sorted`] makes
+/// proofs position-independent; [`OrderedMerkleTree`] binds each leaf's
+/// position into its proof instead. Both share the same account layout.
 #[repr(C)]
 #[derive(Clone, Copy, PartialEq, Debug)]
-pub struct MerkleTree<const N: usize> {
+pub struct MerkleTree<const N: usize, H: NodeHasher = Sorted> {
     pub root: Hash,
     pub filled_subtrees: [Hash; N],
     pub zero_values: [Hash; N],
     pub next_index: u64,
+    hasher: PhantomData<H>,
 }
 
-unsafe impl<const N: usize> Zeroable for MerkleTree<N> {}
-unsafe impl<const N: usize> Pod for MerkleTree<N> {}
+/// A [`MerkleTree`] hashing `left || right` without sorting.
+pub type OrderedMerkleTree<const N: usize> = MerkleTree<N, Ordered>;
 
-impl<const N: usize> MerkleTree<N> {
+unsafe impl<const N: usize, H: NodeHasher> Zeroable for MerkleTree<N, H> {}
+unsafe impl<const N: usize, H: NodeHasher> Pod for MerkleTree<N, H> {}
+
+// The Pod/Zeroable impls above are only sound if the tree has no padding:
+// root + filled_subtrees + zero_values hashes, followed by the u64 index.
+const _: () = assert!(
+    core::mem::size_of::<MerkleTree<18>>() == (1 + 2 * 18) * HASH_BYTES + 8,
+    "MerkleTree<18> has unexpected padding"
+);
+const _: () = assert!(
+    core::mem::size_of::<MerkleTree<10>>() == (1 + 2 * 10) * HASH_BYTES + 8,
+    "MerkleTree<10> has unexpected padding"
+);
+
+impl<const N: usize, H: NodeHasher> MerkleTree<N, H> {
     pub fn new(seeds: &[&[u8]]) -> Self {
-        let zeros = Self::calc_zeros(seeds);
-        Self {
-            next_index: 0,
-            root: zeros[N - 1],
-            filled_subtrees: zeros,
-            zero_values: zeros,
-        }
+        Self::from_zeros(Self::calc_zeros(seeds))
     }
 
     pub fn from_zeros(zeros: [Hash; N]) -> Self {
@@ -161,6 +175,7 @@ impl<const N: usize> MerkleTree<N> {
             root: zeros[N - 1],
             filled_subtrees: zeros,
             zero_values: zeros,
+            hasher: PhantomData,
         }
     }
 
@@ -172,6 +187,19 @@ impl<const N: usize> MerkleTree<N> {
         core::mem::size_of::<Self>()
     }
 
+    /// Returns the raw `get_size()` bytes of the tree, as stored in an account.
+    pub fn to_bytes(&self) -> &[u8] {
+        bytemuck::bytes_of(self)
+    }
+
+    /// Reinterprets bytes from [`Self::to_bytes`] as a tree without copying.
+    ///
+    /// The buffer must be exactly `get_size()` bytes and aligned for `u64`,
+    /// otherwise this fails with `InvalidArgument`.
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<&Self, BrineTreeError> {
+        bytemuck::try_from_bytes(bytes).map_err(|_| BrineTreeError::InvalidArgument)
+    }
+
     pub fn get_root(&self) -> Hash {
         self.root
     }
@@ -217,9 +245,18 @@ impl<const N: usize> MerkleTree<N> {
     }
 
     pub fn try_add_leaf(&mut self, leaf: Leaf) -> ProgramResult {
+        self.try_add_leaf_indexed(leaf).map(|_| ())
+    }
+
+    /// Appends `leaf` and returns the index it landed at.
+    ///
+    /// Fails with `TreeFull`, leaving `next_index` unchanged, once the tree is
+    /// at capacity.
+    pub fn try_add_leaf_indexed(&mut self, leaf: Leaf) -> Result<u64, BrineTreeError> {
         check_condition(self.next_index < (1u64 << N), BrineTreeError::TreeFull)?;
 
-        let mut current_index = self.next_index;
+        let index = self.next_index;
+        let mut current_index = index;
         let mut current_hash = Hash::from(leaf);
         let mut left;
         let mut right;
@@ -234,16 +271,84 @@ impl<const N: usize> MerkleTree<N> {
                 right = current_hash;
             }
 
-            current_hash = hash_left_right(left, right);
+            current_hash = H::hash_nodes(left, right);
             current_index /= 2;
         }
 
         self.root = current_hash;
         self.next_index += 1;
 
-        Ok(())
+        Ok(index)
+    }
+
+    /// Appends a contiguous run of leaves, leaving the tree exactly as repeated
+    /// `try_add_leaf` calls would.
+    ///
+    /// Every leaf but the last only hashes up through the subtrees it completes,
+    /// so the run costs about one hash per leaf instead of `N`. The last leaf then
+    /// refreshes `filled_subtrees` and `root` along its full path. Fails with
+    /// `TreeFull`, without touching the tree, if the run does not fit.
+    pub fn try_add_leaves(&mut self, leaves: &[Leaf]) -> ProgramResult {
+        let Some((last, rest)) = leaves.split_last() else {
+            return Ok(());
+        };
+
+        check_condition(
+            self.next_index.saturating_add(leaves.len() as u64) <= self.get_capacity(),
+            BrineTreeError::TreeFull,
+        )?;
+
+        for leaf in rest {
+            let mut current_index = self.next_index;
+            let mut current_hash = Hash::from(*leaf);
+
+            // A left child is not complete yet, park it and stop climbing
+            for i in 0..N {
+                if current_index % 2 == 0 {
+                    self.filled_subtrees[i] = current_hash;
+                    break;
+                }
+
+                current_hash = H::hash_nodes(self.filled_subtrees[i], current_hash);
+                current_index /= 2;
+            }
+
+            self.next_index += 1;
+        }
+
+        self.try_add_leaf(*last)
     }
 
+    /// Sets the leaf at `index`, for rebuilding a tree from out-of-order (index, leaf)
+    /// updates. Slots skipped between the current end and `index` hold the empty leaf,
+    /// and `next_index` becomes `max(next_index, index + 1)`.
+    ///
+    /// The tree only keeps the frontier of its last append, not its leaves, so a slot
+    /// below `next_index` can't be rewritten from the leaf alone and fails with
+    /// `InvalidArgument`. Feed updates in index order, or fill earlier slots of an
+    /// [`OrderedMerkleTree`] with [`OrderedMerkleTree::insert_at_with_proof`].
+    pub fn insert_at(&mut self, index: u64, leaf: Leaf) -> ProgramResult {
+        check_condition(index < self.get_capacity(), BrineTreeError::TreeFull)?;
+        check_condition(index >= self.next_index, BrineTreeError::InvalidArgument)?;
+
+        let empty_leaf = self.get_empty_leaf();
+        while self.next_index < index {
+            self.try_add_leaf(empty_leaf)?;
+        }
+
+        self.try_add_leaf(leaf)
+    }
+
+    /// Yields the first `next_index` leaves of `leaves`, skipping any zero
+    /// padding past the end of the tree, so off-chain callers can rebuild
+    /// the root from leaf data.
+    #[cfg(feature = "std")]
+    pub fn leaves_iter<'a>(&self, leaves: &'a [Leaf]) -> impl Iterator<Item = &'a Leaf> {
+        leaves.iter().take(self.next_index as usize)
+    }
+}
+
+impl<const N: usize> MerkleTree<N> {
     /// Removes a leaf from the tree using the provided proof.
     #[cfg(feature = "std")]
     pub fn try_remove<P>(&mut self, proof: &[P], data: &[&[u8]]) -> ProgramResult
@@ -308,12 +413,27 @@ impl<const N: usize> MerkleTree<N> {
         original_data: &[&[u8]],
         new_data: &[&[u8]],
     ) -> ProgramResult
+    where
+        P: Into<Hash> + Copy,
+    {
+        self.try_replace_prev_root_no_std(proof, original_data, new_data)
+            .map(|_| ())
+    }
+
+    /// Replaces a leaf in the tree with new data using the provided proof without Vec allocation.
+    /// Returns the root of the tree before the replacement.
+    pub fn try_replace_prev_root_no_std<P>(
+        &mut self,
+        proof: &[P],
+        original_data: &[&[u8]],
+        new_data: &[&[u8]],
+    ) -> Result<Hash, BrineTreeError>
     where
         P: Into<Hash> + Copy,
     {
         let original_leaf = Leaf::new(original_data);
         let new_leaf = Leaf::new(new_data);
-        self.try_replace_leaf_no_std(proof, original_leaf, new_leaf)
+        self.try_replace_leaf_prev_root_no_std(proof, original_leaf, new_leaf)
     }
 
     /// Replaces a leaf in the tree with a new leaf using the provided proof.
@@ -351,6 +471,21 @@ impl<const N: usize> MerkleTree<N> {
         original_leaf: Leaf,
         new_leaf: Leaf,
     ) -> ProgramResult
+    where
+        P: Into<Hash> + Copy,
+    {
+        self.try_replace_leaf_prev_root_no_std(proof, original_leaf, new_leaf)
+            .map(|_| ())
+    }
+
+    /// Replaces a leaf in the tree with a new leaf using the provided proof without Vec allocation.
+    /// Returns the root of the tree before the replacement.
+    pub fn try_replace_leaf_prev_root_no_std<P>(
+        &mut self,
+        proof: &[P],
+        original_leaf: Leaf,
+        new_leaf: Leaf,
+    ) -> Result<Hash, BrineTreeError>
     where
         P: Into<Hash> + Copy,
     {
@@ -363,8 +498,9 @@ impl<const N: usize> MerkleTree<N> {
                 self.filled_subtrees[i] = new_path[i];
             }
         }
+        let prev_root = self.root;
         self.root = new_root;
-        Ok(())
+        Ok(prev_root)
     }
 
     /// Checks if the proof contains the specified data.
@@ -413,7 +549,7 @@ impl<const N: usize> MerkleTree<N> {
 
     /// Checks if the proof length matches the expected depth of the tree.
     fn check_length(&self, proof: &[Hash]) -> Result<(), BrineTreeError> {
-        check_condition(proof.len() == N, BrineTreeError::ProofLength)
+        check_proof_length(proof.len(), N)
     }
 
     /// Checks if the proof length matches the expected depth of the tree (no_std version).
@@ -421,7 +557,7 @@ impl<const N: usize> MerkleTree<N> {
     where
         P: Into<Hash> + Copy,
     {
-        check_condition(proof.len() == N, BrineTreeError::ProofLength)
+        check_proof_length(proof.len(), N)
     }
 
     /// Computes the path from the leaf to the root using the provided proof without Vec allocation.
@@ -456,19 +592,33 @@ impl<const N: usize> MerkleTree<N> {
     }
 
     /// Returns a Merkle proof for a specific leaf in the tree without Vec allocation.
-    /// Uses MaybeUninit for efficient fixed-size array operations.
-    pub fn get_proof_no_std(&self, leaves: &[Leaf], leaf_index: usize) -> [Hash; N] {
-        get_merkle_proof_no_std(leaves, &self.zero_values, leaf_index)
+    /// Uses fixed-size stack arrays with room for `MAX_LAYER_SIZE` leaves.
+    pub fn get_proof_no_std<const MAX_LAYER_SIZE: usize>(
+        &self,
+        leaves: &[Leaf],
+        leaf_index: usize,
+    ) -> Result<[Hash; N], BrineTreeError> {
+        get_merkle_proof_no_std::<N, MAX_LAYER_SIZE>(leaves, &self.zero_values, leaf_index)
+    }
+
+    /// Returns the proofs for several leaves from a single pass over the layers,
+    /// each identical to a [`get_proof_no_std`](Self::get_proof_no_std) call.
+    pub fn get_proofs_no_std<const MAX_LAYER_SIZE: usize, const K: usize>(
+        &self,
+        leaves: &[Leaf],
+        leaf_indices: &[usize; K],
+    ) -> Result<[[Hash; N]; K], BrineTreeError> {
+        get_merkle_proofs_no_std::<N, MAX_LAYER_SIZE, K>(leaves, &self.zero_values, leaf_indices)
     }
 
     /// Returns the layer nodes at a specific layer without Vec allocation.
     /// Returns the number of nodes written and the buffer containing the nodes.
-    pub fn get_layer_nodes_no_std<const MAX_NODES: usize>(
+    pub fn get_layer_nodes_no_std<const MAX_NODES: usize, const MAX_LAYER_SIZE: usize>(
         &self,
         leaves: &[Leaf],
         layer_number: usize,
-    ) -> (usize, [Hash; MAX_NODES]) {
-        get_layer_nodes_no_std::<N, MAX_NODES>(
+    ) -> Result<(usize, [Hash; MAX_NODES]), BrineTreeError> {
+        get_layer_nodes_no_std::<N, MAX_NODES, MAX_LAYER_SIZE>(
             leaves,
             &self.zero_values,
             layer_number,
@@ -525,55 +675,134 @@ impl<const N: usize> MerkleTree<N> {
     }
 }
 
+impl<const N: usize> OrderedMerkleTree<N> {
+    /// Returns the proof for `leaf_index` without Vec allocation, as
+    /// [`MerkleTree::get_proof_no_std`] does for the sorted tree.
+    pub fn get_proof_no_std<const MAX_LAYER_SIZE: usize>(
+        &self,
+        leaves: &[Leaf],
+        leaf_index: usize,
+    ) -> Result<[Hash; N], BrineTreeError> {
+        merkle_proof_no_std::<Ordered, N, MAX_LAYER_SIZE>(leaves, &self.zero_values, leaf_index)
+    }
+
+    /// [`MerkleTree::insert_at`] that can also fill a slot below `next_index`, which
+    /// must still hold the empty leaf, given a `proof` of that empty leaf at `index`.
+    /// `proof` is ignored for slots past the current end.
+    ///
+    /// Only the ordered tree has this: sorted hashing doesn't tie a proof to a position, so
+    /// an empty slot's proof could be replayed at another index and corrupt the tree.
+    pub fn insert_at_with_proof<P>(&mut self, index: u64, leaf: Leaf, proof: &[P]) -> ProgramResult
+    where
+        P: Into<Hash> + Copy,
+    {
+        check_condition(index < self.get_capacity(), BrineTreeError::TreeFull)?;
+
+        if index >= self.next_index {
+            return self.insert_at(index, leaf);
+        }
+
+        let empty_leaf = self.get_empty_leaf();
+        check_proof_length(proof.len(), N)?;
+        let (_, original_root) = Self::compute_path_at(proof, empty_leaf, index);
+        let (new_path, new_root) = Self::compute_path_at(proof, leaf, index);
+        check_condition(original_root == self.root, BrineTreeError::InvalidProof)?;
+
+        // Only the left siblings of the next append are read back, so refresh
+        // the ones that sit on this leaf's path.
+        for (i, (filled, node)) in self.filled_subtrees.iter_mut().zip(new_path).enumerate() {
+            let frontier = self.next_index >> i;
+            if frontier % 2 == 1 && (index >> i) == frontier - 1 {
+                *filled = node;
+            }
+        }
+        self.root = new_root;
+
+        Ok(())
+    }
+
+    /// Hashes `leaf` up through `proof` as the node at `index`, returning the
+    /// node at each level and the root, as `verify_ordered_no_std` walks it.
+    fn compute_path_at<P>(proof: &[P], leaf: Leaf, index: u64) -> ([Hash; N], Hash)
+    where
+        P: Into<Hash> + Copy,
+    {
+        let mut path_hashes = [Hash::default(); N];
+        let mut computed_hash = Hash::from(leaf);
+        let mut current_index = index;
+
+        for (i, sibling) in proof.iter().enumerate() {
+            if i < N {
+                path_hashes[i] = computed_hash;
+            }
+            computed_hash = if current_index & 1 == 0 {
+                Ordered::hash_nodes(computed_hash, (*sibling).into())
+            } else {
+                Ordered::hash_nodes((*sibling).into(), computed_hash)
+            };
+            current_index /= 2;
+        }
+
+        (path_hashes, computed_hash)
+    }
+
+    /// Checks that `leaf` sits at `index` in the current tree.
+    pub fn contains_leaf_at<P>(&self, proof: &[P], leaf: Leaf, index: u64) -> bool
+    where
+        P: Into<Hash> + Copy,
+    {
+        proof.len() == N && verify_ordered_no_std(self.root, proof, leaf, index)
+    }
+}
+
 /// Returns the layer nodes at a specific layer without Vec allocation.
 /// Returns the number of nodes written and the buffer containing the nodes.
-pub fn get_layer_nodes_no_std<const N: usize, const MAX_NODES: usize>(
+///
+/// Like [`get_merkle_proof_no_std`], the working layers hold `MAX_LAYER_SIZE` hashes
+/// and more valid leaves than that fail with `BufferTooSmall`.
+pub fn get_layer_nodes_no_std<
+    const N: usize,
+    const MAX_NODES: usize,
+    const MAX_LAYER_SIZE: usize,
+>(
     leaves: &[Leaf],
     zero_values: &[Hash],
     layer_number: usize,
     next_index: usize,
-) -> (usize, [Hash; MAX_NODES]) {
+) -> Result<(usize, [Hash; MAX_NODES]), BrineTreeError> {
     let mut result_buffer: [Hash; MAX_NODES] = [Hash::default(); MAX_NODES];
 
     if layer_number > N {
-        return (0, result_buffer);
+        return Ok((0, result_buffer));
     }
 
     // Take only the valid leaves up to next_index
     let valid_leaf_count = core::cmp::min(leaves.len(), next_index);
 
     if valid_leaf_count == 0 {
-        return (0, result_buffer);
+        return Ok((0, result_buffer));
     }
 
-    // Use a reasonable maximum size that won't cause stack overflow
-    const MAX_LAYER_SIZE: usize = 4096;
+    check_condition(
+        valid_leaf_count <= MAX_LAYER_SIZE,
+        BrineTreeError::BufferTooSmall,
+    )?;
 
-    // If we have too many leaves, limit them
-    let actual_leaf_count = if valid_leaf_count > MAX_LAYER_SIZE {
-        MAX_LAYER_SIZE
-    } else {
-        valid_leaf_count
-    };
-
-    // Initialize first layer with valid leaves
-    let mut current_layer: [MaybeUninit<Hash>; MAX_LAYER_SIZE] =
-        unsafe { MaybeUninit::uninit().assume_init() };
-    let mut next_layer: [MaybeUninit<Hash>; MAX_LAYER_SIZE] =
-        unsafe { MaybeUninit::uninit().assume_init() };
+    // Initialize first layer with valid leaves. Only the first `current_size`
+    // entries of either layer are ever read.
+    let mut current_layer: [Hash; MAX_LAYER_SIZE] = [Hash::default(); MAX_LAYER_SIZE];
+    let mut next_layer: [Hash; MAX_LAYER_SIZE] = [Hash::default(); MAX_LAYER_SIZE];
 
-    let mut current_size = actual_leaf_count;
-    for i in 0..actual_leaf_count {
-        current_layer[i].write(Hash::from(leaves[i]));
+    let mut current_size = valid_leaf_count;
+    for i in 0..valid_leaf_count {
+        current_layer[i] = Hash::from(leaves[i]);
     }
 
     // If layer_number is 0, return the leaf hashes
     if layer_number == 0 {
         let result_count = core::cmp::min(current_size, MAX_NODES);
-        for i in 0..result_count {
-            result_buffer[i] = unsafe { current_layer[i].assume_init() };
-        }
-        return (result_count, result_buffer);
+        result_buffer[..result_count].copy_from_slice(&current_layer[..result_count]);
+        return Ok((result_count, result_buffer));
     }
 
     let mut current_level = 0;
@@ -590,15 +819,14 @@ pub fn get_layer_nodes_no_std<const N: usize, const MAX_NODES: usize>(
             let left_idx = i * 2;
             let right_idx = left_idx + 1;
 
-            let left = unsafe { current_layer[left_idx].assume_init() };
+            let left = current_layer[left_idx];
             let right = if right_idx < current_size {
-                unsafe { current_layer[right_idx].assume_init() }
+                current_layer[right_idx]
             } else {
                 zero_values[current_level]
             };
 
-            let hashed = hash_left_right(left, right);
-            next_layer[i].write(hashed);
+            next_layer[i] = hash_left_right(left, right);
         }
 
         current_level += 1;
@@ -606,10 +834,8 @@ pub fn get_layer_nodes_no_std<const N: usize, const MAX_NODES: usize>(
         // Check if we've reached the target layer
         if current_level == layer_number {
             let result_count = core::cmp::min(next_size, MAX_NODES);
-            for i in 0..result_count {
-                result_buffer[i] = unsafe { next_layer[i].assume_init() };
-            }
-            return (result_count, result_buffer);
+            result_buffer[..result_count].copy_from_slice(&next_layer[..result_count]);
+            return Ok((result_count, result_buffer));
         }
 
         // Swap layers for next iteration
@@ -617,7 +843,7 @@ pub fn get_layer_nodes_no_std<const N: usize, const MAX_NODES: usize>(
         current_size = next_size;
     }
 
-    (0, result_buffer)
+    Ok((0, result_buffer))
 }
 
 fn is_valid_leaf_no_std<P>(proof: &[P], root: Hash, leaf: Leaf) -> bool
@@ -630,7 +856,7 @@ where
         computed_hash = hash_left_right(computed_hash, (*proof_element).into());
     }
 
-    computed_hash == root
+    computed_hash.ct_eq(&root)
 }
 
 /// Returns a Merkle proof for a specific leaf in the tree.
@@ -675,71 +901,101 @@ pub fn get_merkle_proof(
 
 /// Returns a Merkle proof for a specific leaf in the tree without Vec allocation.
 /// Uses a simplified approach that builds the proof directly without storing all layers.
-pub fn get_merkle_proof_no_std<const N: usize>(
+///
+/// The two working layers hold `MAX_LAYER_SIZE` hashes each on the stack, so size it
+/// to the leaf count. More leaves than that fail with `BufferTooSmall`, and a
+/// `leaf_index` past the last of several leaves fails with `InvalidArgument`.
+pub fn get_merkle_proof_no_std<const N: usize, const MAX_LAYER_SIZE: usize>(
     leaves: &[Leaf],
     zero_values: &[Hash],
     leaf_index: usize,
-) -> [Hash; N] {
-    // Use a reasonable maximum size that won't cause stack overflow
-    const MAX_LAYER_SIZE: usize = 4096;
+) -> Result<[Hash; N], BrineTreeError> {
+    merkle_proof_no_std::<Sorted, N, MAX_LAYER_SIZE>(leaves, zero_values, leaf_index)
+}
 
-    // Check if we exceed the maximum supported size for no-std
-    if leaves.len() > MAX_LAYER_SIZE {
-        // For very large trees, we'll need to fallback to a different approach
-        // For now, we'll work with the first MAX_LAYER_SIZE leaves
-    }
+/// Returns the Merkle proofs for every index in `leaf_indices`, hashing the
+/// layers once rather than once per proof. Each proof equals what
+/// [`get_merkle_proof_no_std`] returns for its index, so for K > 1 this costs
+/// roughly 1/K of the hashing of K separate calls.
+pub fn get_merkle_proofs_no_std<const N: usize, const MAX_LAYER_SIZE: usize, const K: usize>(
+    leaves: &[Leaf],
+    zero_values: &[Hash],
+    leaf_indices: &[usize; K],
+) -> Result<[[Hash; N]; K], BrineTreeError> {
+    merkle_proofs_no_std::<Sorted, N, MAX_LAYER_SIZE, K>(leaves, zero_values, leaf_indices)
+}
 
-    let actual_leaves = if leaves.len() > MAX_LAYER_SIZE {
-        &leaves[..MAX_LAYER_SIZE]
-    } else {
-        leaves
-    };
+/// [`get_merkle_proof_no_std`] for either hashing mode.
+fn merkle_proof_no_std<H: NodeHasher, const N: usize, const MAX_LAYER_SIZE: usize>(
+    leaves: &[Leaf],
+    zero_values: &[Hash],
+    leaf_index: usize,
+) -> Result<[Hash; N], BrineTreeError> {
+    let [proof] =
+        merkle_proofs_no_std::<H, N, MAX_LAYER_SIZE, 1>(leaves, zero_values, &[leaf_index])?;
+    Ok(proof)
+}
 
-    // Use MaybeUninit for efficient initialization
-    let mut current_layer: [MaybeUninit<Hash>; MAX_LAYER_SIZE] =
-        unsafe { MaybeUninit::uninit().assume_init() };
-    let mut next_layer: [MaybeUninit<Hash>; MAX_LAYER_SIZE] =
-        unsafe { MaybeUninit::uninit().assume_init() };
+/// [`get_merkle_proofs_no_std`] for either hashing mode.
+fn merkle_proofs_no_std<
+    H: NodeHasher,
+    const N: usize,
+    const MAX_LAYER_SIZE: usize,
+    const K: usize,
+>(
+    leaves: &[Leaf],
+    zero_values: &[Hash],
+    leaf_indices: &[usize; K],
+) -> Result<[[Hash; N]; K], BrineTreeError> {
+    check_condition(
+        leaves.len() <= MAX_LAYER_SIZE,
+        BrineTreeError::BufferTooSmall,
+    )?;
 
-    // Initialize first layer with leaves
-    let mut current_size = actual_leaves.len();
-    for (i, leaf) in actual_leaves.iter().enumerate() {
-        current_layer[i].write(Hash::from(*leaf));
-    }
+    // Siblings are read from the layers, so each leaf has to be one of them
+    check_condition(
+        leaves.len() <= 1 || leaf_indices.iter().all(|&i| i < leaves.len()),
+        BrineTreeError::InvalidArgument,
+    )?;
+
+    // Only the first `current_size` entries of either layer are ever read
+    let mut current_layer: [Hash; MAX_LAYER_SIZE] = [Hash::default(); MAX_LAYER_SIZE];
+    let mut next_layer: [Hash; MAX_LAYER_SIZE] = [Hash::default(); MAX_LAYER_SIZE];
 
-    // Pad with zero if needed
-    if current_size % 2 != 0 {
-        current_layer[current_size].write(zero_values[0]);
-        current_size += 1;
+    // Initialize first layer with leaves, a missing right sibling reads as zero below
+    let mut current_size = leaves.len();
+    for (i, leaf) in leaves.iter().enumerate() {
+        current_layer[i] = Hash::from(*leaf);
     }
 
-    let mut proof: [MaybeUninit<Hash>; N] = unsafe { MaybeUninit::uninit().assume_init() };
-    let mut current_index = leaf_index;
+    let mut proofs: [[Hash; N]; K] = [[Hash::default(); N]; K];
+    let mut current_indices = *leaf_indices;
 
-    // Build proof level by level
+    // Build proofs level by level
     for level in 0..N {
         if current_size <= 1 {
-            // Fill remaining proof with zero values
-            for i in level..N {
-                proof[i].write(zero_values[i]);
+            // Fill remaining proofs with zero values
+            for proof in proofs.iter_mut() {
+                proof[level..N].copy_from_slice(&zero_values[level..N]);
             }
             break;
         }
 
-        // Get sibling for proof
-        let sibling = if current_index % 2 == 0 {
-            // Right sibling
-            if current_index + 1 < current_size {
-                unsafe { current_layer[current_index + 1].assume_init() }
+        // Get each proof's sibling
+        for (proof, current_index) in proofs.iter_mut().zip(current_indices.iter_mut()) {
+            proof[level] = if *current_index % 2 == 0 {
+                // Right sibling
+                if *current_index + 1 < current_size {
+                    current_layer[*current_index + 1]
+                } else {
+                    zero_values[level]
+                }
             } else {
-                zero_values[level]
-            }
-        } else {
-            // Left sibling
-            unsafe { current_layer[current_index - 1].assume_init() }
-        };
-
-        proof[level].write(sibling);
+                // Left sibling
+                current_layer[*current_index - 1]
+            };
+            *current_index /= 2;
+        }
 
         // Build next layer
         let next_size = (current_size + 1) / 2;
@@ -747,29 +1003,63 @@ pub fn get_merkle_proof_no_std<const N: usize>(
             let left_idx = i * 2;
             let right_idx = left_idx + 1;
 
-            let left = unsafe { current_layer[left_idx].assume_init() };
+            let left = current_layer[left_idx];
             let right = if right_idx < current_size {
-                unsafe { current_layer[right_idx].assume_init() }
+                current_layer[right_idx]
             } else {
                 zero_values[level]
             };
 
-            let hashed = hash_left_right(left, right);
-            next_layer[i].write(hashed);
+            next_layer[i] = H::hash_nodes(left, right);
         }
 
         // Swap layers
         core::mem::swap(&mut current_layer, &mut next_layer);
         current_size = next_size;
-        current_index /= 2;
     }
 
-    // Convert MaybeUninit array to initialized array safely
-    let mut result: [Hash; N] = [Hash::default(); N];
-    for i in 0..N {
-        result[i] = unsafe { proof[i].assume_init() };
-    }
-    result
+    Ok(proofs)
+}
+
+/// Patches the proof for `proof_index` after the leaf at `changed_index` went
+/// from `old_leaf` to `new_leaf`, returning whether the proof changed.
+///
+/// Only the sibling where the two paths meet depends on the changed leaf. Its
+/// new value is rebuilt from `new_leaf` and the changed leaf's own proof, which
+/// the change does not affect, so no other leaves are needed. `old_leaf` must
+/// rebuild the current sibling, otherwise the proofs belong to different trees.
+pub fn update_proof_no_std<const N: usize>(
+    proof: &mut [Hash; N],
+    proof_index: usize,
+    changed_index: usize,
+    changed_proof: &[Hash; N],
+    old_leaf: Leaf,
+    new_leaf: Leaf,
+) -> Result<bool, BrineTreeError> {
+    check_condition(
+        proof_index < (1 << N) && changed_index < (1 << N),
+        BrineTreeError::InvalidArgument,
+    )?;
+
+    // A proof never contains the leaf it proves
+    if proof_index == changed_index {
+        return Ok(false);
+    }
+
+    // The paths meet above the highest bit where the indices differ
+    let level = (usize::BITS - 1 - (proof_index ^ changed_index).leading_zeros()) as usize;
+
+    let mut old_hash = Hash::from(old_leaf);
+    let mut new_hash = Hash::from(new_leaf);
+    for sibling in &changed_proof[..level] {
+        old_hash = hash_left_right(old_hash, *sibling);
+        new_hash = hash_left_right(new_hash, *sibling);
+    }
+
+    check_condition(proof[level] == old_hash, BrineTreeError::InvalidProof)?;
+
+    proof[level] = new_hash;
+    Ok(old_hash != new_hash)
 }
 
 /// Hashes pairs of hashes together, returning a new vector of hashes.
@@ -789,16 +1079,20 @@ pub fn hash_pairs(pairs: Vec<Hash>) -> Vec<Hash> {
 }
 
 /// Hashes pairs of hashes together without Vec allocation.
+/// A trailing odd element is paired with `zero`, matching `get_layer_nodes`.
 /// Returns the number of pairs processed and the result buffer.
-pub fn hash_pairs_no_std<const MAX_PAIRS: usize>(pairs: &[Hash]) -> (usize, [Hash; MAX_PAIRS]) {
+pub fn hash_pairs_no_std<const MAX_PAIRS: usize>(
+    pairs: &[Hash],
+    zero: Hash,
+) -> (usize, [Hash; MAX_PAIRS]) {
     let mut result_buffer: [Hash; MAX_PAIRS] = [Hash::default(); MAX_PAIRS];
 
-    let num_pairs = pairs.len() / 2;
+    let num_pairs = pairs.len().div_ceil(2);
     let result_count = core::cmp::min(num_pairs, MAX_PAIRS);
 
     for i in 0..result_count {
         let left = pairs[i * 2];
-        let right = pairs[i * 2 + 1];
+        let right = pairs.get(i * 2 + 1).copied().unwrap_or(zero);
         let hashed = hash_left_right(left, right);
         result_buffer[i] = hashed;
     }
@@ -806,6 +1100,34 @@ pub fn hash_pairs_no_std<const MAX_PAIRS: usize>(pairs: &[Hash]) -> (usize, [Has
     (result_count, result_buffer)
 }
 
+/// How a [`MerkleTree`] combines two sibling nodes into their parent.
+pub trait NodeHasher: Clone + Copy + PartialEq + core::fmt::Debug + 'static {
+    fn hash_nodes(left: Hash, right: Hash) -> Hash;
+}
+
+/// Sorts the siblings before hashing, so proofs need no leaf positions.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Sorted;
+
+impl NodeHasher for Sorted {
+    #[inline(always)]
+    fn hash_nodes(left: Hash, right: Hash) -> Hash {
+        hash_left_right(left, right)
+    }
+}
+
+/// Hashes `left || right` as given, binding each leaf's position into its
+/// proof, as some ZK circuits require.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Ordered;
+
+impl NodeHasher for Ordered {
+    #[inline(always)]
+    fn hash_nodes(left: Hash, right: Hash) -> Hash {
+        hashv(&[b"NODE".as_ref(), left.as_ref(), right.as_ref()])
+    }
+}
+
 /// Hashes two hashes together, ensuring a consistent order.
 
 pub fn hash_left_right(left: Hash, right: Hash) -> Hash {
@@ -890,6 +1212,12 @@ pub fn is_valid_path_no_std(path_buffer: &[Hash], path_count: usize, root: Hash)
     path_buffer[path_count - 1] == root
 }
 
+/// Checks a proof of `got` hashes against a tree of depth `expected`,
+/// reporting both lengths on a mismatch.
+pub fn check_proof_length(got: usize, expected: usize) -> Result<(), BrineTreeError> {
+    check_condition(got == expected, BrineTreeError::proof_length(expected, got))
+}
+
 /// Verifies that a given merkle root contains the leaf using the provided proof.
 #[cfg(feature = "std")]
 pub fn verify<Root, Item, L>(root: Root, proof: &[Item], leaf: L) -> bool
@@ -914,15 +1242,121 @@ where
     L: Into<Leaf>,
 {
     let root_h: Hash = root.into();
-    let leaf_h: Leaf = leaf.into();
 
-    let mut computed_hash = Hash::from(leaf_h);
+    root_from_proof_no_std(proof, leaf.into()).ct_eq(&root_h)
+}
 
-    for proof_element in proof.iter() {
-        computed_hash = hash_left_right(computed_hash, (*proof_element).into());
+/// Folds `leaf` up through `proof` and returns the root it reaches.
+///
+/// Use this over [`verify_no_std`] when the root itself is wanted, e.g. to
+/// log it; comparing against an expected root is left to the caller.
+pub fn root_from_proof_no_std<P>(proof: &[P], leaf: Leaf) -> Hash
+where
+    P: Into<Hash> + Copy,
+{
+    proof
+        .iter()
+        .fold(Hash::from(leaf), |computed_hash, sibling| {
+            hash_left_right(computed_hash, (*sibling).into())
+        })
+}
+
+/// Deepest proof for which [`verify_many_no_std`] remembers the previous path.
+const MAX_SHARED_DEPTH: usize = 32;
+
+/// Verifies `proofs[i]` for `leaves[i]` against one root, stopping at the first failure.
+///
+/// Once a path meets the previous proof's path below siblings they have in common, the
+/// rest of it is already known to reach the root and is not hashed again. Proofs for
+/// nearby leaves share most of their path, so sorting them by index saves the most.
+/// Mismatched lengths fail, and an empty batch verifies trivially.
+pub fn verify_many_no_std<Root>(root: Root, proofs: &[&[Hash]], leaves: &[Leaf]) -> bool
+where
+    Root: Into<Hash>,
+{
+    if proofs.len() != leaves.len() {
+        return false;
     }
 
-    computed_hash == root_h
+    let root_h: Hash = root.into();
+    let mut path = [Hash::default(); MAX_SHARED_DEPTH];
+    let mut previous: &[Hash] = &[];
+
+    for (proof, leaf) in proofs.iter().zip(leaves) {
+        // The level above which this proof repeats the previous one, if it was recorded
+        let shared_from = if proof.len() == previous.len() && proof.len() <= MAX_SHARED_DEPTH {
+            let common = proof
+                .iter()
+                .rev()
+                .zip(previous.iter().rev())
+                .take_while(|(a, b)| a == b)
+                .count();
+            proof.len() - common
+        } else {
+            usize::MAX
+        };
+
+        let mut computed_hash = Hash::from(*leaf);
+        let mut reached_root = false;
+
+        for (level, sibling) in proof.iter().enumerate() {
+            if level == shared_from && path[level] == computed_hash {
+                reached_root = true;
+                break;
+            }
+
+            if let Some(node) = path.get_mut(level) {
+                *node = computed_hash;
+            }
+            computed_hash = hash_left_right(computed_hash, *sibling);
+        }
+
+        if !reached_root && computed_hash != root_h {
+            return false;
+        }
+        previous = proof;
+    }
+
+    true
+}
+
+/// Verifies that `segment` sits at `index` under `root`, for trees of
+/// [`Leaf::from_segment`] leaves.
+///
+/// Sorted pair hashing ignores which side a node is on, so the index is bound
+/// through the leaf instead: the same segment claimed at another index hashes
+/// to a different leaf and fails. An index past the proof's depth fails too.
+pub fn verify_at<Root, Item>(root: Root, proof: &[Item], segment: &[u8], index: u64) -> bool
+where
+    Root: Into<Hash>,
+    Item: Into<Hash> + Copy,
+{
+    let in_depth = proof.len() >= 64 || index >> proof.len() == 0;
+    in_depth && verify_no_std(root, proof, Leaf::from_segment(index, segment))
+}
+
+/// Verifies a proof from an [`OrderedMerkleTree`]. Each bit of `index`, from
+/// the lowest, says whether the node is the right child at that level.
+pub fn verify_ordered_no_std<Root, Item, L>(root: Root, proof: &[Item], leaf: L, index: u64) -> bool
+where
+    Root: Into<Hash>,
+    Item: Into<Hash> + Copy,
+    L: Into<Leaf>,
+{
+    let mut computed_hash = Hash::from(leaf.into());
+    let mut current_index = index;
+
+    for sibling in proof {
+        computed_hash = if current_index & 1 == 0 {
+            Ordered::hash_nodes(computed_hash, (*sibling).into())
+        } else {
+            Ordered::hash_nodes((*sibling).into(), computed_hash)
+        };
+        current_index /= 2;
+    }
+
+    // An index past the proof's depth belongs to a different tree
+    current_index == 0 && computed_hash == root.into()
 }
 
 #[cfg(test)]
@@ -932,7 +1366,10 @@ mod tests {
 
     // Tests always use std for convenience - this doesn't affect the no-std nature of the functions being tested
     extern crate std;
-    use std::{format, println, vec::Vec};
+    use std::{format, println, vec, vec::Vec};
+
+    /// Working buffer for the no-std proof helpers, large enough for every tree below
+    const MAX_LAYER: usize = 4096;
 
     /// Creates test leaves with predictable data
     fn create_test_leaves(count: usize) -> Vec<Leaf> {
@@ -970,7 +1407,9 @@ mod tests {
         #[cfg(feature = "std")]
         {
             let std_proof = get_merkle_proof(&leaves, &zero_values, leaf_index, HEIGHT);
-            let no_std_proof = get_merkle_proof_no_std::<HEIGHT>(&leaves, &zero_values, leaf_index);
+            let no_std_proof =
+                get_merkle_proof_no_std::<HEIGHT, MAX_LAYER>(&leaves, &zero_values, leaf_index)
+                    .unwrap();
 
             // Compare lengths
             assert_eq!(
@@ -992,7 +1431,9 @@ mod tests {
         #[cfg(not(feature = "std"))]
         {
             // When std is not available, just test the no-std version
-            let no_std_proof = get_merkle_proof_no_std::<HEIGHT>(&leaves, &zero_values, leaf_index);
+            let no_std_proof =
+                get_merkle_proof_no_std::<HEIGHT, MAX_LAYER>(&leaves, &zero_values, leaf_index)
+                    .unwrap();
             assert_eq!(
                 no_std_proof.len(),
                 HEIGHT,
@@ -1013,7 +1454,9 @@ mod tests {
         #[cfg(feature = "std")]
         {
             let std_proof = get_merkle_proof(&leaves, &zero_values, leaf_index, HEIGHT);
-            let no_std_proof = get_merkle_proof_no_std::<HEIGHT>(&leaves, &zero_values, leaf_index);
+            let no_std_proof =
+                get_merkle_proof_no_std::<HEIGHT, MAX_LAYER>(&leaves, &zero_values, leaf_index)
+                    .unwrap();
 
             // Compare lengths
             assert_eq!(
@@ -1034,7 +1477,9 @@ mod tests {
 
         #[cfg(not(feature = "std"))]
         {
-            let no_std_proof = get_merkle_proof_no_std::<HEIGHT>(&leaves, &zero_values, leaf_index);
+            let no_std_proof =
+                get_merkle_proof_no_std::<HEIGHT, MAX_LAYER>(&leaves, &zero_values, leaf_index)
+                    .unwrap();
             assert_eq!(
                 no_std_proof.len(),
                 HEIGHT,
@@ -1055,7 +1500,9 @@ mod tests {
         #[cfg(feature = "std")]
         {
             let std_proof = get_merkle_proof(&leaves, &zero_values, leaf_index, HEIGHT);
-            let no_std_proof = get_merkle_proof_no_std::<HEIGHT>(&leaves, &zero_values, leaf_index);
+            let no_std_proof =
+                get_merkle_proof_no_std::<HEIGHT, MAX_LAYER>(&leaves, &zero_values, leaf_index)
+                    .unwrap();
 
             // Compare lengths
             assert_eq!(
@@ -1076,7 +1523,9 @@ mod tests {
 
         #[cfg(not(feature = "std"))]
         {
-            let no_std_proof = get_merkle_proof_no_std::<HEIGHT>(&leaves, &zero_values, leaf_index);
+            let no_std_proof =
+                get_merkle_proof_no_std::<HEIGHT, MAX_LAYER>(&leaves, &zero_values, leaf_index)
+                    .unwrap();
             assert_eq!(
                 no_std_proof.len(),
                 HEIGHT,
@@ -1093,18 +1542,21 @@ mod tests {
 
         // Test with single leaf
         let single_leaf = create_test_leaves(1);
-        let single_proof = get_merkle_proof_no_std::<HEIGHT>(&single_leaf, &zero_values, 0);
+        let single_proof =
+            get_merkle_proof_no_std::<HEIGHT, MAX_LAYER>(&single_leaf, &zero_values, 0).unwrap();
         assert_eq!(single_proof.len(), HEIGHT);
 
         // Test with odd number of leaves
         let odd_leaves = create_test_leaves(7);
-        let odd_proof = get_merkle_proof_no_std::<HEIGHT>(&odd_leaves, &zero_values, 3);
+        let odd_proof =
+            get_merkle_proof_no_std::<HEIGHT, MAX_LAYER>(&odd_leaves, &zero_values, 3).unwrap();
         assert_eq!(odd_proof.len(), HEIGHT);
 
         // Test with power of 2 leaves
         let power_of_2_leaves = create_test_leaves(16);
         let power_of_2_proof =
-            get_merkle_proof_no_std::<HEIGHT>(&power_of_2_leaves, &zero_values, 8);
+            get_merkle_proof_no_std::<HEIGHT, MAX_LAYER>(&power_of_2_leaves, &zero_values, 8)
+                .unwrap();
         assert_eq!(power_of_2_proof.len(), HEIGHT);
 
         println!("✅ Edge case tests passed");
@@ -1118,7 +1570,8 @@ mod tests {
         let leaf_index = 7;
 
         // Generate proof using no-std version
-        let proof = get_merkle_proof_no_std::<HEIGHT>(&leaves, &zero_values, leaf_index);
+        let proof = get_merkle_proof_no_std::<HEIGHT, MAX_LAYER>(&leaves, &zero_values, leaf_index)
+            .unwrap();
 
         // Create a simple merkle tree to get the root
         let mut tree = MerkleTree::<HEIGHT>::new(&[b"test_zero"]);
@@ -1151,7 +1604,9 @@ mod tests {
         }
 
         // Generate proof using the tree's no-std method
-        let proof = tree.get_proof_no_std(&leaves, leaf_index);
+        let proof = tree
+            .get_proof_no_std::<MAX_LAYER>(&leaves, leaf_index)
+            .unwrap();
 
         // Verify the proof
         let root = tree.get_root();
@@ -1184,8 +1639,9 @@ mod tests {
             #[cfg(feature = "std")]
             {
                 let std_result = tree.get_layer_nodes(&leaves, layer);
-                let (no_std_count, no_std_buffer) =
-                    tree.get_layer_nodes_no_std::<MAX_NODES>(&leaves, layer);
+                let (no_std_count, no_std_buffer) = tree
+                    .get_layer_nodes_no_std::<MAX_NODES, MAX_LAYER>(&leaves, layer)
+                    .unwrap();
 
                 // Compare lengths
                 assert_eq!(
@@ -1211,8 +1667,9 @@ mod tests {
 
             #[cfg(not(feature = "std"))]
             {
-                let (no_std_count, _no_std_buffer) =
-                    tree.get_layer_nodes_no_std::<MAX_NODES>(&leaves, layer);
+                let (no_std_count, _no_std_buffer) = tree
+                    .get_layer_nodes_no_std::<MAX_NODES, MAX_LAYER>(&leaves, layer)
+                    .unwrap();
                 // Just verify we get reasonable results
                 if layer <= HEIGHT {
                     assert!(
@@ -1249,8 +1706,9 @@ mod tests {
             #[cfg(feature = "std")]
             {
                 let std_result = tree.get_layer_nodes(&leaves, layer);
-                let (no_std_count, no_std_buffer) =
-                    tree.get_layer_nodes_no_std::<MAX_NODES>(&leaves, layer);
+                let (no_std_count, no_std_buffer) = tree
+                    .get_layer_nodes_no_std::<MAX_NODES, MAX_LAYER>(&leaves, layer)
+                    .unwrap();
 
                 // Compare lengths
                 assert_eq!(
@@ -1276,8 +1734,9 @@ mod tests {
 
             #[cfg(not(feature = "std"))]
             {
-                let (no_std_count, _no_std_buffer) =
-                    tree.get_layer_nodes_no_std::<MAX_NODES>(&leaves, layer);
+                let (no_std_count, _no_std_buffer) = tree
+                    .get_layer_nodes_no_std::<MAX_NODES, MAX_LAYER>(&leaves, layer)
+                    .unwrap();
                 // Just verify we get reasonable results
                 if layer <= HEIGHT {
                     assert!(
@@ -1314,8 +1773,9 @@ mod tests {
             #[cfg(feature = "std")]
             {
                 let std_result = tree.get_layer_nodes(&leaves, layer);
-                let (no_std_count, no_std_buffer) =
-                    tree.get_layer_nodes_no_std::<MAX_NODES>(&leaves, layer);
+                let (no_std_count, no_std_buffer) = tree
+                    .get_layer_nodes_no_std::<MAX_NODES, MAX_LAYER>(&leaves, layer)
+                    .unwrap();
 
                 // Compare lengths
                 assert_eq!(
@@ -1341,8 +1801,9 @@ mod tests {
 
             #[cfg(not(feature = "std"))]
             {
-                let (no_std_count, _no_std_buffer) =
-                    tree.get_layer_nodes_no_std::<MAX_NODES>(&leaves, layer);
+                let (no_std_count, _no_std_buffer) = tree
+                    .get_layer_nodes_no_std::<MAX_NODES, MAX_LAYER>(&leaves, layer)
+                    .unwrap();
                 // Just verify we get reasonable results
                 if layer <= HEIGHT {
                     assert!(
@@ -1369,17 +1830,23 @@ mod tests {
         tree.try_add_leaf(single_leaf[0])
             .expect("Should be able to add leaf");
 
-        let (count, _buffer) = tree.get_layer_nodes_no_std::<MAX_NODES>(&single_leaf, 0);
+        let (count, _buffer) = tree
+            .get_layer_nodes_no_std::<MAX_NODES, MAX_LAYER>(&single_leaf, 0)
+            .unwrap();
         assert_eq!(count, 1, "Single leaf should produce 1 node at layer 0");
 
         // Test with empty leaves
         let empty_leaves = create_test_leaves(0);
-        let (count, _buffer) = tree.get_layer_nodes_no_std::<MAX_NODES>(&empty_leaves, 0);
+        let (count, _buffer) = tree
+            .get_layer_nodes_no_std::<MAX_NODES, MAX_LAYER>(&empty_leaves, 0)
+            .unwrap();
         assert_eq!(count, 0, "Empty leaves should produce 0 nodes");
 
         // Test layer beyond tree height
         let leaves = create_test_leaves(4);
-        let (count, _buffer) = tree.get_layer_nodes_no_std::<MAX_NODES>(&leaves, HEIGHT + 1);
+        let (count, _buffer) = tree
+            .get_layer_nodes_no_std::<MAX_NODES, MAX_LAYER>(&leaves, HEIGHT + 1)
+            .unwrap();
         assert_eq!(count, 0, "Layer beyond height should produce 0 nodes");
 
         println!("✅ Layer nodes edge cases test passed");
@@ -1398,9 +1865,15 @@ mod tests {
         }
 
         // Verify that layer progression makes sense
-        let (layer0_count, _) = tree.get_layer_nodes_no_std::<MAX_NODES>(&leaves, 0);
-        let (layer1_count, _) = tree.get_layer_nodes_no_std::<MAX_NODES>(&leaves, 1);
-        let (layer2_count, _) = tree.get_layer_nodes_no_std::<MAX_NODES>(&leaves, 2);
+        let (layer0_count, _) = tree
+            .get_layer_nodes_no_std::<MAX_NODES, MAX_LAYER>(&leaves, 0)
+            .unwrap();
+        let (layer1_count, _) = tree
+            .get_layer_nodes_no_std::<MAX_NODES, MAX_LAYER>(&leaves, 1)
+            .unwrap();
+        let (layer2_count, _) = tree
+            .get_layer_nodes_no_std::<MAX_NODES, MAX_LAYER>(&leaves, 2)
+            .unwrap();
 
         assert_eq!(layer0_count, 10, "Layer 0 should have 10 leaf nodes");
         assert_eq!(layer1_count, 5, "Layer 1 should have 5 nodes (10/2)");
@@ -1427,8 +1900,12 @@ mod tests {
             #[cfg(feature = "std")]
             {
                 let std_proof = get_merkle_proof(&leaves, &zero_values, leaf_index, TAPE_HEIGHT);
-                let no_std_proof =
-                    get_merkle_proof_no_std::<TAPE_HEIGHT>(&leaves, &zero_values, leaf_index);
+                let no_std_proof = get_merkle_proof_no_std::<TAPE_HEIGHT, MAX_LAYER>(
+                    &leaves,
+                    &zero_values,
+                    leaf_index,
+                )
+                .unwrap();
 
                 assert_eq!(
                     std_proof.len(),
@@ -1464,8 +1941,12 @@ mod tests {
 
             #[cfg(not(feature = "std"))]
             {
-                let no_std_proof =
-                    get_merkle_proof_no_std::<TAPE_HEIGHT>(&leaves, &zero_values, leaf_index);
+                let no_std_proof = get_merkle_proof_no_std::<TAPE_HEIGHT, MAX_LAYER>(
+                    &leaves,
+                    &zero_values,
+                    leaf_index,
+                )
+                .unwrap();
                 assert_eq!(
                     no_std_proof.len(),
                     TAPE_HEIGHT,
@@ -1487,8 +1968,12 @@ mod tests {
             #[cfg(feature = "std")]
             {
                 let std_proof = get_merkle_proof(&leaves, &zero_values, leaf_index, SEGMENT_HEIGHT);
-                let no_std_proof =
-                    get_merkle_proof_no_std::<SEGMENT_HEIGHT>(&leaves, &zero_values, leaf_index);
+                let no_std_proof = get_merkle_proof_no_std::<SEGMENT_HEIGHT, MAX_LAYER>(
+                    &leaves,
+                    &zero_values,
+                    leaf_index,
+                )
+                .unwrap();
 
                 assert_eq!(
                     std_proof.len(),
@@ -1524,8 +2009,12 @@ mod tests {
 
             #[cfg(not(feature = "std"))]
             {
-                let no_std_proof =
-                    get_merkle_proof_no_std::<SEGMENT_HEIGHT>(&leaves, &zero_values, leaf_index);
+                let no_std_proof = get_merkle_proof_no_std::<SEGMENT_HEIGHT, MAX_LAYER>(
+                    &leaves,
+                    &zero_values,
+                    leaf_index,
+                )
+                .unwrap();
                 assert_eq!(
                     no_std_proof.len(),
                     SEGMENT_HEIGHT,
@@ -1550,7 +2039,9 @@ mod tests {
         #[cfg(feature = "std")]
         {
             let std_proof = get_merkle_proof(&leaves, &zero_values, leaf_index, HEIGHT);
-            let no_std_proof = get_merkle_proof_no_std::<HEIGHT>(&leaves, &zero_values, leaf_index);
+            let no_std_proof =
+                get_merkle_proof_no_std::<HEIGHT, MAX_LAYER>(&leaves, &zero_values, leaf_index)
+                    .unwrap();
 
             // Create a tree to get the actual root
             let mut tree = MerkleTree::<HEIGHT>::new(&[b"test_zero"]);
@@ -1576,7 +2067,9 @@ mod tests {
 
         #[cfg(not(feature = "std"))]
         {
-            let no_std_proof = get_merkle_proof_no_std::<HEIGHT>(&leaves, &zero_values, leaf_index);
+            let no_std_proof =
+                get_merkle_proof_no_std::<HEIGHT, MAX_LAYER>(&leaves, &zero_values, leaf_index)
+                    .unwrap();
 
             // Create a tree to get the actual root
             let mut tree = MerkleTree::<HEIGHT>::new(&[b"test_zero"]);
@@ -1616,7 +2109,9 @@ mod tests {
         #[cfg(feature = "std")]
         let proof = tree_std.get_proof(&leaves, target_index);
         #[cfg(not(feature = "std"))]
-        let proof = tree_no_std.get_proof_no_std(&leaves, target_index);
+        let proof = tree_no_std
+            .get_proof_no_std::<MAX_LAYER>(&leaves, target_index)
+            .unwrap();
 
         let initial_root_std = tree_std.get_root();
         let initial_root_no_std = tree_no_std.get_root();
@@ -1676,7 +2171,9 @@ mod tests {
         #[cfg(feature = "std")]
         let proof = tree_std.get_proof(&leaves, target_index);
         #[cfg(not(feature = "std"))]
-        let proof = tree_no_std.get_proof_no_std(&leaves, target_index);
+        let proof = tree_no_std
+            .get_proof_no_std::<MAX_LAYER>(&leaves, target_index)
+            .unwrap();
 
         // Test leaf removal
         #[cfg(feature = "std")]
@@ -1730,7 +2227,9 @@ mod tests {
         #[cfg(feature = "std")]
         let proof = tree_std.get_proof(&leaves, target_index);
         #[cfg(not(feature = "std"))]
-        let proof = tree_no_std.get_proof_no_std(&leaves, target_index);
+        let proof = tree_no_std
+            .get_proof_no_std::<MAX_LAYER>(&leaves, target_index)
+            .unwrap();
 
         // Test replacement
         #[cfg(feature = "std")]
@@ -1784,7 +2283,9 @@ mod tests {
         #[cfg(feature = "std")]
         let proof = tree_std.get_proof(&leaves, target_index);
         #[cfg(not(feature = "std"))]
-        let proof = tree_no_std.get_proof_no_std(&leaves, target_index);
+        let proof = tree_no_std
+            .get_proof_no_std::<MAX_LAYER>(&leaves, target_index)
+            .unwrap();
 
         // Test leaf replacement
         #[cfg(feature = "std")]
@@ -1840,7 +2341,9 @@ mod tests {
         #[cfg(feature = "std")]
         let proof = tree.get_proof(&leaves, target_index);
         #[cfg(not(feature = "std"))]
-        let proof = tree.get_proof_no_std(&leaves, target_index);
+        let proof = tree
+            .get_proof_no_std::<MAX_LAYER>(&leaves, target_index)
+            .unwrap();
 
         #[cfg(feature = "std")]
         {
@@ -1897,7 +2400,9 @@ mod tests {
         #[cfg(feature = "std")]
         let proof = tree.get_proof(&leaves, target_index);
         #[cfg(not(feature = "std"))]
-        let proof = tree.get_proof_no_std(&leaves, target_index);
+        let proof = tree
+            .get_proof_no_std::<MAX_LAYER>(&leaves, target_index)
+            .unwrap();
 
         #[cfg(feature = "std")]
         {
@@ -1958,7 +2463,9 @@ mod tests {
         #[cfg(feature = "std")]
         let proof = tree_std.get_proof(&leaves, target_index);
         #[cfg(not(feature = "std"))]
-        let proof = tree_no_std.get_proof_no_std(&leaves, target_index);
+        let proof = tree_no_std
+            .get_proof_no_std::<MAX_LAYER>(&leaves, target_index)
+            .unwrap();
 
         #[cfg(feature = "std")]
         {
@@ -2021,7 +2528,8 @@ mod tests {
         #[cfg(feature = "std")]
         {
             let std_result = hash_pairs(hashes.clone());
-            let (no_std_count, no_std_buffer) = hash_pairs_no_std::<MAX_PAIRS>(&hashes);
+            let (no_std_count, no_std_buffer) =
+                hash_pairs_no_std::<MAX_PAIRS>(&hashes, Hash::default());
 
             assert_eq!(
                 std_result.len(),
@@ -2042,7 +2550,8 @@ mod tests {
 
         #[cfg(not(feature = "std"))]
         {
-            let (no_std_count, _no_std_buffer) = hash_pairs_no_std::<MAX_PAIRS>(&hashes);
+            let (no_std_count, _no_std_buffer) =
+                hash_pairs_no_std::<MAX_PAIRS>(&hashes, Hash::default());
             assert_eq!(
                 no_std_count,
                 hashes.len() / 2,
@@ -2052,6 +2561,58 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_try_add_leaf_capacity_boundary() {
+        let leaves = create_test_leaves(9);
+        let mut tree: MerkleTree<3> = MerkleTree::new(&[b"test"]);
+        assert_eq!(tree.get_capacity(), 8);
+
+        for leaf in &leaves[..7] {
+            tree.try_add_leaf(*leaf).unwrap();
+        }
+
+        // The last valid index fills the tree exactly
+        assert_eq!(tree.next_index, 7);
+        assert_eq!(tree.try_add_leaf(leaves[7]), Ok(()));
+        assert_eq!(tree.next_index, 8);
+
+        // A full tree needs no zero values: the root is the plain pairwise hash
+        let hashes: Vec<Hash> = leaves[..8].iter().map(|&leaf| Hash::from(leaf)).collect();
+        let layer_1: Vec<Hash> = hashes
+            .chunks(2)
+            .map(|pair| hash_left_right(pair[0], pair[1]))
+            .collect();
+        let layer_2: Vec<Hash> = layer_1
+            .chunks(2)
+            .map(|pair| hash_left_right(pair[0], pair[1]))
+            .collect();
+        assert_eq!(tree.get_root(), hash_left_right(layer_2[0], layer_2[1]));
+
+        // One past capacity is rejected and leaves the tree untouched
+        let root = tree.get_root();
+        assert_eq!(tree.try_add_leaf(leaves[8]), Err(BrineTreeError::TreeFull));
+        assert_eq!(tree.next_index, 8);
+        assert_eq!(tree.get_root(), root);
+    }
+
+    #[test]
+    fn test_hash_pairs_no_std_pads_odd_length() {
+        const MAX_PAIRS: usize = 4;
+
+        let hashes = create_test_leaves(5)
+            .into_iter()
+            .map(Hash::from)
+            .collect::<Vec<Hash>>();
+        let zero = Hash::new_from_array([7; 32]);
+
+        let (count, buffer) = hash_pairs_no_std::<MAX_PAIRS>(&hashes, zero);
+
+        assert_eq!(count, 3, "Trailing element should not be dropped");
+        assert_eq!(buffer[0], hash_left_right(hashes[0], hashes[1]));
+        assert_eq!(buffer[1], hash_left_right(hashes[2], hashes[3]));
+        assert_eq!(buffer[2], hash_left_right(hashes[4], zero));
+    }
+
     #[test]
     fn test_compute_path_comparison() {
         const HEIGHT: usize = 6;
@@ -2070,7 +2631,9 @@ mod tests {
         #[cfg(feature = "std")]
         let proof = tree.get_proof(&leaves, target_index);
         #[cfg(not(feature = "std"))]
-        let proof = tree.get_proof_no_std(&leaves, target_index);
+        let proof = tree
+            .get_proof_no_std::<MAX_LAYER>(&leaves, target_index)
+            .unwrap();
 
         #[cfg(feature = "std")]
         {
@@ -2124,7 +2687,9 @@ mod tests {
         #[cfg(feature = "std")]
         let proof = tree.get_proof(&leaves, target_index);
         #[cfg(not(feature = "std"))]
-        let proof = tree.get_proof_no_std(&leaves, target_index);
+        let proof = tree
+            .get_proof_no_std::<MAX_LAYER>(&leaves, target_index)
+            .unwrap();
 
         #[cfg(feature = "std")]
         {
@@ -2190,7 +2755,9 @@ mod tests {
         #[cfg(feature = "std")]
         let proof = tree.get_proof(&leaves, target_index);
         #[cfg(not(feature = "std"))]
-        let proof = tree.get_proof_no_std(&leaves, target_index);
+        let proof = tree
+            .get_proof_no_std::<MAX_LAYER>(&leaves, target_index)
+            .unwrap();
 
         // Test the complete workflow with no-std functions
         let (path_count, path_buffer) = compute_path_no_std::<MAX_PATH>(&proof, target_leaf);
@@ -2203,7 +2770,8 @@ mod tests {
 
         // Test hash_pairs_no_std as part of the workflow
         let leaf_hashes: Vec<Hash> = leaves.iter().map(|&leaf| Hash::from(leaf)).collect();
-        let (pairs_count, _pairs_buffer) = hash_pairs_no_std::<MAX_PAIRS>(&leaf_hashes);
+        let (pairs_count, _pairs_buffer) =
+            hash_pairs_no_std::<MAX_PAIRS>(&leaf_hashes, Hash::default());
 
         assert_eq!(
             pairs_count,
@@ -2213,4 +2781,749 @@ mod tests {
 
         println!("✅ All utility functions integration test passed");
     }
+
+    #[test]
+    fn test_layout_sizes() {
+        use core::mem::{align_of, size_of};
+
+        println!("Hash:           {} bytes", size_of::<Hash>());
+        println!("Leaf:           {} bytes", size_of::<Leaf>());
+        println!("MerkleTree<10>: {} bytes", size_of::<MerkleTree<10>>());
+        println!("MerkleTree<18>: {} bytes", size_of::<MerkleTree<18>>());
+
+        assert_eq!(size_of::<Hash>(), HASH_BYTES);
+        assert_eq!(size_of::<Leaf>(), HASH_BYTES);
+        assert_eq!(align_of::<Hash>(), 1);
+        assert_eq!(align_of::<Leaf>(), 1);
+
+        assert_eq!(size_of::<MerkleTree<10>>(), 21 * HASH_BYTES + 8);
+        assert_eq!(size_of::<MerkleTree<18>>(), 37 * HASH_BYTES + 8);
+        assert_eq!(MerkleTree::<18>::get_size(), size_of::<MerkleTree<18>>());
+
+        println!("✅ Layout sizes test passed");
+    }
+
+    #[test]
+    fn test_try_replace_leaf_returns_prev_root() {
+        const HEIGHT: usize = 4;
+
+        let leaves = create_test_leaves(4);
+        let zero_values = create_zero_values::<HEIGHT>();
+        let mut tree = MerkleTree::<HEIGHT>::from_zeros(zero_values);
+        for leaf in &leaves {
+            tree.try_add_leaf(*leaf).unwrap();
+        }
+
+        let target_index = 2;
+        let proof = tree
+            .get_proof_no_std::<MAX_LAYER>(&leaves, target_index)
+            .unwrap();
+        let new_leaf = Leaf::new(&[b"replacement"]);
+
+        let root_before = tree.get_root();
+        let prev_root = tree
+            .try_replace_leaf_prev_root_no_std(&proof, leaves[target_index], new_leaf)
+            .unwrap();
+
+        assert_eq!(
+            prev_root, root_before,
+            "Should return the root before replacement"
+        );
+        assert_ne!(
+            tree.get_root(),
+            prev_root,
+            "Root should change after replacement"
+        );
+
+        // A failed replacement leaves the tree untouched
+        let root_before = tree.get_root();
+        assert_eq!(
+            tree.try_replace_leaf_prev_root_no_std(&proof, leaves[target_index], new_leaf),
+            Err(BrineTreeError::InvalidProof)
+        );
+        assert_eq!(tree.get_root(), root_before);
+
+        println!("✅ Replace leaf returns previous root test passed");
+    }
+
+    #[test]
+    fn test_insert_at_matches_sequential() {
+        const HEIGHT: usize = 4;
+
+        let leaves = create_test_leaves(6);
+        let zero_values = create_zero_values::<HEIGHT>();
+
+        // Gaps ahead of the end are padded with empty leaves
+        let mut tree = MerkleTree::<HEIGHT>::from_zeros(zero_values);
+        let empty = tree.get_empty_leaf();
+        tree.insert_at(3, leaves[3]).unwrap();
+        assert_eq!(tree.get_leaf_count(), 4);
+        tree.insert_at(5, leaves[5]).unwrap();
+        assert_eq!(tree.get_leaf_count(), 6);
+
+        let mut sequential = MerkleTree::<HEIGHT>::from_zeros(zero_values);
+        for leaf in [empty, empty, empty, leaves[3], empty, leaves[5]] {
+            sequential.try_add_leaf(leaf).unwrap();
+        }
+        assert_eq!(tree.get_root(), sequential.get_root());
+
+        // A slot below the end can't be rebuilt from the leaf alone
+        let root_before = tree.get_root();
+        assert_eq!(
+            tree.insert_at(0, leaves[0]),
+            Err(BrineTreeError::InvalidArgument)
+        );
+        assert_eq!(
+            tree.insert_at(1 << HEIGHT, leaves[0]),
+            Err(BrineTreeError::TreeFull)
+        );
+        assert_eq!(tree.get_root(), root_before);
+        assert_eq!(tree.get_leaf_count(), 6);
+
+        // An ordered tree fills it given a proof: insert 3, then 0 and 1
+        let mut tree = OrderedMerkleTree::<HEIGHT>::from_zeros(zero_values);
+        tree.insert_at(3, leaves[3]).unwrap();
+        assert_eq!(tree.get_leaf_count(), 4);
+
+        let proof = tree
+            .get_proof_no_std::<MAX_LAYER>(&[empty, empty, empty, leaves[3]], 0)
+            .unwrap();
+        tree.insert_at_with_proof(0, leaves[0], &proof).unwrap();
+
+        let proof = tree
+            .get_proof_no_std::<MAX_LAYER>(&[leaves[0], empty, empty, leaves[3]], 1)
+            .unwrap();
+        tree.insert_at_with_proof(1, leaves[1], &proof).unwrap();
+
+        let mut sequential = OrderedMerkleTree::<HEIGHT>::from_zeros(zero_values);
+        for leaf in [leaves[0], leaves[1], empty, leaves[3]] {
+            sequential.try_add_leaf(leaf).unwrap();
+        }
+
+        assert_eq!(tree.get_root(), sequential.get_root());
+        assert_eq!(tree.get_leaf_count(), sequential.get_leaf_count());
+
+        // Appending afterwards still agrees
+        tree.try_add_leaf(leaves[4]).unwrap();
+        sequential.try_add_leaf(leaves[4]).unwrap();
+        assert_eq!(tree.get_root(), sequential.get_root());
+
+        // An occupied slot cannot be inserted into
+        let proof = tree
+            .get_proof_no_std::<MAX_LAYER>(&[leaves[0], leaves[1], empty, leaves[3], leaves[4]], 1)
+            .unwrap();
+        assert_eq!(
+            tree.insert_at_with_proof(1, leaves[2], &proof),
+            Err(BrineTreeError::InvalidProof)
+        );
+
+        // Slot 2's proof would also fold an empty leaf up to the root in a
+        // sorted tree, but here it doesn't prove slot 0
+        let mut tree = OrderedMerkleTree::<HEIGHT>::from_zeros(zero_values);
+        tree.insert_at(3, leaves[3]).unwrap();
+        let proof = tree
+            .get_proof_no_std::<MAX_LAYER>(&[empty, empty, empty, leaves[3]], 2)
+            .unwrap();
+        let root_before = tree.get_root();
+        assert_eq!(
+            tree.insert_at_with_proof(0, leaves[0], &proof),
+            Err(BrineTreeError::InvalidProof)
+        );
+        assert_eq!(tree.get_root(), root_before);
+
+        println!("✅ Insert at index test passed");
+    }
+
+    #[test]
+    fn test_verify_at_rejects_wrong_index() {
+        const HEIGHT: usize = 4;
+
+        let segments: Vec<[u8; 8]> = (0..4u8).map(|i| [i; 8]).collect();
+        let leaves: Vec<Leaf> = segments
+            .iter()
+            .enumerate()
+            .map(|(i, segment)| Leaf::from_segment(i as u64, segment))
+            .collect();
+
+        let mut tree = MerkleTree::<HEIGHT>::from_zeros(create_zero_values::<HEIGHT>());
+        for leaf in &leaves {
+            tree.try_add_leaf(*leaf).unwrap();
+        }
+
+        let root = tree.get_root();
+        let proof = tree.get_proof_no_std::<MAX_LAYER>(&leaves, 2).unwrap();
+
+        assert!(verify_at(root, &proof, &segments[2], 2));
+
+        // The sorted verification never sees the claimed index, so it accepts
+        // the proof whichever index the caller had in mind
+        assert!(verify_no_std(root, &proof, leaves[2]));
+        assert!(!verify_at(root, &proof, &segments[2], 3));
+        assert!(!verify_at(root, &proof, &segments[2], 0));
+
+        // Nor can the index wrap around past the tree's capacity
+        assert!(!verify_at(root, &proof, &segments[2], 2 + (1 << HEIGHT)));
+
+        println!("✅ Index-aware verification test passed");
+    }
+
+    #[test]
+    fn test_get_proof_no_std_every_index_full_tree() {
+        const HEIGHT: usize = 4;
+        let leaves = create_test_leaves(1 << HEIGHT);
+
+        let mut tree = MerkleTree::<HEIGHT>::new(&[b"test_zero"]);
+        for leaf in &leaves {
+            tree.try_add_leaf(*leaf).unwrap();
+        }
+        let root = tree.get_root();
+
+        // Every slot of a full tree must prove against the appended root
+        let verified = (0..leaves.len())
+            .filter(|&i| {
+                verify_no_std(
+                    root,
+                    &tree.get_proof_no_std::<MAX_LAYER>(&leaves, i).unwrap(),
+                    leaves[i],
+                )
+            })
+            .count();
+        assert_eq!(verified, leaves.len());
+
+        println!("✅ Exhaustive proof test passed");
+    }
+
+    #[test]
+    fn test_get_proofs_no_std_matches_single_proofs() {
+        const HEIGHT: usize = 4;
+        let leaves = create_test_leaves(11);
+
+        let mut tree = MerkleTree::<HEIGHT>::new(&[b"test_zero"]);
+        for leaf in &leaves {
+            tree.try_add_leaf(*leaf).unwrap();
+        }
+
+        // One pass over the layers yields the same proofs as three separate calls
+        let indices = [0, 5, 10];
+        let proofs = tree
+            .get_proofs_no_std::<MAX_LAYER, 3>(&leaves, &indices)
+            .unwrap();
+        for (proof, &i) in proofs.iter().zip(indices.iter()) {
+            assert_eq!(
+                *proof,
+                tree.get_proof_no_std::<MAX_LAYER>(&leaves, i).unwrap()
+            );
+            assert!(verify_no_std(tree.get_root(), proof, leaves[i]));
+        }
+
+        assert_eq!(
+            tree.get_proofs_no_std::<MAX_LAYER, 2>(&leaves, &[0, leaves.len()]),
+            Err(BrineTreeError::InvalidArgument)
+        );
+    }
+
+    /// Small enough to run under `cargo miri test`, which flags any read of a
+    /// layer slot that was never written
+    #[test]
+    fn test_no_std_helpers_odd_leaf_counts() {
+        const HEIGHT: usize = 4;
+        const BUFFER: usize = 9;
+
+        for count in [1, 3, 5, 7, 9] {
+            let leaves = create_test_leaves(count);
+            let mut tree = MerkleTree::<HEIGHT>::new(&[b"test_zero"]);
+            for leaf in &leaves {
+                tree.try_add_leaf(*leaf).unwrap();
+            }
+            let root = tree.get_root();
+
+            for (i, leaf) in leaves.iter().enumerate() {
+                let proof = tree.get_proof_no_std::<BUFFER>(&leaves, i).unwrap();
+                assert!(
+                    verify_no_std(root, &proof, *leaf),
+                    "{count} leaves, index {i}"
+                );
+            }
+
+            let (written, nodes) = tree
+                .get_layer_nodes_no_std::<1, BUFFER>(&leaves, HEIGHT)
+                .unwrap();
+            assert_eq!((written, nodes[0]), (1, root), "{count} leaves");
+
+            // Past the last of several leaves there is no sibling to read
+            if count > 1 {
+                assert_eq!(
+                    tree.get_proof_no_std::<BUFFER>(&leaves, count),
+                    Err(BrineTreeError::InvalidArgument)
+                );
+            }
+        }
+    }
+
+    /// Checks a batched append against the per-leaf path, starting from a
+    /// partially filled tree so runs straddle existing subtrees
+    fn assert_batch_matches_sequential<const N: usize>(prefill: usize, batch: usize) {
+        let leaves = create_test_leaves(prefill + batch);
+        let zero_values = create_zero_values::<N>();
+
+        let mut batched = MerkleTree::<N>::from_zeros(zero_values);
+        let mut sequential = MerkleTree::<N>::from_zeros(zero_values);
+        for leaf in &leaves[..prefill] {
+            batched.try_add_leaf(*leaf).unwrap();
+            sequential.try_add_leaf(*leaf).unwrap();
+        }
+
+        batched.try_add_leaves(&leaves[prefill..]).unwrap();
+        for leaf in &leaves[prefill..] {
+            sequential.try_add_leaf(*leaf).unwrap();
+        }
+
+        // Whole-struct equality also covers filled_subtrees for later appends
+        assert_eq!(
+            batched, sequential,
+            "N={} prefill={} batch={}",
+            N, prefill, batch
+        );
+    }
+
+    #[test]
+    fn test_try_add_leaves_matches_sequential() {
+        for prefill in 0..=8 {
+            for batch in 0..=(8 - prefill) {
+                assert_batch_matches_sequential::<3>(prefill, batch);
+            }
+        }
+
+        for (prefill, batch) in [(0, 1), (0, 16), (1, 15), (3, 7), (5, 11)] {
+            assert_batch_matches_sequential::<4>(prefill, batch);
+        }
+
+        for (prefill, batch) in [(0, 100), (1, 64), (7, 300), (511, 513), (0, 1024)] {
+            assert_batch_matches_sequential::<10>(prefill, batch);
+        }
+
+        println!("✅ Batched append test passed");
+    }
+
+    #[test]
+    fn test_try_add_leaves_overflow_is_atomic() {
+        let leaves = create_test_leaves(9);
+        let mut tree: MerkleTree<3> = MerkleTree::new(&[b"test"]);
+        tree.try_add_leaves(&leaves[..5]).unwrap();
+        let before = tree;
+
+        // Four more would need a ninth slot, so nothing is appended
+        assert_eq!(
+            tree.try_add_leaves(&leaves[5..]),
+            Err(BrineTreeError::TreeFull)
+        );
+        assert_eq!(tree, before);
+
+        // Exactly filling the tree still works, and an empty run is a no-op
+        tree.try_add_leaves(&leaves[5..8]).unwrap();
+        assert_eq!(tree.get_leaf_count(), 8);
+        tree.try_add_leaves(&[]).unwrap();
+        assert_eq!(
+            tree.try_add_leaves(&leaves[8..]),
+            Err(BrineTreeError::TreeFull)
+        );
+    }
+
+    #[test]
+    fn test_update_proof_no_std_tracks_random_writes() {
+        const HEIGHT: usize = 6;
+        const LEAF_COUNT: usize = 40;
+
+        let zero_values = create_zero_values::<HEIGHT>();
+        let mut leaves = create_test_leaves(LEAF_COUNT);
+        let root_of = |leaves: &[Leaf]| {
+            let mut tree = MerkleTree::<HEIGHT>::from_zeros(zero_values);
+            tree.try_add_leaves(leaves).unwrap();
+            tree.get_root()
+        };
+
+        // Track a proof for every written leaf
+        let mut proofs: Vec<[Hash; HEIGHT]> = (0..LEAF_COUNT)
+            .map(|i| {
+                get_merkle_proof_no_std::<HEIGHT, MAX_LAYER>(&leaves, &zero_values, i).unwrap()
+            })
+            .collect();
+
+        // Deterministic xorshift so failures reproduce
+        let mut seed = 0x9e37_79b9_7f4a_7c15u64;
+        let mut next = || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        };
+
+        for round in 0..50 {
+            let changed = next() as usize % LEAF_COUNT;
+            let old_leaf = leaves[changed];
+            let new_leaf = Leaf::new(&[format!("write_{}", round).as_bytes()]);
+            leaves[changed] = new_leaf;
+
+            let changed_proof = proofs[changed];
+            for (i, proof) in proofs.iter_mut().enumerate() {
+                let affected =
+                    update_proof_no_std(proof, i, changed, &changed_proof, old_leaf, new_leaf)
+                        .unwrap();
+                assert_eq!(affected, i != changed);
+            }
+
+            let root = root_of(&leaves);
+            for (i, proof) in proofs.iter().enumerate() {
+                assert_eq!(
+                    *proof,
+                    get_merkle_proof_no_std::<HEIGHT, MAX_LAYER>(&leaves, &zero_values, i).unwrap()
+                );
+                assert!(verify_no_std(root, proof, leaves[i]));
+            }
+        }
+
+        println!("✅ Incremental proof update test passed");
+    }
+
+    #[test]
+    fn test_update_proof_no_std_rejects_mismatched_leaf() {
+        const HEIGHT: usize = 3;
+
+        let zero_values = create_zero_values::<HEIGHT>();
+        let leaves = create_test_leaves(8);
+        let mut proof: [Hash; HEIGHT] =
+            get_merkle_proof_no_std::<HEIGHT, MAX_LAYER>(&leaves, &zero_values, 0).unwrap();
+        let changed_proof: [Hash; HEIGHT] =
+            get_merkle_proof_no_std::<HEIGHT, MAX_LAYER>(&leaves, &zero_values, 5).unwrap();
+        let original = proof;
+
+        // A stale old leaf means the caller's proofs disagree, leave the proof as is
+        assert_eq!(
+            update_proof_no_std(&mut proof, 0, 5, &changed_proof, leaves[4], leaves[0]),
+            Err(BrineTreeError::InvalidProof)
+        );
+        assert_eq!(proof, original);
+
+        // Rewriting a leaf with its current value is not a change
+        assert_eq!(
+            update_proof_no_std(&mut proof, 0, 5, &changed_proof, leaves[5], leaves[5]),
+            Ok(false)
+        );
+        assert_eq!(
+            update_proof_no_std(&mut proof, 0, 8, &changed_proof, leaves[5], leaves[0]),
+            Err(BrineTreeError::InvalidArgument)
+        );
+    }
+
+    #[test]
+    fn test_no_std_helpers_beyond_4096_leaves() {
+        const HEIGHT: usize = 18; // SEGMENT_TREE_HEIGHT
+        const LEAF_COUNT: usize = 5000;
+        const BUFFER: usize = 8192;
+
+        let leaves = create_test_leaves(LEAF_COUNT);
+        let mut tree = MerkleTree::<HEIGHT>::from_zeros(SEGMENT_TREE_ZEROS_18);
+        for leaf in &leaves {
+            tree.try_add_leaf(*leaf).unwrap();
+        }
+        let root = tree.get_root();
+
+        // Leaves past the old 4096 cap used to be dropped, giving proofs that
+        // missed the appended root
+        for leaf_index in [0, 4095, 4096, LEAF_COUNT - 1] {
+            let proof = tree
+                .get_proof_no_std::<BUFFER>(&leaves, leaf_index)
+                .unwrap();
+            assert!(verify_no_std(root, &proof, leaves[leaf_index]));
+        }
+
+        let (count, nodes) = tree
+            .get_layer_nodes_no_std::<1, BUFFER>(&leaves, HEIGHT)
+            .unwrap();
+        assert_eq!((count, nodes[0]), (1, root));
+
+        // An undersized buffer is an error rather than a proof for a smaller tree
+        assert_eq!(
+            tree.get_proof_no_std::<4096>(&leaves, 0),
+            Err(BrineTreeError::BufferTooSmall)
+        );
+        assert_eq!(
+            tree.get_layer_nodes_no_std::<1, 4096>(&leaves, HEIGHT),
+            Err(BrineTreeError::BufferTooSmall)
+        );
+
+        println!("✅ Large tree no-std helpers test passed");
+    }
+
+    #[test]
+    fn test_recall_proof_past_index_4095_verifies() {
+        const HEIGHT: usize = 18;
+        const LEAF_COUNT: usize = 5000;
+        const RECALL_INDEX: usize = 4100;
+
+        let leaves = create_test_leaves(LEAF_COUNT);
+        let zero_values = create_zero_values::<HEIGHT>();
+        let mut tree = MerkleTree::<HEIGHT>::from_zeros(zero_values);
+        tree.try_add_leaves(&leaves).unwrap();
+
+        // A segment past the old 4096 leaf cap must still prove against the
+        // on-chain root, or a miner recalling it could never be rewarded
+        let proof =
+            get_merkle_proof_no_std::<HEIGHT, LEAF_COUNT>(&leaves, &zero_values, RECALL_INDEX)
+                .unwrap();
+        assert!(verify_no_std(tree.get_root(), &proof, leaves[RECALL_INDEX]));
+        assert!(tree.contains_leaf_no_std(&proof, leaves[RECALL_INDEX]));
+
+        // Too small a buffer is reported instead of proving a truncated tree
+        assert_eq!(
+            get_merkle_proof_no_std::<HEIGHT, 4096>(&leaves, &zero_values, RECALL_INDEX),
+            Err(BrineTreeError::BufferTooSmall)
+        );
+    }
+
+    #[test]
+    fn test_byte_snapshot_round_trip() {
+        const HEIGHT: usize = 10;
+
+        let leaves = create_test_leaves(24);
+        let mut tree = MerkleTree::<HEIGHT>::new(&[b"snapshot"]);
+        tree.try_add_leaves(&leaves[..11]).unwrap();
+
+        // Persist the snapshot the way an indexer would, into a u64-aligned buffer
+        let size = MerkleTree::<HEIGHT>::get_size();
+        let bytes = tree.to_bytes();
+        assert_eq!(bytes.len(), size);
+
+        let mut storage = vec![0u64; size / 8 + 1];
+        let buffer: &mut [u8] = bytemuck::cast_slice_mut(&mut storage);
+        buffer[..size].copy_from_slice(bytes);
+
+        let mut reloaded = *MerkleTree::<HEIGHT>::try_from_bytes(&buffer[..size]).unwrap();
+        assert_eq!(reloaded, tree);
+
+        // The reloaded tree keeps growing exactly like the original
+        for leaf in &leaves[11..] {
+            tree.try_add_leaf(*leaf).unwrap();
+            reloaded.try_add_leaf(*leaf).unwrap();
+            assert_eq!(reloaded.get_root(), tree.get_root());
+        }
+        assert_eq!(reloaded, tree);
+
+        // Wrong lengths and misaligned buffers are rejected rather than misread
+        let invalid = [
+            &buffer[..size - 1],
+            &buffer[..size + 1],
+            &buffer[1..size + 1],
+        ];
+        for bytes in invalid {
+            assert_eq!(
+                MerkleTree::<HEIGHT>::try_from_bytes(bytes),
+                Err(BrineTreeError::InvalidArgument)
+            );
+        }
+    }
+
+    #[test]
+    fn test_verify_many_no_std_matches_individual() {
+        const HEIGHT: usize = 6;
+
+        let leaves = create_test_leaves(20);
+        let mut tree = MerkleTree::<HEIGHT>::from_zeros(create_zero_values::<HEIGHT>());
+        tree.try_add_leaves(&leaves).unwrap();
+        let root = tree.get_root();
+
+        let proofs: Vec<[Hash; HEIGHT]> = (0..leaves.len())
+            .map(|i| tree.get_proof_no_std::<MAX_LAYER>(&leaves, i).unwrap())
+            .collect();
+        let stranger = Leaf::new(&[b"not in the tree"]);
+
+        let check = |indices: &[usize], tamper: Option<usize>| {
+            let proof_refs: Vec<&[Hash]> = indices.iter().map(|&i| &proofs[i][..]).collect();
+            let mut batch: Vec<Leaf> = indices.iter().map(|&i| leaves[i]).collect();
+            if let Some(position) = tamper {
+                batch[position] = stranger;
+            }
+
+            let individually = proof_refs
+                .iter()
+                .zip(&batch)
+                .all(|(proof, leaf)| verify_no_std(root, proof, *leaf));
+            assert_eq!(
+                verify_many_no_std(root, &proof_refs, &batch),
+                individually,
+                "indices={:?} tamper={:?}",
+                indices,
+                tamper
+            );
+            individually
+        };
+
+        let batches: [&[usize]; 5] = [
+            &[7],
+            &[4, 5],
+            &[5, 4],
+            &[0, 1, 2, 3, 12, 13, 18, 19],
+            &[19, 0, 9, 9, 2, 17, 6, 11],
+        ];
+        for indices in batches {
+            assert!(check(indices, None));
+            for position in 0..indices.len() {
+                assert!(!check(indices, Some(position)));
+            }
+        }
+
+        // A sibling swapped for the previous leaf must not ride on its path
+        let swapped = [&proofs[4][..], &proofs[4][..]];
+        assert!(!verify_many_no_std(root, &swapped, &[leaves[4], leaves[5]]));
+        assert!(!verify_many_no_std(root, &[&proofs[0][..]], &[]));
+        assert!(verify_many_no_std(root, &[], &[]));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_leaves_iter_rebuilds_root() {
+        const HEIGHT: usize = 6;
+        const K: usize = 11;
+
+        let mut tree = MerkleTree::<HEIGHT>::from_zeros(create_zero_values::<HEIGHT>());
+        let leaves = create_test_leaves(K);
+        for leaf in &leaves {
+            tree.try_add_leaf(*leaf).unwrap();
+        }
+
+        // Account-style storage: the leaves followed by empty slots
+        let mut stored = leaves.clone();
+        stored.resize(tree.get_capacity() as usize, tree.get_empty_leaf());
+
+        assert_eq!(tree.leaves_iter(&stored).count(), K);
+
+        let rebuilt = tree.leaves_iter(&stored).fold(
+            MerkleTree::<HEIGHT>::from_zeros(tree.zero_values),
+            |mut acc, leaf| {
+                acc.try_add_leaf(*leaf).unwrap();
+                acc
+            },
+        );
+        assert_eq!(rebuilt.get_root(), tree.get_root());
+    }
+
+    #[test]
+    fn test_try_add_leaf_indexed_returns_sequential_indices() {
+        const HEIGHT: usize = 3;
+
+        let mut tree = MerkleTree::<HEIGHT>::from_zeros(create_zero_values::<HEIGHT>());
+        let leaves = create_test_leaves(9);
+
+        for (i, leaf) in leaves[..8].iter().enumerate() {
+            assert_eq!(tree.try_add_leaf_indexed(*leaf), Ok(i as u64));
+        }
+
+        let root = tree.get_root();
+        assert_eq!(
+            tree.try_add_leaf_indexed(leaves[8]),
+            Err(BrineTreeError::TreeFull)
+        );
+        assert_eq!(tree.get_leaf_count(), 8);
+        assert_eq!(tree.get_root(), root);
+    }
+
+    #[test]
+    fn test_wrong_proof_length_reports_both_lengths() {
+        const HEIGHT: usize = 18;
+
+        let mut tree = MerkleTree::<HEIGHT>::from_zeros(create_zero_values::<HEIGHT>());
+        let leaf = create_test_leaves(1)[0];
+        tree.try_add_leaf(leaf).unwrap();
+
+        // A proof built for a TAPE_TREE_HEIGHT tree instead of the segment tree
+        let short_proof = [Hash::default(); 10];
+        assert_eq!(
+            tree.try_remove_leaf_no_std(&short_proof, leaf),
+            Err(BrineTreeError::ProofLength {
+                expected: 18,
+                got: 10
+            })
+        );
+
+        assert_eq!(
+            check_proof_length(300, HEIGHT),
+            Err(BrineTreeError::ProofLength {
+                expected: 18,
+                got: u8::MAX
+            })
+        );
+        assert_eq!(check_proof_length(HEIGHT, HEIGHT), Ok(()));
+    }
+
+    #[test]
+    fn test_ordered_and_sorted_trees_differ() {
+        const HEIGHT: usize = 4;
+
+        let zeros = create_zero_values::<HEIGHT>();
+        let leaves = create_test_leaves(6);
+
+        let mut sorted = MerkleTree::<HEIGHT>::from_zeros(zeros);
+        let mut ordered = OrderedMerkleTree::<HEIGHT>::from_zeros(zeros);
+        sorted.try_add_leaves(&leaves).unwrap();
+        for leaf in &leaves {
+            ordered.try_add_leaf(*leaf).unwrap();
+        }
+
+        // Same layout and leaf count, different roots
+        assert_eq!(ordered.to_bytes().len(), sorted.to_bytes().len());
+        assert_eq!(ordered.get_leaf_count(), sorted.get_leaf_count());
+        assert_ne!(ordered.get_root(), sorted.get_root());
+
+        // Two leaves hashed in order, padded with empty subtrees up to the root
+        let mut two = OrderedMerkleTree::<HEIGHT>::from_zeros(zeros);
+        two.try_add_leaves(&leaves[..2]).unwrap();
+        let mut expected = Ordered::hash_nodes(Hash::from(leaves[0]), Hash::from(leaves[1]));
+        for zero in &zeros[1..] {
+            expected = Ordered::hash_nodes(expected, *zero);
+        }
+        assert_eq!(two.get_root(), expected);
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let sorted_proof = sorted
+                .get_proof_no_std::<MAX_LAYER>(&leaves, index)
+                .unwrap();
+            let ordered_proof = ordered
+                .get_proof_no_std::<MAX_LAYER>(&leaves, index)
+                .unwrap();
+
+            // Each proof only verifies in its own mode
+            assert!(verify_no_std(sorted.get_root(), &sorted_proof, *leaf));
+            assert!(ordered.contains_leaf_at(&ordered_proof, *leaf, index as u64));
+            assert!(!verify_no_std(ordered.get_root(), &ordered_proof, *leaf));
+            assert!(!verify_ordered_no_std(
+                sorted.get_root(),
+                &sorted_proof,
+                *leaf,
+                index as u64
+            ));
+
+            // The ordered proof is bound to the leaf's position
+            let other = (index as u64 + 1) % leaves.len() as u64;
+            assert!(!ordered.contains_leaf_at(&ordered_proof, *leaf, other));
+        }
+    }
+
+    #[test]
+    fn test_root_from_proof_matches_tree_root() {
+        const HEIGHT: usize = 5;
+
+        let mut tree = MerkleTree::<HEIGHT>::from_zeros(create_zero_values::<HEIGHT>());
+        let leaves = create_test_leaves(13);
+        tree.try_add_leaves(&leaves).unwrap();
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = tree.get_proof_no_std::<MAX_LAYER>(&leaves, index).unwrap();
+            assert_eq!(root_from_proof_no_std(&proof, *leaf), tree.get_root());
+        }
+
+        // A leaf that is not in the tree reaches some other root
+        let proof = tree.get_proof_no_std::<MAX_LAYER>(&leaves, 0).unwrap();
+        let stranger = Leaf::new(&[b"stranger".as_ref()]);
+        assert_ne!(root_from_proof_no_std(&proof, stranger), tree.get_root());
+    }
 }